@@ -6,6 +6,14 @@ const DATA: &[u8] = include_bytes!("../src/lib.rs");
 fn bench_encode(c: &mut Criterion) {
     c.bench_function("hex_encode", |b| b.iter(|| hex::encode(DATA)));
 
+    c.bench_function("hex_encode_hex_collect", |b| {
+        b.iter(|| hex::ToHex::encode_hex::<String>(&DATA))
+    });
+
+    c.bench_function("hex_encode_hex_string", |b| {
+        b.iter(|| hex::ToHex::encode_hex_string(&DATA, hex::Case::Lower))
+    });
+
     c.bench_function("rustc_hex_encode", |b| b.iter(|| DATA.to_hex::<String>()));
 
     c.bench_function("faster_hex_encode", |b| {
@@ -57,5 +65,18 @@ fn bench_decode(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_encode, bench_decode);
+fn bench_fixed(c: &mut Criterion) {
+    let digest = [0xa5_u8; 32];
+    c.bench_function("hex_encode_32", |b| {
+        let mut out = [0; 64];
+        b.iter(|| hex::encode_to_slice(digest, &mut out).map(|_| ()).unwrap())
+    });
+
+    c.bench_function("hex_decode_32", |b| {
+        let hex = hex::encode(digest);
+        b.iter(|| <[u8; 32] as hex::FromHex>::from_hex(&hex).unwrap())
+    });
+}
+
+criterion_group!(benches, bench_encode, bench_decode, bench_fixed);
 criterion_main!(benches);
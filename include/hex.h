@@ -0,0 +1,55 @@
+/* C interface of the `hex` crate, built with the `ffi` feature. */
+
+#ifndef HEX_H
+#define HEX_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The operation succeeded. */
+#define HEX_OK 0
+/* The input contains a character which is not a hex digit. */
+#define HEX_ERR_INVALID_CHAR -1
+/* The input has an odd length. */
+#define HEX_ERR_ODD_LENGTH -2
+/* The output buffer doesn't have the required length. */
+#define HEX_ERR_INVALID_LENGTH -3
+/* A required pointer was null. */
+#define HEX_ERR_NULL -4
+
+/* Returns the length of the hex encoding of `len` bytes, or 0 on overflow. */
+size_t hex_encoded_len(size_t len);
+
+/* Returns the number of bytes encoded by `len` hex digits. */
+size_t hex_decoded_len(size_t len);
+
+/*
+ * Encodes `src_len` bytes at `src` as lowercase hex into `dst`.
+ *
+ * `dst_len` has to be exactly `hex_encoded_len(src_len)`. No terminating null
+ * byte is written. `src` and `dst` must not overlap.
+ */
+int32_t hex_encode(const uint8_t *src, size_t src_len, uint8_t *dst, size_t dst_len);
+
+/* Like `hex_encode`, but using uppercase letters. */
+int32_t hex_encode_upper(const uint8_t *src, size_t src_len, uint8_t *dst, size_t dst_len);
+
+/*
+ * Decodes `src_len` hex digits at `src` into `dst`.
+ *
+ * `dst_len` has to be exactly `hex_decoded_len(src_len)`. If `src` contains an
+ * invalid character and `error_index` isn't null, the index of the character
+ * is stored in `*error_index`. `src` and `dst` must not overlap.
+ */
+int32_t hex_decode(const uint8_t *src, size_t src_len, uint8_t *dst, size_t dst_len,
+                   size_t *error_index);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* HEX_H */
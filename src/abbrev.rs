@@ -0,0 +1,210 @@
+//! Abbreviation of hashes to their shortest unambiguous hex prefix.
+//!
+//! Like git's short object IDs, an abbreviated hash has just enough digits
+//! to tell it apart from every other hash it is shown with, which keeps
+//! listings of many hashes compact. Prefixes are counted in digits, so they
+//! may end in the middle of a byte. Use [`nibble::starts_with_hex_prefix`]
+//! to resolve them again.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! let hashes = [[0xde, 0xad, 0xbe, 0xef], [0xde, 0xaf, 0x00, 0x00], [0x12, 0x34, 0x56, 0x78]];
+//! assert_eq!(hex::abbrev::unique_prefixes(&hashes), ["dead", "deaf", "1"]);
+//! # }
+//! ```
+//!
+//! [`nibble::starts_with_hex_prefix`]: crate::nibble::starts_with_hex_prefix
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
+
+// Returns the number of leading nibbles `a` and `b` have in common.
+fn common_nibbles(a: &[u8], b: &[u8]) -> usize {
+    match a.iter().zip(b).position(|(x, y)| x != y) {
+        Some(i) => 2 * i + usize::from(a[i] >> 4 == b[i] >> 4),
+        None => 2 * a.len().min(b.len()),
+    }
+}
+
+/// Returns the number of digits of the shortest hex prefix of `bytes` which
+/// none of the `existing` hashes start with.
+///
+/// Entries of `existing` equal to `bytes` are ignored, so it may contain
+/// `bytes` itself. If `bytes` is a prefix of one of them, the length of the
+/// whole of `bytes` is returned. Callers that want a minimum length, like
+/// the 7 digits git uses, take the maximum of that and the result.
+///
+/// # Example
+///
+/// ```
+/// use hex::abbrev::unique_prefix_len;
+///
+/// let existing = [[0xde, 0xad, 0xbe, 0xef], [0xde, 0xaf, 0x00, 0x00]];
+/// assert_eq!(unique_prefix_len(&[0xde, 0xad, 0x00, 0x00], &existing), 5);
+/// assert_eq!(unique_prefix_len(&[0xd0, 0x00, 0x00, 0x00], &existing), 2);
+/// assert_eq!(unique_prefix_len(&[0x00, 0x00, 0x00, 0x00], &existing), 1);
+/// ```
+pub fn unique_prefix_len<I>(bytes: &[u8], existing: I) -> usize
+where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    let common = existing
+        .into_iter()
+        .filter(|other| other.as_ref() != bytes)
+        .map(|other| common_nibbles(bytes, other.as_ref()))
+        .max();
+    match common {
+        Some(common) => (common + 1).min(2 * bytes.len()),
+        None => usize::from(!bytes.is_empty()),
+    }
+}
+
+/// Returns the shortest hex prefix of `bytes` which none of the `existing`
+/// hashes start with, in lowercase.
+///
+/// See [`unique_prefix_len`] for details.
+///
+/// # Example
+///
+/// ```
+/// let existing = [[0xde, 0xad, 0xbe, 0xef], [0xde, 0xaf, 0x00, 0x00]];
+/// assert_eq!(hex::abbrev::abbreviate(&[0xde, 0xad, 0xbe, 0xef], &existing), "dead");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn abbreviate<I>(bytes: &[u8], existing: I) -> String
+where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    let len = unique_prefix_len(bytes, existing);
+    let mut hex = crate::encode(bytes);
+    hex.truncate(len);
+    hex
+}
+
+/// Returns the number of digits of the shortest unambiguous hex prefix of
+/// every hash in `hashes`, in the same order.
+///
+/// This is equivalent to calling [`unique_prefix_len`] for every hash with
+/// all of `hashes` as `existing`, but takes `O(n log n)` instead of `O(n²)`
+/// time.
+///
+/// # Example
+///
+/// ```
+/// let hashes = [[0x12, 0x34], [0x12, 0x56], [0xff, 0xff]];
+/// assert_eq!(hex::abbrev::unique_prefix_lens(&hashes), [3, 3, 1]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn unique_prefix_lens<T: AsRef<[u8]>>(hashes: &[T]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..hashes.len()).collect();
+    order.sort_unstable_by_key(|&i| hashes[i].as_ref());
+
+    // In sorted order, the longest common prefix with any other hash is the
+    // one with a neighbour, skipping over duplicates.
+    let mut lens = vec![0; hashes.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let bytes = hashes[order[start]].as_ref();
+        let end = start
+            + order[start..]
+                .iter()
+                .take_while(|&&i| hashes[i].as_ref() == bytes)
+                .count();
+        let neighbours = [
+            start.checked_sub(1),
+            Some(end).filter(|&end| end < order.len()),
+        ];
+        let len = unique_prefix_len(
+            bytes,
+            neighbours
+                .iter()
+                .flatten()
+                .map(|&neighbour| hashes[order[neighbour]].as_ref()),
+        );
+        for &i in &order[start..end] {
+            lens[i] = len;
+        }
+        start = end;
+    }
+    lens
+}
+
+/// Returns the shortest unambiguous hex prefix of every hash in `hashes`,
+/// in lowercase and in the same order.
+///
+/// See [`unique_prefix_lens`] for details.
+///
+/// # Example
+///
+/// ```
+/// let hashes = [[0x12, 0x34], [0x12, 0x56], [0xff, 0xff]];
+/// assert_eq!(hex::abbrev::unique_prefixes(&hashes), ["123", "125", "f"]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn unique_prefixes<T: AsRef<[u8]>>(hashes: &[T]) -> Vec<String> {
+    hashes
+        .iter()
+        .zip(unique_prefix_lens(hashes))
+        .map(|(bytes, len)| {
+            let mut hex = crate::encode(bytes);
+            hex.truncate(len);
+            hex
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_unique_prefix_len() {
+        let none: [&[u8]; 0] = [];
+        assert_eq!(unique_prefix_len(&[0xab], none), 1);
+        assert_eq!(unique_prefix_len(&[], none), 0);
+        assert_eq!(unique_prefix_len(&[0xab], [[0xab]]), 1);
+        assert_eq!(unique_prefix_len(&[0xab], [[0xac]]), 2);
+        assert_eq!(unique_prefix_len(&[0xab, 0xcd], [[0xbb, 0xcd]]), 1);
+        // a prefix of another hash can't be told apart by any prefix
+        assert_eq!(unique_prefix_len(&[0xab], [&[0xab, 0xcd][..]]), 2);
+        assert_eq!(unique_prefix_len(&[0xab, 0xcd], [&[0xab][..]]), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_unique_prefix_lens() {
+        let empty: [[u8; 2]; 0] = [];
+        assert_eq!(unique_prefix_lens(&empty), Vec::<usize>::new());
+        assert_eq!(unique_prefix_lens(&[[0x12, 0x34]]), [1]);
+        // duplicates share the length of their distinct neighbours
+        assert_eq!(
+            unique_prefix_lens(&[[0x12, 0x34], [0x12, 0x35], [0x12, 0x34], [0x00, 0x00]]),
+            [4, 4, 4, 1]
+        );
+
+        let hashes: Vec<[u8; 3]> = (0..200u32)
+            .map(|i| {
+                let x = i.wrapping_mul(0x9e37_79b9);
+                [(x >> 24) as u8, (x >> 16) as u8, (x >> 8) as u8]
+            })
+            .collect();
+        let lens = unique_prefix_lens(&hashes);
+        for (bytes, len) in hashes.iter().zip(lens) {
+            assert_eq!(len, unique_prefix_len(bytes, &hashes));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_abbreviate() {
+        let existing = [[0xde, 0xad, 0xbe, 0xef], [0xde, 0xaf, 0x00, 0x00]];
+        assert_eq!(abbreviate(&[0xde, 0xad, 0xbe, 0xef], existing), "dead");
+        assert_eq!(abbreviate(&[0x12, 0x34, 0x56, 0x78], existing), "1");
+        assert_eq!(unique_prefixes(&existing), ["dead", "deaf"]);
+    }
+}
@@ -0,0 +1,302 @@
+use core::{borrow::Borrow, iter::FusedIterator};
+
+use crate::{Case, FromHexError, DECODE_TABLE};
+
+/// An extension trait making hex encoding and decoding a step in iterator
+/// pipelines.
+///
+/// This is implemented for all iterators. Slice iterators implement
+/// [`ToHex`](crate::ToHex) as well, but as these methods take the iterator by
+/// value, method calls resolve to them even if both traits are in scope.
+///
+/// # Example
+///
+/// ```
+/// use hex::IterHexExt;
+///
+/// let encoded: String = (0..4_u8).map(|i| i * 0x11).encode_hex().collect();
+/// assert_eq!(encoded, "00112233");
+///
+/// let decoded: Result<Vec<u8>, _> = encoded.chars().rev().decode_hex().collect();
+/// assert_eq!(decoded, Ok(vec![0x33, 0x22, 0x11, 0x00]));
+/// ```
+pub trait IterHexExt: Iterator + Sized {
+    /// Encodes the bytes yielded by `self`, using lowercase characters.
+    fn encode_hex(self) -> EncodeHex<Self>
+    where
+        Self::Item: Borrow<u8>,
+    {
+        EncodeHex::new(self, Case::Lower)
+    }
+
+    /// Encodes the bytes yielded by `self`, using uppercase characters.
+    fn encode_hex_upper(self) -> EncodeHex<Self>
+    where
+        Self::Item: Borrow<u8>,
+    {
+        EncodeHex::new(self, Case::Upper)
+    }
+
+    /// Decodes the hex characters yielded by `self`.
+    ///
+    /// See [`HexToBytes`] for the details.
+    fn decode_hex(self) -> HexToBytes<Self>
+    where
+        Self::Item: Into<char>,
+    {
+        HexToBytes::new(self)
+    }
+}
+
+impl<I: Iterator> IterHexExt for I {}
+
+/// An iterator adaptor which lazily encodes the bytes of another iterator as
+/// hex characters.
+///
+/// This struct is created by [`IterHexExt::encode_hex`] and
+/// [`IterHexExt::encode_hex_upper`].
+#[derive(Debug, Clone)]
+pub struct EncodeHex<I> {
+    inner: I,
+    table: &'static [u8; 16],
+    next: Option<char>,
+}
+
+impl<I> EncodeHex<I>
+where
+    I: Iterator,
+    I::Item: Borrow<u8>,
+{
+    /// Creates an adaptor encoding the bytes yielded by `iter` using `case`.
+    pub fn new<T: IntoIterator<IntoIter = I>>(iter: T, case: Case) -> Self {
+        EncodeHex {
+            inner: iter.into_iter(),
+            table: case.table(),
+            next: None,
+        }
+    }
+}
+
+impl<I> Iterator for EncodeHex<I>
+where
+    I: Iterator,
+    I::Item: Borrow<u8>,
+{
+    type Item = char;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next.take() {
+            Some(current) => Some(current),
+            None => self.inner.next().map(|byte| {
+                let byte = *byte.borrow();
+                self.next = Some(self.table[(byte & 0x0F) as usize] as char);
+                self.table[(byte >> 4) as usize] as char
+            }),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let extra = self.next.is_some() as usize;
+        let (lower, upper) = self.inner.size_hint();
+        (
+            lower.saturating_mul(2).saturating_add(extra),
+            upper.and_then(|upper| upper.checked_mul(2)?.checked_add(extra)),
+        )
+    }
+}
+
+impl<I> ExactSizeIterator for EncodeHex<I>
+where
+    I: ExactSizeIterator,
+    I::Item: Borrow<u8>,
+{
+}
+
+impl<I> FusedIterator for EncodeHex<I>
+where
+    I: FusedIterator,
+    I::Item: Borrow<u8>,
+{
+}
+
+/// An iterator adaptor which lazily decodes hex read from another iterator.
+///
+/// The input can be given as bytes or as `char`s. Every pair of digits yields
+/// one byte. An invalid character yields a
+/// [`FromHexError::InvalidHexCharacter`], and a single digit left over at the
+/// end a [`FromHexError::OddLength`]; the iterator ends after an error.
+///
+/// # Example
+///
+/// ```
+/// use hex::{FromHexError, HexToBytes};
+///
+/// let decoded = HexToBytes::new("6b697769".bytes()).collect::<Result<Vec<u8>, _>>();
+/// assert_eq!(decoded, Ok(b"kiwi".to_vec()));
+///
+/// let mut odd = HexToBytes::new("abc".bytes());
+/// assert_eq!(odd.next(), Some(Ok(0xab)));
+/// assert_eq!(odd.next(), Some(Err(FromHexError::OddLength)));
+/// assert_eq!(odd.next(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HexToBytes<I> {
+    inner: I,
+    // The index of the next character, or `None` after an error.
+    index: Option<usize>,
+}
+
+impl<I> HexToBytes<I>
+where
+    I: Iterator,
+    I::Item: Into<char>,
+{
+    /// Creates an adaptor decoding the hex yielded by `iter`.
+    pub fn new<T: IntoIterator<IntoIter = I>>(iter: T) -> Self {
+        HexToBytes {
+            inner: iter.into_iter(),
+            index: Some(0),
+        }
+    }
+
+    /// Returns the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+
+    // Decodes a single digit, ending the iteration on error.
+    fn digit(&mut self, c: I::Item, index: usize) -> Result<u8, FromHexError> {
+        let c = c.into();
+        let digit = DECODE_TABLE.get(c as usize).copied().unwrap_or(u8::MAX);
+        if digit == u8::MAX {
+            self.index = None;
+            return Err(FromHexError::InvalidHexCharacter { c, index });
+        }
+        Ok(digit)
+    }
+}
+
+impl<I> Iterator for HexToBytes<I>
+where
+    I: Iterator,
+    I::Item: Into<char>,
+{
+    type Item = Result<u8, FromHexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.index?;
+        let high = match self.inner.next() {
+            Some(c) => c,
+            None => {
+                self.index = None;
+                return None;
+            }
+        };
+        let low = match self.inner.next() {
+            Some(c) => c,
+            None => {
+                self.index = None;
+                return Some(self.digit(high, index).and(Err(FromHexError::OddLength)));
+            }
+        };
+        self.index = Some(index + 2);
+
+        Some(
+            self.digit(high, index)
+                .and_then(|high| Ok((high << 4) | self.digit(low, index + 1)?)),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.index.is_none() {
+            return (0, Some(0));
+        }
+        let (lower, upper) = self.inner.size_hint();
+        (lower.min(1), upper.map(|upper| upper / 2 + upper % 2))
+    }
+}
+
+impl<I> FusedIterator for HexToBytes<I>
+where
+    I: Iterator,
+    I::Item: Into<char>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_hex_to_bytes() {
+        let mut iter = HexToBytes::new(b"00fFa".iter().copied());
+        assert_eq!(iter.size_hint(), (1, Some(3)));
+        assert_eq!(iter.next(), Some(Ok(0x00)));
+        assert_eq!(iter.next(), Some(Ok(0xff)));
+        assert_eq!(iter.next(), Some(Err(FromHexError::OddLength)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+
+        let mut iter = HexToBytes::new(b"12g4ab".iter().copied());
+        assert_eq!(iter.next(), Some(Ok(0x12)));
+        assert_eq!(
+            iter.next(),
+            Some(Err(FromHexError::InvalidHexCharacter { c: 'g', index: 2 }))
+        );
+        assert_eq!(iter.next(), None);
+
+        let mut iter = HexToBytes::new(b"1x".iter().copied());
+        assert_eq!(
+            iter.next(),
+            Some(Err(FromHexError::InvalidHexCharacter { c: 'x', index: 1 }))
+        );
+
+        let mut iter = HexToBytes::new(b"x".iter().copied());
+        assert_eq!(
+            iter.next(),
+            Some(Err(FromHexError::InvalidHexCharacter { c: 'x', index: 0 }))
+        );
+        assert_eq!(HexToBytes::new(core::iter::empty::<u8>()).next(), None);
+
+        let unbounded = HexToBytes::new((0..usize::MAX).map(|_| b'0'));
+        assert_eq!(unbounded.size_hint(), (1, Some(usize::MAX / 2 + 1)));
+    }
+
+    #[test]
+    fn test_decode_chars() {
+        let mut iter = "aB€".chars().decode_hex();
+        assert_eq!(iter.next(), Some(Ok(0xab)));
+        assert_eq!(
+            iter.next(),
+            Some(Err(FromHexError::InvalidHexCharacter {
+                c: '€', index: 2
+            }))
+        );
+        // U+00FF fits into the table's index range, but isn't a digit
+        let mut iter = "\u{ff}0".chars().decode_hex();
+        assert_eq!(
+            iter.next(),
+            Some(Err(FromHexError::InvalidHexCharacter { c: 'ÿ', index: 0 }))
+        );
+    }
+
+    #[test]
+    fn test_encode_hex() {
+        let bytes = [0x01_u8, 0xab, 0xff];
+        let mut iter = bytes.iter().encode_hex_upper();
+        assert_eq!(iter.len(), 6);
+        assert_eq!(iter.next(), Some('0'));
+        assert_eq!(iter.len(), 5);
+        assert!(iter.eq("1ABFF".chars()));
+
+        let mut iter = bytes.iter().copied().filter(|&b| b != 0xab).encode_hex();
+        assert_eq!(iter.size_hint(), (0, Some(6)));
+        assert!(iter.by_ref().eq("01ff".chars()));
+        assert_eq!(iter.next(), None);
+
+        let roundtrip = bytes.iter().encode_hex().decode_hex();
+        assert!(roundtrip.eq(bytes.iter().map(|&b| Ok(b))));
+    }
+}
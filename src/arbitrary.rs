@@ -0,0 +1,72 @@
+//! Implementations of [`arbitrary::Arbitrary`] for the wrapper types.
+//!
+//! Values are always valid, so fuzzers spend their time on the code
+//! consuming them instead of on rejected inputs.
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::HexString;
+use crate::{FixedHex, Hex};
+
+#[cfg(feature = "alloc")]
+impl<'a> Arbitrary<'a> for HexString {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Vec::<u8>::arbitrary(u).map(HexString::encode)
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        Vec::<u8>::arbitrary_take_rest(u).map(HexString::encode)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        Vec::<u8>::size_hint(depth)
+    }
+}
+
+impl<'a, const N: usize> Arbitrary<'a> for FixedHex<N> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        <[u8; N]>::arbitrary(u).map(FixedHex)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <[u8; N]>::size_hint(depth)
+    }
+}
+
+impl<'a, T: Arbitrary<'a>> Arbitrary<'a> for Hex<T> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        T::arbitrary(u).map(Hex)
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        T::arbitrary_take_rest(u).map(Hex)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        T::size_hint(depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_arbitrary() {
+        let data = [0xde, 0xad, 0xbe, 0xef, 0x01, 0x02];
+        let mut u = Unstructured::new(&data);
+        let fixed = FixedHex::<2>::arbitrary(&mut u).unwrap();
+        assert_eq!(fixed, FixedHex([0xde, 0xad]));
+
+        #[cfg(feature = "alloc")]
+        {
+            let hex = HexString::arbitrary_take_rest(u).unwrap();
+            assert!(HexString::new(hex.as_str()).is_ok());
+            assert!(hex.as_bytes_len() <= 4);
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Hex encoding into [`core::ascii::Char`].
+//!
+//! The output type guarantees that the encoded digits are ASCII, so they
+//! convert to `&str` with [`as_str`](slice::as_str) for free, without unsafe
+//! code or validating them again. This requires a nightly compiler, as
+//! `ascii::Char` isn't stable yet.
+//!
+//! # Example
+//!
+//! ```
+//! #![feature(ascii_char)]
+//! use core::ascii::Char;
+//! use hex::Case;
+//!
+//! let mut out = [Char::from_u8(0).unwrap(); 8];
+//! let digits = hex::ascii::encode_to_slice(b"kiwi", &mut out, Case::Lower)?;
+//! assert_eq!(digits.as_str(), "6b697769");
+//! # Ok::<(), hex::FromHexError>(())
+//! ```
+use core::ascii::Char;
+
+use crate::{Case, FromHexError, HEX_CHARS_LOWER, HEX_CHARS_UPPER};
+
+// Converts an ASCII byte at compile time.
+const fn to_char(byte: u8) -> Char {
+    match Char::from_u8(byte) {
+        Some(c) => c,
+        None => panic!("not ASCII"),
+    }
+}
+
+const fn digits(table: &[u8; 16]) -> [Char; 16] {
+    let mut digits = [to_char(b'0'); 16];
+    let mut i = 0;
+    while i < 16 {
+        digits[i] = to_char(table[i]);
+        i += 1;
+    }
+    digits
+}
+
+const DIGITS_LOWER: [Char; 16] = digits(HEX_CHARS_LOWER);
+const DIGITS_UPPER: [Char; 16] = digits(HEX_CHARS_UPPER);
+
+fn table(case: Case) -> &'static [Char; 16] {
+    match case {
+        Case::Lower => &DIGITS_LOWER,
+        Case::Upper => &DIGITS_UPPER,
+    }
+}
+
+/// Encodes `input` into `out`, which has to be exactly twice as long.
+///
+/// Otherwise, [`FromHexError::InvalidStringLength`] is returned.
+pub fn encode_to_slice<T: AsRef<[u8]>>(
+    input: T,
+    out: &mut [Char],
+    case: Case,
+) -> Result<&mut [Char], FromHexError> {
+    let input = input.as_ref();
+    if crate::encoded_len(input.len()) != Some(out.len()) {
+        return Err(FromHexError::InvalidStringLength);
+    }
+    let table = table(case);
+    for (&byte, out) in input.iter().zip(out.chunks_exact_mut(2)) {
+        out[0] = table[usize::from(byte >> 4)];
+        out[1] = table[usize::from(byte & 0x0f)];
+    }
+    Ok(out)
+}
+
+/// Returns an iterator over the digits encoding `data`.
+///
+/// # Example
+///
+/// ```
+/// #![feature(ascii_char)]
+/// use hex::Case;
+///
+/// let digits: Vec<_> = hex::ascii::encode_iter(&[0xab], Case::Upper).collect();
+/// assert_eq!(digits.as_str(), "AB");
+/// ```
+pub fn encode_iter(data: &[u8], case: Case) -> impl ExactSizeIterator<Item = Char> + '_ {
+    let table = table(case);
+    (0..data.len() * 2).map(move |i| {
+        let byte = data[i / 2];
+        let digit = if i % 2 == 0 { byte >> 4 } else { byte & 0x0f };
+        table[usize::from(digit)]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_encode_to_slice() {
+        let data: [u8; 256] = core::array::from_fn(|i| i as u8);
+        let mut out = [to_char(0); 512];
+        let mut expected = [0; 512];
+        for case in [Case::Lower, Case::Upper] {
+            let digits = encode_to_slice(data, &mut out, case).unwrap().as_str();
+            let hex = crate::encode_to_slice_case(data, &mut expected, case).unwrap();
+            assert_eq!(digits, hex);
+        }
+        assert_eq!(
+            encode_to_slice([0], &mut out, Case::Lower),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+
+    #[test]
+    fn test_encode_iter() {
+        let mut iter = encode_iter(&[0x0f, 0xa0], Case::Lower);
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(to_char(b'0')));
+        assert_eq!(iter.next(), Some(to_char(b'f')));
+        assert_eq!(iter.next(), Some(to_char(b'a')));
+        assert_eq!(iter.next(), Some(to_char(b'0')));
+        assert_eq!(iter.next(), None);
+    }
+}
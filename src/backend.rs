@@ -0,0 +1,261 @@
+//! Reporting and overriding which implementation encodes and decodes.
+//!
+//! Long inputs are processed with the fastest vector instructions which the
+//! build and the CPU support, see the `simd` and `portable-simd` features,
+//! and with the generic loops otherwise. [`selected`] reports which
+//! [`Backend`] that is, and [`force`] overrides the choice for the whole
+//! process, e.g. to benchmark the backends against each other or to get
+//! reproducible behavior across machines.
+//!
+//! # Example
+//!
+//! ```
+//! use hex::backend::{self, Backend};
+//!
+//! println!("using {:?}", backend::selected());
+//!
+//! assert!(backend::force(Backend::Scalar));
+//! assert_eq!(backend::selected(), Backend::Scalar);
+//! # #[cfg(feature = "alloc")]
+//! assert_eq!(hex::encode([0xca; 64]), "ca".repeat(64));
+//!
+//! // back to the fastest one
+//! backend::reset();
+//! ```
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// An implementation of encoding and decoding.
+///
+/// All backends produce the same results, including the errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum Backend {
+    /// The generic loops, which work everywhere.
+    Scalar = 1,
+    /// SSSE3 on x86 and x86_64, with the `simd` feature.
+    Ssse3,
+    /// AVX2 on x86 and x86_64, with the `simd` feature. Inputs too short for
+    /// it use SSSE3.
+    Avx2,
+    /// AVX-512 VBMI on x86 and x86_64, with the `simd` feature. It is only
+    /// used for decoding, encoding uses AVX2.
+    Avx512Vbmi,
+    /// SIMD128 on wasm32, with the `simd` feature and the `simd128` target
+    /// feature.
+    Simd128,
+    /// The vector extension of RISC-V, with the `rvv` feature and the `v`
+    /// target feature.
+    RiscvVector,
+    /// `core::simd`, with the `portable-simd` feature.
+    Portable,
+}
+
+// From the fastest to the slowest, which is available everywhere.
+const PREFERENCE: [Backend; 7] = [
+    Backend::Avx512Vbmi,
+    Backend::Avx2,
+    Backend::Ssse3,
+    Backend::Simd128,
+    Backend::RiscvVector,
+    Backend::Portable,
+    Backend::Scalar,
+];
+
+// The selected backend, or 0 if it hasn't been selected yet.
+static SELECTED: AtomicU8 = AtomicU8::new(0);
+
+// Returns `true` if `backend` is one of the x86 backends of the `simd`
+// feature, and the CPU supports it.
+#[cfg(all(
+    feature = "simd",
+    not(feature = "force-scalar"),
+    any(target_arch = "x86", target_arch = "x86_64")
+))]
+fn x86_supports(backend: Backend) -> bool {
+    match backend {
+        Backend::Ssse3 => is_x86_feature_detected!("ssse3"),
+        Backend::Avx2 => is_x86_feature_detected!("avx2") && x86_supports(Backend::Ssse3),
+        Backend::Avx512Vbmi => {
+            is_x86_feature_detected!("avx512bw")
+                && is_x86_feature_detected!("avx512vbmi")
+                && x86_supports(Backend::Avx2)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(not(all(
+    feature = "simd",
+    not(feature = "force-scalar"),
+    any(target_arch = "x86", target_arch = "x86_64")
+)))]
+fn x86_supports(_: Backend) -> bool {
+    false
+}
+
+/// Returns `true` if `backend` is compiled in and supported by the CPU.
+///
+/// # Example
+///
+/// ```
+/// use hex::backend::{is_available, Backend};
+///
+/// assert!(is_available(Backend::Scalar));
+/// ```
+#[must_use]
+pub fn is_available(backend: Backend) -> bool {
+    match backend {
+        Backend::Scalar => true,
+        Backend::Ssse3 | Backend::Avx2 | Backend::Avx512Vbmi => x86_supports(backend),
+        Backend::Simd128 => cfg!(all(
+            feature = "simd",
+            not(feature = "force-scalar"),
+            target_arch = "wasm32",
+            target_feature = "simd128"
+        )),
+        Backend::RiscvVector => cfg!(all(
+            feature = "rvv",
+            not(feature = "force-scalar"),
+            any(target_arch = "riscv32", target_arch = "riscv64"),
+            target_feature = "v"
+        )),
+        Backend::Portable => cfg!(all(
+            feature = "portable-simd",
+            not(feature = "force-scalar")
+        )),
+    }
+}
+
+/// Returns the backend in use.
+///
+/// This is the fastest available one, unless another one was chosen with
+/// [`force`].
+#[must_use]
+pub fn selected() -> Backend {
+    let backend = match SELECTED.load(Ordering::Relaxed) {
+        0 => PREFERENCE
+            .iter()
+            .copied()
+            .find(|&backend| is_available(backend))
+            .unwrap_or(Backend::Scalar),
+        1 => return Backend::Scalar,
+        2 => return Backend::Ssse3,
+        3 => return Backend::Avx2,
+        4 => return Backend::Avx512Vbmi,
+        5 => return Backend::Simd128,
+        6 => return Backend::RiscvVector,
+        _ => return Backend::Portable,
+    };
+    SELECTED.store(backend as u8, Ordering::Relaxed);
+    backend
+}
+
+/// Uses `backend` from now on, in all threads.
+///
+/// Returns `false` and keeps the current backend if `backend` isn't
+/// available.
+///
+/// # Example
+///
+/// ```
+/// use hex::backend::{self, Backend};
+/// use hex::FromHex;
+///
+/// for backend in [Backend::Scalar, Backend::Avx2, Backend::Portable] {
+///     if backend::force(backend) {
+///         assert_eq!(<[u8; 2]>::from_hex("cafe"), Ok([0xca, 0xfe]));
+///     }
+/// }
+/// backend::reset();
+/// ```
+pub fn force(backend: Backend) -> bool {
+    if !is_available(backend) {
+        return false;
+    }
+    SELECTED.store(backend as u8, Ordering::Relaxed);
+    true
+}
+
+/// Goes back to using the fastest available backend, undoing [`force`].
+pub fn reset() {
+    SELECTED.store(0, Ordering::Relaxed);
+}
+
+// Encodes a prefix of `input` into `output`, which is twice as long, with
+// the selected backend. Returns the number of bytes encoded.
+#[inline]
+#[cfg(all(
+    any(feature = "simd", feature = "portable-simd"),
+    not(feature = "force-scalar")
+))]
+pub(crate) fn encode(
+    input: &[u8],
+    output: &mut [core::mem::MaybeUninit<u8>],
+    table: &[u8; 16],
+) -> usize {
+    match selected() {
+        Backend::Scalar => 0,
+        #[cfg(feature = "portable-simd")]
+        Backend::Portable => crate::portable::encode(input, output, table),
+        #[cfg(feature = "simd")]
+        // Saftey: only available backends are selected.
+        backend => unsafe { crate::simd::encode(backend, input, output, table) },
+        #[cfg(not(feature = "simd"))]
+        _ => 0,
+    }
+}
+
+// Decodes a prefix of `hex` into `out`, which is half as long, with the
+// selected backend. Returns the number of bytes decoded, stopping early if
+// `hex` contains invalid characters.
+#[inline]
+#[cfg(all(
+    any(feature = "simd", feature = "portable-simd"),
+    not(feature = "force-scalar")
+))]
+pub(crate) fn decode(hex: &[u8], out: &mut [core::mem::MaybeUninit<u8>]) -> usize {
+    match selected() {
+        Backend::Scalar => 0,
+        #[cfg(feature = "portable-simd")]
+        Backend::Portable => crate::portable::decode(hex, out),
+        #[cfg(feature = "simd")]
+        // Saftey: only available backends are selected.
+        backend => unsafe { crate::simd::decode(backend, hex, out) },
+        #[cfg(not(feature = "simd"))]
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_force() {
+        let fastest = selected();
+        assert!(is_available(fastest));
+
+        let mut hex = [0; 256];
+        let mut out = [0; 128];
+        let data: [u8; 128] = core::array::from_fn(|i| i as u8);
+        for backend in PREFERENCE {
+            assert_eq!(force(backend), is_available(backend));
+            if is_available(backend) {
+                assert_eq!(selected(), backend);
+                crate::encode_to_slice(data, &mut hex).unwrap();
+                assert_eq!(crate::decode_to_slice(hex, &mut out), Ok(()));
+                assert_eq!(out, data);
+                hex[77] = b'x';
+                assert_eq!(
+                    crate::decode_to_slice(hex, &mut out),
+                    Err(crate::FromHexError::InvalidHexCharacter { c: 'x', index: 77 })
+                );
+            }
+        }
+
+        reset();
+        assert_eq!(selected(), fastest);
+    }
+}
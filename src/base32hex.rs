@@ -0,0 +1,519 @@
+//! Base32 with the "Extended Hex" alphabet, as specified in
+//! [RFC 4648, section 7](https://www.rfc-editor.org/rfc/rfc4648#section-7).
+//!
+//! Unlike standard base32, the alphabet `0-9A-V` preserves the sort order of
+//! the encoded data, like hex does. It's used by DNSSEC for the hashed owner
+//! names of NSEC3 records.
+//!
+//! The API mirrors the hex functions of the crate root: [`encode_to_slice`]
+//! and [`decode_to_slice`] work without allocating, [`encode`] and
+//! [`decode`] return owned values, and [`encode_iter`] and [`decode_iter`]
+//! work lazily on iterators. Encoding uses uppercase letters and pads the
+//! output with `=` to a multiple of eight characters. Decoding ignores the
+//! case of the letters and accepts both padded and unpadded input, but
+//! rejects a last digit with bits set beyond the end of the data, so every
+//! byte string has only one accepted encoding up to case and padding. Errors
+//! are reported as [`FromHexError`], with
+//! [`FromHexError::InvalidStringLength`] for impossible lengths.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! assert_eq!(hex::base32hex::encode(b"foobar"), "CPNMUOJ1E8======");
+//! assert_eq!(hex::base32hex::decode("cpnmuoj1e8"), Ok(b"foobar".to_vec()));
+//! # }
+//! ```
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
+use core::iter::FusedIterator;
+
+use crate::FromHexError;
+
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHIJKLMNOPQRSTUV";
+const PAD: u8 = b'=';
+
+// Returns the value of a digit, or `None` for anything else.
+fn value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'A'..=b'V' => Some(c - b'A' + 10),
+        b'a'..=b'v' => Some(c - b'a' + 10),
+        _ => None,
+    }
+}
+
+/// Returns the length of the padded encoding of `len` bytes, or `None` if it
+/// overflows `usize`.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::base32hex::encoded_len(0), Some(0));
+/// assert_eq!(hex::base32hex::encoded_len(1), Some(8));
+/// assert_eq!(hex::base32hex::encoded_len(20), Some(32));
+/// ```
+pub const fn encoded_len(len: usize) -> Option<usize> {
+    let groups = len / 5 + (len % 5 != 0) as usize;
+    groups.checked_mul(8)
+}
+
+/// Returns the number of bytes encoded by `data`, checking its length and
+/// padding but not its digits.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::base32hex::decoded_len("CPNMU==="), Ok(3));
+/// assert_eq!(hex::base32hex::decoded_len("CPNMU"), Ok(3));
+/// assert!(hex::base32hex::decoded_len("CPN").is_err());
+/// ```
+pub fn decoded_len<T: AsRef<[u8]>>(data: T) -> Result<usize, FromHexError> {
+    let data = data.as_ref();
+    let digits = digits(data).len();
+    let padding = data.len() - digits;
+    if padding != 0 && (data.len() % 8 != 0 || padding >= 8) {
+        return Err(FromHexError::InvalidStringLength);
+    }
+    match digits % 8 {
+        0 | 2 | 4 | 5 | 7 => Ok(digits / 8 * 5 + digits % 8 * 5 / 8),
+        _ => Err(FromHexError::InvalidStringLength),
+    }
+}
+
+// Strips the padding from `data`.
+fn digits(data: &[u8]) -> &[u8] {
+    let len = data.iter().rposition(|&c| c != PAD).map_or(0, |i| i + 1);
+    &data[..len]
+}
+
+/// Encodes `data` as padded base32hex into `out`.
+///
+/// The output buffer has to be exactly [`encoded_len`] bytes long, otherwise
+/// [`FromHexError::InvalidStringLength`] is returned.
+///
+/// # Example
+///
+/// ```
+/// let mut out = [0; 8];
+/// assert_eq!(hex::base32hex::encode_to_slice(b"f", &mut out)?, "CO======");
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+pub fn encode_to_slice<T: AsRef<[u8]>>(data: T, out: &mut [u8]) -> Result<&mut str, FromHexError> {
+    let data = data.as_ref();
+    if encoded_len(data.len()) != Some(out.len()) {
+        return Err(FromHexError::InvalidStringLength);
+    }
+    for (c, out) in encode_iter(data.iter().copied()).zip(out.iter_mut()) {
+        *out = c as u8;
+    }
+    // Saftey: We just wrote ASCII digits and padding into the output
+    Ok(unsafe { core::str::from_utf8_unchecked_mut(out) })
+}
+
+/// Encodes `data` as padded base32hex.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::base32hex::encode(b"fo"), "CPNG====");
+/// ```
+#[must_use]
+#[cfg(feature = "alloc")]
+pub fn encode<T: AsRef<[u8]>>(data: T) -> String {
+    encode_iter(data.as_ref().iter().copied()).collect()
+}
+
+/// Decodes base32hex into `out`.
+///
+/// The output buffer has to be exactly [`decoded_len`] bytes long, otherwise
+/// [`FromHexError::InvalidStringLength`] is returned. A last digit with bits
+/// set beyond the end of the data, like the `H` of `CPNMUOH=`, is reported as
+/// an invalid character.
+///
+/// # Example
+///
+/// ```
+/// use hex::FromHexError;
+///
+/// let mut out = [0; 3];
+/// hex::base32hex::decode_to_slice("CPNMU===", &mut out)?;
+/// assert_eq!(&out, b"foo");
+///
+/// let mut out = [0; 4];
+/// assert_eq!(
+///     hex::base32hex::decode_to_slice("CPNMUOH=", &mut out),
+///     Err(FromHexError::InvalidHexCharacter { c: 'H', index: 6 })
+/// );
+/// # Ok::<(), FromHexError>(())
+/// ```
+pub fn decode_to_slice<T: AsRef<[u8]>>(data: T, out: &mut [u8]) -> Result<(), FromHexError> {
+    let data = data.as_ref();
+    if decoded_len(data)? != out.len() {
+        return Err(FromHexError::InvalidStringLength);
+    }
+    // The decoder has to run to the end to check the bits left over.
+    let mut out = out.iter_mut();
+    for byte in decode_iter(data.iter().copied()) {
+        let byte = byte?;
+        if let Some(out) = out.next() {
+            *out = byte;
+        }
+    }
+    Ok(())
+}
+
+/// Decodes base32hex into a vector.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::base32hex::decode("CPNMUOG="), Ok(b"foob".to_vec()));
+/// assert!(hex::base32hex::decode("CPNMUOW=").is_err());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, FromHexError> {
+    let data = data.as_ref();
+    let mut out = vec![0; decoded_len(data)?];
+    decode_to_slice(data, &mut out)?;
+    Ok(out)
+}
+
+/// Returns an iterator lazily encoding the bytes yielded by `iter` as padded
+/// base32hex.
+///
+/// # Example
+///
+/// ```
+/// let encoded = hex::base32hex::encode_iter(b"foo".iter().copied());
+/// assert!(encoded.eq("CPNMU===".chars()));
+/// ```
+pub fn encode_iter<I: IntoIterator<Item = u8>>(iter: I) -> EncodeIter<I::IntoIter> {
+    EncodeIter {
+        inner: iter.into_iter(),
+        buffer: 0,
+        bits: 0,
+        len: 0,
+        done: false,
+    }
+}
+
+/// An iterator encoding bytes as base32hex characters.
+///
+/// This struct is created by [`encode_iter`].
+#[derive(Debug, Clone)]
+pub struct EncodeIter<I> {
+    inner: I,
+    // Bits which were read but not yet encoded, in the low `bits` bits.
+    buffer: u16,
+    bits: u32,
+    // The number of characters yielded, for padding the last group.
+    len: usize,
+    done: bool,
+}
+
+impl<I> EncodeIter<I> {
+    fn digit(&mut self) -> char {
+        self.bits -= 5;
+        let value = (self.buffer >> self.bits) & 0x1f;
+        self.buffer &= (1 << self.bits) - 1;
+        self.len += 1;
+        ALPHABET[value as usize] as char
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for EncodeIter<I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.bits < 5 && !self.done {
+            match self.inner.next() {
+                Some(byte) => {
+                    self.buffer = (self.buffer << 8) | u16::from(byte);
+                    self.bits += 8;
+                }
+                None => self.done = true,
+            }
+        }
+        if self.bits >= 5 {
+            Some(self.digit())
+        } else if self.bits > 0 {
+            // the last digit is filled up with zeros
+            self.buffer <<= 5 - self.bits;
+            self.bits = 5;
+            Some(self.digit())
+        } else if self.len % 8 != 0 {
+            self.len += 1;
+            Some(PAD as char)
+        } else {
+            None
+        }
+    }
+}
+
+impl<I: Iterator<Item = u8>> FusedIterator for EncodeIter<I> {}
+
+/// Returns an iterator lazily decoding the base32hex characters yielded by
+/// `iter`.
+///
+/// Every complete byte is yielded as soon as its last digit was read, and
+/// errors are reported like [`decode_to_slice`] does: invalid characters
+/// when they are read, and invalid lengths or a last digit with bits set
+/// beyond the end of the data once the input ends. The iterator ends after an
+/// error.
+///
+/// # Example
+///
+/// ```
+/// use hex::FromHexError;
+///
+/// let decoded: Result<Vec<u8>, _> = hex::base32hex::decode_iter(*b"CPNMU===").collect();
+/// assert_eq!(decoded, Ok(b"foo".to_vec()));
+///
+/// let mut decoded = hex::base32hex::decode_iter(*b"CPN");
+/// assert_eq!(decoded.next(), Some(Ok(b'f')));
+/// assert_eq!(decoded.next(), Some(Err(FromHexError::InvalidStringLength)));
+/// assert_eq!(decoded.next(), None);
+/// ```
+pub fn decode_iter<I: IntoIterator<Item = u8>>(iter: I) -> DecodeIter<I::IntoIter> {
+    DecodeIter {
+        inner: iter.into_iter(),
+        buffer: 0,
+        bits: 0,
+        digits: 0,
+        padding: 0,
+        last: 0,
+        done: false,
+    }
+}
+
+/// An iterator decoding base32hex characters into bytes.
+///
+/// This struct is created by [`decode_iter`].
+#[derive(Debug, Clone)]
+pub struct DecodeIter<I> {
+    inner: I,
+    // Bits which were read but not yet yielded, in the low `bits` bits.
+    buffer: u16,
+    bits: u32,
+    // The number of digits and padding characters read.
+    digits: usize,
+    padding: usize,
+    // The last digit read, for reporting bits left over at the end.
+    last: u8,
+    done: bool,
+}
+
+impl<I> DecodeIter<I> {
+    fn fail(&mut self, err: FromHexError) -> Option<Result<u8, FromHexError>> {
+        self.done = true;
+        Some(Err(err))
+    }
+
+    // Checks the length and the bits left over once the input ended.
+    fn finish(&self) -> Result<(), FromHexError> {
+        let len = self.digits + self.padding;
+        if self.padding != 0 && (len % 8 != 0 || self.padding >= 8) {
+            return Err(FromHexError::InvalidStringLength);
+        }
+        if !matches!(self.digits % 8, 0 | 2 | 4 | 5 | 7) {
+            return Err(FromHexError::InvalidStringLength);
+        }
+        if self.buffer != 0 {
+            return Err(FromHexError::InvalidHexCharacter {
+                c: self.last as char,
+                index: self.digits - 1,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for DecodeIter<I> {
+    type Item = Result<u8, FromHexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let c = match self.inner.next() {
+                Some(c) => c,
+                None => {
+                    self.done = true;
+                    return self.finish().err().map(Err);
+                }
+            };
+            if c == PAD {
+                self.padding += 1;
+                continue;
+            }
+            let value = match value(c) {
+                // digits after the padding make the first `=` invalid
+                Some(_) if self.padding != 0 => {
+                    return self.fail(FromHexError::InvalidHexCharacter {
+                        c: PAD as char,
+                        index: self.digits,
+                    })
+                }
+                Some(value) => value,
+                None => {
+                    let index = self.digits + self.padding;
+                    return self.fail(FromHexError::InvalidHexCharacter {
+                        c: c as char,
+                        index,
+                    });
+                }
+            };
+            self.digits += 1;
+            self.last = c;
+            self.buffer = (self.buffer << 5) | u16::from(value);
+            self.bits += 5;
+            if self.bits >= 8 {
+                self.bits -= 8;
+                let byte = (self.buffer >> self.bits) as u8;
+                self.buffer &= (1 << self.bits) - 1;
+                return Some(Ok(byte));
+            }
+        }
+        None
+    }
+}
+
+impl<I: Iterator<Item = u8>> FusedIterator for DecodeIter<I> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    // The test vectors of RFC 4648, section 10.
+    const VECTORS: [(&str, &str); 7] = [
+        ("", ""),
+        ("f", "CO======"),
+        ("fo", "CPNG===="),
+        ("foo", "CPNMU==="),
+        ("foob", "CPNMUOG="),
+        ("fooba", "CPNMUOJ1"),
+        ("foobar", "CPNMUOJ1E8======"),
+    ];
+
+    #[test]
+    fn test_encode_to_slice() {
+        for (data, encoded) in VECTORS {
+            let mut out = [0; 16];
+            let out = &mut out[..encoded.len()];
+            assert_eq!(encode_to_slice(data, out).map(|s| &*s), Ok(encoded));
+        }
+        assert_eq!(
+            encode_to_slice(b"f", &mut [0; 2]),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+
+    #[test]
+    fn test_decode_to_slice() {
+        for (data, encoded) in VECTORS {
+            let mut out = [0; 6];
+            let out = &mut out[..data.len()];
+            assert_eq!(decode_to_slice(encoded, out), Ok(()));
+            assert_eq!(out, data.as_bytes());
+
+            let unpadded = encoded.trim_end_matches('=').to_ascii_lowercase();
+            assert_eq!(decode_to_slice(unpadded, out), Ok(()));
+            assert_eq!(out, data.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        for data in ["C", "CPN", "CPNMUO", "CO=", "CO=====", "========"] {
+            assert_eq!(
+                decoded_len(data),
+                Err(FromHexError::InvalidStringLength),
+                "{}",
+                data
+            );
+        }
+        assert_eq!(
+            decode_to_slice("CW======", &mut [0; 1]),
+            Err(FromHexError::InvalidHexCharacter { c: 'W', index: 1 })
+        );
+        assert_eq!(
+            decode_to_slice("C=OO====", &mut [0; 2]),
+            Err(FromHexError::InvalidHexCharacter { c: '=', index: 1 })
+        );
+        assert_eq!(
+            decode_to_slice("CO", &mut [0; 2]),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+
+    #[test]
+    fn test_non_canonical() {
+        // the last digit has bits set beyond the end of the data
+        for (data, len, c, index) in [
+            ("CPNMUOH=", 4, 'H', 6),
+            ("CP======", 1, 'P', 1),
+            ("cpnmuoj1e9", 6, '9', 9),
+        ] {
+            assert_eq!(
+                decode_to_slice(data, &mut [0; 6][..len]),
+                Err(FromHexError::InvalidHexCharacter { c, index }),
+                "{}",
+                data
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_iter() {
+        for (data, encoded) in VECTORS {
+            assert!(decode_iter(encoded.bytes()).eq(data.bytes().map(Ok)));
+            let unpadded = encoded.trim_end_matches('=').to_ascii_lowercase();
+            assert!(decode_iter(unpadded.bytes()).eq(data.bytes().map(Ok)));
+        }
+
+        let decode = |data: &str| decode_iter(data.bytes()).collect::<Result<Vec<u8>, _>>();
+        for data in ["C", "CPN", "CO=", "CO=====", "========"] {
+            assert_eq!(
+                decode(data),
+                Err(FromHexError::InvalidStringLength),
+                "{}",
+                data
+            );
+        }
+        assert_eq!(
+            decode("CO==W==="),
+            Err(FromHexError::InvalidHexCharacter { c: 'W', index: 4 })
+        );
+        assert_eq!(
+            decode("C=OO===="),
+            Err(FromHexError::InvalidHexCharacter { c: '=', index: 1 })
+        );
+        assert_eq!(
+            decode("CPNMUOH="),
+            Err(FromHexError::InvalidHexCharacter { c: 'H', index: 6 })
+        );
+
+        let mut iter = decode_iter(*b"CPNMUOW");
+        assert_eq!(iter.next(), Some(Ok(b'f')));
+        assert_eq!(iter.next(), Some(Ok(b'o')));
+        assert_eq!(iter.next(), Some(Ok(b'o')));
+        assert!(iter.next().unwrap().is_err());
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_sort_order() {
+        let mut pairs = [[0xff, 0x00], [0x00, 0xff], [0x80, 0x01], [0x7f, 0xff]];
+        let mut encoded: [[u8; 8]; 4] = [[0; 8]; 4];
+        for (data, out) in pairs.iter().zip(&mut encoded) {
+            encode_to_slice(data, out).unwrap();
+        }
+        pairs.sort();
+        encoded.sort();
+        for (data, encoded) in pairs.iter().zip(&encoded) {
+            let mut out = [0; 8];
+            assert_eq!(encode_to_slice(data, &mut out).unwrap().as_bytes(), encoded);
+        }
+    }
+}
@@ -0,0 +1,206 @@
+//! Encoding and decoding of many fixed-size items at once.
+//!
+//! Workloads handling large numbers of hashes or IDs spend much of their time
+//! in per-item allocations and length checks. The functions in this module
+//! size their output once up front and validate every item in a single pass.
+//!
+//! # Example
+//!
+//! ```
+//! let ids = [[0x01, 0x02], [0xab, 0xcd]];
+//!
+//! let hex = hex::batch::encode(&ids);
+//! assert_eq!(hex, ["0102", "abcd"]);
+//! assert_eq!(hex::batch::decode(&hex), Ok(ids.to_vec()));
+//!
+//! let hex = hex::batch::encode_concat(&ids);
+//! assert_eq!(hex, "0102abcd");
+//! assert_eq!(hex::batch::decode_concat(hex), Ok(ids.to_vec()));
+//! ```
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::{decode_to_slice, encode_append, FromHexError};
+
+/// The error returned by [`decode`], saying which item failed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ItemError {
+    /// The index of the item which failed to decode.
+    pub item: usize,
+    /// Why it failed. Indices of invalid characters are positions within the
+    /// item.
+    pub error: FromHexError,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ItemError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl fmt::Display for ItemError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Item {}: {}", self.item, self.error)
+    }
+}
+
+/// Encodes every item as a lowercase hex string.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::batch::encode(&[[0xff; 2]; 2]), ["ffff", "ffff"]);
+/// ```
+#[must_use]
+pub fn encode<const N: usize>(items: &[[u8; N]]) -> Vec<String> {
+    items.iter().map(crate::encode).collect()
+}
+
+/// Encodes all items as a single lowercase hex string, without separators.
+///
+/// The output is allocated once, and the encoding of item `i` starts at
+/// index `i * 2 * N`.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::batch::encode_concat(&[[0x01], [0x02], [0x03]]), "010203");
+/// ```
+#[must_use]
+pub fn encode_concat<const N: usize>(items: &[[u8; N]]) -> String {
+    let mut out = Vec::with_capacity(items.len() * 2 * N);
+    for item in items {
+        encode_append(item, &mut out);
+    }
+    // Saftey: `encode_append` only writes hex digits into the output
+    unsafe { String::from_utf8_unchecked(out) }
+}
+
+/// Decodes every hex string into an item.
+///
+/// Every string has to be exactly `2 * N` characters long, otherwise
+/// [`FromHexError::InvalidStringLength`] is returned, also for strings of odd
+/// length. Errors are wrapped in an [`ItemError`] with the index of the
+/// offending string, and indices of invalid characters are positions within
+/// it.
+///
+/// # Example
+///
+/// ```
+/// use hex::batch::ItemError;
+/// use hex::FromHexError;
+///
+/// assert_eq!(hex::batch::decode(&["0102", "0304"]), Ok(vec![[1, 2], [3, 4]]));
+/// assert_eq!(
+///     hex::batch::decode::<2, _>(&["0102", "03x4"]),
+///     Err(ItemError {
+///         item: 1,
+///         error: FromHexError::InvalidHexCharacter { c: 'x', index: 2 }
+///     })
+/// );
+/// ```
+pub fn decode<const N: usize, T: AsRef<[u8]>>(items: &[T]) -> Result<Vec<[u8; N]>, ItemError> {
+    let mut out = Vec::with_capacity(items.len());
+    for (i, item) in items.iter().enumerate() {
+        let item = item.as_ref();
+        let mut bytes = [0; N];
+        let result = if item.len() == 2 * N {
+            decode_to_slice(item, &mut bytes)
+        } else {
+            Err(FromHexError::InvalidStringLength)
+        };
+        result.map_err(|error| ItemError { item: i, error })?;
+        out.push(bytes);
+    }
+    Ok(out)
+}
+
+/// Decodes a hex string holding concatenated items, the inverse of
+/// [`encode_concat`].
+///
+/// The length of `hex` has to be a multiple of `2 * N`, otherwise
+/// [`FromHexError::OddLength`] or [`FromHexError::InvalidStringLength`] is
+/// returned.
+///
+/// # Example
+///
+/// ```
+/// let items: Vec<[u8; 3]> = hex::batch::decode_concat("010203aabbcc")?;
+/// assert_eq!(items, [[0x01, 0x02, 0x03], [0xaa, 0xbb, 0xcc]]);
+///
+/// assert!(hex::batch::decode_concat::<3>("010203aabb").is_err());
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+pub fn decode_concat<const N: usize>(hex: impl AsRef<[u8]>) -> Result<Vec<[u8; N]>, FromHexError> {
+    let hex = hex.as_ref();
+    let len = crate::decoded_len(hex.len())?;
+    if N == 0 {
+        return match len {
+            0 => Ok(Vec::new()),
+            _ => Err(FromHexError::InvalidStringLength),
+        };
+    }
+    if len % N != 0 {
+        return Err(FromHexError::InvalidStringLength);
+    }
+
+    let mut out = Vec::with_capacity(len / N);
+    for (i, chunk) in hex.chunks_exact(2 * N).enumerate() {
+        let mut bytes = [0; N];
+        decode_to_slice(chunk, &mut bytes).map_err(|err| err.offset(i * 2 * N))?;
+        out.push(bytes);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_roundtrip() {
+        let items: Vec<[u8; 4]> = (0..=255).map(|i| [i, !i, i ^ 0x5a, 0]).collect();
+        assert_eq!(decode(&encode(&items)), Ok(items.clone()));
+        assert_eq!(decode_concat(encode_concat(&items)), Ok(items));
+
+        let empty: [[u8; 4]; 0] = [];
+        assert_eq!(encode_concat(&empty), "");
+        assert_eq!(decode_concat::<4>(""), Ok(vec![]));
+        assert_eq!(decode_concat::<0>(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_errors() {
+        let length = |item| {
+            Err(ItemError {
+                item,
+                error: FromHexError::InvalidStringLength,
+            })
+        };
+        assert_eq!(decode::<2, _>(&["0102", "03"]), length(1));
+        assert_eq!(decode::<2, _>(&["030", "0102"]), length(0));
+        assert_eq!(
+            decode::<2, _>(&["0102", "0304", "0g05"]),
+            Err(ItemError {
+                item: 2,
+                error: FromHexError::InvalidHexCharacter { c: 'g', index: 1 }
+            })
+        );
+        assert_eq!(decode_concat::<2>("0102030"), Err(FromHexError::OddLength));
+        assert_eq!(
+            decode_concat::<2>("010203"),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            decode_concat::<0>("00"),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            decode_concat::<2>("01020g04"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 5 })
+        );
+    }
+}
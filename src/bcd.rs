@@ -0,0 +1,208 @@
+//! Packing and unpacking of binary-coded decimals.
+//!
+//! Packed BCD stores one decimal digit per nibble, so the digits `"1234"` are
+//! stored as the bytes `[0x12, 0x34]`. In other words, the hex encoding of
+//! BCD bytes is the decimal number itself, which is why BCD shows up
+//! alongside hex in many payment and telecom protocols.
+//!
+//! # Example
+//!
+//! ```
+//! let mut packed = [0; 2];
+//! hex::bcd::pack_to_slice("1234", &mut packed)?;
+//! assert_eq!(packed, [0x12, 0x34]);
+//!
+//! let mut digits = [0; 4];
+//! assert_eq!(hex::bcd::unpack_to_slice(&packed, &mut digits)?, "1234");
+//!
+//! // nibbles above 9 are not valid BCD
+//! assert!(hex::bcd::unpack_to_slice(&[0x1a], &mut [0; 2]).is_err());
+//! # Ok::<(), hex::FromHexError>(())
+//! ```
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::{FromHexError, HEX_CHARS_LOWER};
+
+// Returns the value of a decimal digit.
+fn digit(c: u8, index: usize) -> Result<u8, FromHexError> {
+    if c.is_ascii_digit() {
+        Ok(c - b'0')
+    } else {
+        Err(FromHexError::InvalidHexCharacter {
+            c: c as char,
+            index,
+        })
+    }
+}
+
+/// Packs the decimal `digits` into `out`, two digits per byte.
+///
+/// An odd number of digits is padded with a leading zero, so `out` has to be
+/// exactly `(digits.len() + 1) / 2` bytes long, otherwise
+/// [`FromHexError::InvalidStringLength`] is returned. Characters other than
+/// `0` to `9` are rejected with [`FromHexError::InvalidHexCharacter`].
+///
+/// # Example
+///
+/// ```
+/// let mut packed = [0; 2];
+/// assert_eq!(hex::bcd::pack_to_slice("123", &mut packed), Ok(()));
+/// assert_eq!(packed, [0x01, 0x23]);
+/// ```
+pub fn pack_to_slice<T: AsRef<[u8]>>(digits: T, out: &mut [u8]) -> Result<(), FromHexError> {
+    let digits = digits.as_ref();
+    if (digits.len() + 1) / 2 != out.len() {
+        return Err(FromHexError::InvalidStringLength);
+    }
+
+    let (first, rest) = digits.split_at(digits.len() % 2);
+    if let [c] = *first {
+        out[0] = digit(c, 0)?;
+    }
+    for (i, (pair, byte)) in rest
+        .chunks_exact(2)
+        .zip(&mut out[first.len()..])
+        .enumerate()
+    {
+        let index = first.len() + 2 * i;
+        *byte = (digit(pair[0], index)? << 4) | digit(pair[1], index + 1)?;
+    }
+    Ok(())
+}
+
+/// Packs the decimal `digits` into BCD bytes.
+///
+/// This works like [`pack_to_slice`], but allocates the output.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::bcd::pack("20240131"), Ok(vec![0x20, 0x24, 0x01, 0x31]));
+/// assert!(hex::bcd::pack("12a4").is_err());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn pack<T: AsRef<[u8]>>(digits: T) -> Result<Vec<u8>, FromHexError> {
+    let digits = digits.as_ref();
+    let mut out = vec![0; (digits.len() + 1) / 2];
+    pack_to_slice(digits, &mut out)?;
+    Ok(out)
+}
+
+/// Unpacks BCD `bytes` into decimal digits, writing them to `out`.
+///
+/// `out` has to be exactly twice as long as `bytes`, otherwise
+/// [`FromHexError::InvalidStringLength`] is returned. A nibble above 9 is
+/// rejected with [`FromHexError::InvalidHexCharacter`], holding the nibble as
+/// a hex digit and its index in the unpacked output.
+///
+/// # Example
+///
+/// ```
+/// let mut digits = [0; 4];
+/// assert_eq!(hex::bcd::unpack_to_slice(&[0x01, 0x23], &mut digits), Ok("0123"));
+/// ```
+pub fn unpack_to_slice<'a>(bytes: &[u8], out: &'a mut [u8]) -> Result<&'a str, FromHexError> {
+    if crate::encoded_len(bytes.len()) != Some(out.len()) {
+        return Err(FromHexError::InvalidStringLength);
+    }
+
+    for (i, (&byte, pair)) in bytes.iter().zip(out.chunks_exact_mut(2)).enumerate() {
+        for (j, nibble) in [byte >> 4, byte & 0x0f].iter().enumerate() {
+            let c = HEX_CHARS_LOWER[*nibble as usize];
+            if *nibble > 9 {
+                return Err(FromHexError::InvalidHexCharacter {
+                    c: c as char,
+                    index: 2 * i + j,
+                });
+            }
+            pair[j] = c;
+        }
+    }
+
+    // Saftey: We just wrote only decimal digits into the output
+    Ok(unsafe { core::str::from_utf8_unchecked(out) })
+}
+
+/// Unpacks BCD `bytes` into a string of decimal digits.
+///
+/// This works like [`unpack_to_slice`], but allocates the output. Leading
+/// zeros are kept.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::bcd::unpack([0x20, 0x24, 0x01, 0x31]), Ok("20240131".to_string()));
+/// assert!(hex::bcd::unpack([0xff]).is_err());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn unpack<T: AsRef<[u8]>>(bytes: T) -> Result<String, FromHexError> {
+    let bytes = bytes.as_ref();
+    let mut out = vec![0; bytes.len() * 2];
+    unpack_to_slice(bytes, &mut out)?;
+    // Saftey: `unpack_to_slice` only writes decimal digits into the output
+    Ok(unsafe { String::from_utf8_unchecked(out) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_pack() {
+        let mut out = [0; 3];
+        assert_eq!(pack_to_slice("123456", &mut out), Ok(()));
+        assert_eq!(out, [0x12, 0x34, 0x56]);
+        assert_eq!(pack_to_slice("99999", &mut out), Ok(()));
+        assert_eq!(out, [0x09, 0x99, 0x99]);
+        assert_eq!(pack_to_slice("", &mut []), Ok(()));
+
+        assert_eq!(
+            pack_to_slice("1234", &mut out),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            pack_to_slice("a2345", &mut out),
+            Err(FromHexError::InvalidHexCharacter { c: 'a', index: 0 })
+        );
+        assert_eq!(
+            pack_to_slice("12345f", &mut out),
+            Err(FromHexError::InvalidHexCharacter { c: 'f', index: 5 })
+        );
+    }
+
+    #[test]
+    fn test_unpack() {
+        let mut out = [0; 4];
+        assert_eq!(unpack_to_slice(&[0x09, 0x87], &mut out), Ok("0987"));
+        assert_eq!(
+            unpack_to_slice(&[0x09], &mut out),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            unpack_to_slice(&[0x12, 0x3c], &mut out),
+            Err(FromHexError::InvalidHexCharacter { c: 'c', index: 3 })
+        );
+        assert_eq!(
+            unpack_to_slice(&[0xa0, 0x00], &mut out),
+            Err(FromHexError::InvalidHexCharacter { c: 'a', index: 0 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_roundtrip() {
+        use alloc::string::ToString;
+
+        for digits in ["", "0", "42", "12345", "0000000000", "9876543210"] {
+            let packed = pack(digits).unwrap();
+            let padded = if digits.len() % 2 == 0 {
+                digits.to_string()
+            } else {
+                "0".to_string() + digits
+            };
+            assert_eq!(unpack(&packed), Ok(padded));
+        }
+    }
+}
@@ -0,0 +1,365 @@
+//! Hex encoding and decoding of fixed-size big integers.
+//!
+//! With the `primitive-types` feature, this covers `U128`, `U256` and `U512`
+//! from `primitive-types`, and with the `ruint` feature, every `Uint` from
+//! `ruint`. Digits are read from and written to the limbs of the integer
+//! directly, without an intermediate byte buffer.
+//!
+//! Like for [primitive integers](crate::int), [`FromHex`] is implemented for
+//! these types and reads a big-endian number, which doesn't need to be
+//! zero-padded. [`ToHex`](crate::ToHex) can't be implemented for them, as it
+//! would overlap with the implementation for all `AsRef<[u8]>` types, so use
+//! [`encode_int`] for zero-padded hex of the full width of the type instead.
+//!
+//! [`encode_minimal`], [`encode_quantity`] and [`decode_minimal`] handle the
+//! form without leading zeros, e.g. JSON-RPC quantities (`0x1a`).
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(all(feature = "alloc", feature = "primitive-types"))]
+//! # {
+//! use hex::{bigint, FromHex};
+//! use primitive_types::U256;
+//!
+//! let value = U256::from_hex("deadbeef").unwrap();
+//! assert_eq!(value, U256::from(0xdeadbeef_u64));
+//! assert_eq!(bigint::encode_quantity(&value), "0xdeadbeef");
+//! assert_eq!(bigint::decode_minimal::<U256>("0xdeadbeef"), Ok(value));
+//! assert_eq!(bigint::encode_int(&value, hex::Case::Lower).len(), 64);
+//! # }
+//! ```
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use core::iter;
+
+#[cfg(feature = "alloc")]
+use crate::Case;
+use crate::{FromHex, FromHexError, DECODE_TABLE};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Fixed-size big integers which can be encoded as and decoded from hex.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait BigUint: Sized + private::Sealed {
+    #[doc(hidden)]
+    const BITS: usize;
+
+    #[doc(hidden)]
+    fn limbs(&self) -> &[u64];
+
+    #[doc(hidden)]
+    fn with_limbs<F: FnOnce(&mut [u64])>(f: F) -> Self;
+}
+
+#[cfg(feature = "primitive-types")]
+macro_rules! impl_primitive_types {
+    ($($ty:ident),*) => {
+        $(
+            impl private::Sealed for primitive_types::$ty {}
+
+            impl BigUint for primitive_types::$ty {
+                const BITS: usize = 8 * core::mem::size_of::<Self>();
+
+                fn limbs(&self) -> &[u64] {
+                    &self.0
+                }
+
+                fn with_limbs<F: FnOnce(&mut [u64])>(f: F) -> Self {
+                    let mut value = Self::zero();
+                    f(&mut value.0);
+                    value
+                }
+            }
+
+            impl FromHex for primitive_types::$ty {
+                type Error = FromHexError;
+
+                fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, Self::Error> {
+                    parse_be(hex.as_ref())
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "primitive-types")]
+impl_primitive_types!(U128, U256, U512);
+
+#[cfg(feature = "ruint")]
+impl<const BITS: usize, const LIMBS: usize> private::Sealed for ruint::Uint<BITS, LIMBS> {}
+
+#[cfg(feature = "ruint")]
+impl<const BITS: usize, const LIMBS: usize> BigUint for ruint::Uint<BITS, LIMBS> {
+    const BITS: usize = BITS;
+
+    fn limbs(&self) -> &[u64] {
+        self.as_limbs()
+    }
+
+    fn with_limbs<F: FnOnce(&mut [u64])>(f: F) -> Self {
+        let mut limbs = [0; LIMBS];
+        f(&mut limbs);
+        Self::from_limbs(limbs)
+    }
+}
+
+#[cfg(feature = "ruint")]
+impl<const BITS: usize, const LIMBS: usize> FromHex for ruint::Uint<BITS, LIMBS> {
+    type Error = FromHexError;
+
+    fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, Self::Error> {
+        parse_be(hex.as_ref())
+    }
+}
+
+// Returns the number of digits of the full width of `T`.
+fn full_digits<T: BigUint>() -> usize {
+    (T::BITS + 3) / 4
+}
+
+// Returns the number of digits needed to represent `value`, at least 1.
+#[cfg(feature = "alloc")]
+fn minimal_digits<T: BigUint>(value: &T) -> usize {
+    let limbs = value.limbs();
+    match limbs.iter().rposition(|&limb| limb != 0) {
+        Some(i) => 16 * i + (64 - limbs[i].leading_zeros() as usize + 3) / 4,
+        None => 1,
+    }
+}
+
+// Returns the `digits` least significant digits of `value`, most
+// significant first.
+#[cfg(feature = "alloc")]
+fn chars<T: BigUint>(value: &T, digits: usize, case: Case) -> impl Iterator<Item = char> + '_ {
+    let table = case.table();
+    let limbs = value.limbs();
+    (0..digits).rev().map(move |i| {
+        // Zero-width integers have no limbs, but are still encoded as "0".
+        let limb = limbs.get(i / 16).copied().unwrap_or(0);
+        let nibble = (limb >> (4 * (i % 16))) & 0xf;
+        table[nibble as usize] as char
+    })
+}
+
+// Parses a big-endian number with at most as many digits as the full width
+// of `T`.
+fn parse_be<T: BigUint>(hex: &[u8]) -> Result<T, FromHexError> {
+    let digits = full_digits::<T>();
+    if hex.is_empty() || hex.len() > digits {
+        return Err(FromHexError::InvalidStringLength);
+    }
+    for (index, &c) in hex.iter().enumerate() {
+        if DECODE_TABLE[c as usize] == u8::MAX {
+            return Err(FromHexError::InvalidHexCharacter {
+                c: c as char,
+                index,
+            });
+        }
+    }
+    // The most significant digit of a type whose width isn't a multiple of
+    // four bits is only partially usable.
+    let top_bits = T::BITS - 4 * (digits - 1);
+    if hex.len() == digits && DECODE_TABLE[hex[0] as usize] >> top_bits != 0 {
+        return Err(FromHexError::InvalidStringLength);
+    }
+    Ok(T::with_limbs(|limbs| {
+        for (i, &c) in hex.iter().rev().enumerate() {
+            limbs[i / 16] |= u64::from(DECODE_TABLE[c as usize]) << (4 * (i % 16));
+        }
+    }))
+}
+
+// Splits off an optional `0x` (or `0X`) prefix, returning its length.
+fn strip_prefix(hex: &[u8]) -> (&[u8], usize) {
+    match hex {
+        [b'0', b'x', rest @ ..] | [b'0', b'X', rest @ ..] => (rest, 2),
+        _ => (hex, 0),
+    }
+}
+
+/// Encodes a big integer as zero-padded hex of the full width of its type.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "primitive-types")]
+/// # {
+/// use hex::{bigint::encode_int, Case};
+/// use primitive_types::U128;
+///
+/// assert_eq!(encode_int(&U128::from(0xab), Case::Upper), "000000000000000000000000000000AB");
+/// # }
+/// ```
+#[must_use]
+#[cfg(feature = "alloc")]
+pub fn encode_int<T: BigUint>(value: &T, case: Case) -> String {
+    chars(value, full_digits::<T>(), case).collect()
+}
+
+/// Encodes a big integer as hex without leading zeros, like `{:x}`.
+///
+/// Zero is encoded as `"0"`.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "primitive-types")]
+/// # {
+/// use hex::{bigint::encode_minimal, Case};
+/// use primitive_types::U256;
+///
+/// assert_eq!(encode_minimal(&U256::from(0xabc), Case::Lower), "abc");
+/// assert_eq!(encode_minimal(&U256::zero(), Case::Lower), "0");
+/// assert_eq!(encode_minimal(&U256::MAX, Case::Upper), "F".repeat(64));
+/// # }
+/// ```
+#[must_use]
+#[cfg(feature = "alloc")]
+pub fn encode_minimal<T: BigUint>(value: &T, case: Case) -> String {
+    chars(value, minimal_digits(value), case).collect()
+}
+
+/// Encodes a big integer as a JSON-RPC quantity, i.e. as lowercase hex
+/// without leading zeros and with a `0x` prefix.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "primitive-types")]
+/// # {
+/// use primitive_types::U256;
+///
+/// assert_eq!(hex::bigint::encode_quantity(&U256::from(1024)), "0x400");
+/// assert_eq!(hex::bigint::encode_quantity(&U256::zero()), "0x0");
+/// # }
+/// ```
+#[must_use]
+#[cfg(feature = "alloc")]
+pub fn encode_quantity<T: BigUint>(value: &T) -> String {
+    iter::once('0')
+        .chain(iter::once('x'))
+        .chain(chars(value, minimal_digits(value), Case::Lower))
+        .collect()
+}
+
+/// Decodes a big integer from hex with an optional `0x` (or `0X`) prefix.
+///
+/// This accepts the output of [`encode_minimal`] and [`encode_quantity`],
+/// but leading zeros are allowed as long as the digits fit into the type.
+/// Indices in errors are relative to the start of `hex`, including the
+/// prefix.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "primitive-types")]
+/// # {
+/// use hex::bigint::decode_minimal;
+/// use primitive_types::U256;
+///
+/// assert_eq!(decode_minimal::<U256>("0x400"), Ok(U256::from(1024)));
+/// assert_eq!(decode_minimal::<U256>("abc"), Ok(U256::from(0xabc)));
+/// assert!(decode_minimal::<U256>("0x").is_err());
+/// # }
+/// ```
+pub fn decode_minimal<T: BigUint>(hex: impl AsRef<[u8]>) -> Result<T, FromHexError> {
+    let (hex, offset) = strip_prefix(hex.as_ref());
+    parse_be(hex).map_err(|err| err.offset(offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[cfg(feature = "primitive-types")]
+    fn test_primitive_types() {
+        use primitive_types::{U128, U256, U512};
+
+        assert_eq!(U128::from_hex("ff"), Ok(U128::from(0xff)));
+        assert_eq!(
+            U256::from_hex("0123456789abcdef0123456789ABCDEF"),
+            Ok(U256::from(0x0123456789abcdef0123456789abcdef_u128))
+        );
+        assert_eq!(U512::from_hex("f".repeat(128)), Ok(U512::MAX));
+        assert_eq!(
+            U128::from_hex("1".repeat(33)),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(U128::from_hex(""), Err(FromHexError::InvalidStringLength));
+        assert_eq!(
+            U256::from_hex("12g"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 2 })
+        );
+        assert_eq!(
+            decode_minimal::<U128>("0x1g"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "primitive-types", feature = "alloc"))]
+    fn test_primitive_types_encode() {
+        use alloc::format;
+        use primitive_types::{U128, U256};
+
+        let value = U256::from(1) << 200;
+        assert_eq!(encode_quantity(&value), format!("0x1{}", "0".repeat(50)));
+        assert_eq!(decode_minimal(encode_quantity(&value)), Ok(value));
+        assert_eq!(
+            encode_int(&U128::from(0x1a), Case::Lower),
+            "0000000000000000000000000000001a"
+        );
+        for value in [0_u128, 1, 0xf, 0x10, u64::MAX as u128 + 1, u128::MAX] {
+            let value = U256::from(value);
+            assert_eq!(U256::from_hex(encode_int(&value, Case::Upper)), Ok(value));
+            assert_eq!(
+                decode_minimal(encode_minimal(&value, Case::Lower)),
+                Ok(value)
+            );
+            assert_eq!(encode_minimal(&value, Case::Lower), format!("{:x}", value));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "ruint")]
+    fn test_ruint() {
+        use ruint::aliases::{U256, U64};
+        use ruint::Uint;
+
+        assert_eq!(U256::from_hex("abc"), Ok(U256::from(0xabc)));
+        assert_eq!(U64::from_hex("f".repeat(16)), Ok(U64::MAX));
+        // 10 bits hold three digits, the first of which is at most 3
+        assert_eq!(Uint::<10, 1>::from_hex("3ff"), Ok(Uint::<10, 1>::MAX));
+        assert_eq!(
+            Uint::<10, 1>::from_hex("400"),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            Uint::<0, 0>::from_hex("0"),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "ruint", feature = "alloc"))]
+    fn test_ruint_encode() {
+        use alloc::format;
+        use ruint::aliases::U256;
+        use ruint::Uint;
+
+        assert_eq!(encode_int(&Uint::<10, 1>::from(5), Case::Lower), "005");
+        assert_eq!(encode_quantity(&Uint::<0, 0>::ZERO), "0x0");
+        for value in [U256::ZERO, U256::from(1), U256::MAX >> 3, U256::MAX] {
+            assert_eq!(U256::from_hex(encode_int(&value, Case::Lower)), Ok(value));
+            assert_eq!(decode_minimal(encode_quantity(&value)), Ok(value));
+            assert_eq!(encode_minimal(&value, Case::Lower), format!("{:x}", value));
+        }
+    }
+}
@@ -0,0 +1,290 @@
+//! A small command line tool for hex encoding, decoding and dumping.
+//!
+//! All subcommands read from stdin and write to stdout, processing the input
+//! in chunks (or, for the textual input of `decode` and `revert`, in lines).
+
+// Avoid raising the minimum supported Rust version for this.
+#![allow(clippy::manual_is_multiple_of)]
+use std::{
+    env,
+    io::{self, BufRead, BufWriter, Read, Write},
+    process,
+};
+
+const USAGE: &str = "\
+Usage: hex <command> [options]
+
+Commands:
+  encode    Encode stdin as hex
+  decode    Decode hex from stdin, ignoring whitespace
+  dump      Print a hexdump of stdin, in the style of xxd
+  revert    Turn a hexdump back into bytes
+
+Options:
+  -u, --upper             Use uppercase letters (encode, dump)
+  -s, --separator <SEP>   Put SEP between bytes (encode), or ignore it (decode)
+  -p, --prefix <PREFIX>   Put PREFIX before every byte (encode), or ignore it (decode)
+  -w, --wrap <BYTES>      Start a new line after BYTES bytes (encode)
+  -h, --help              Print this message
+";
+
+const CHUNK_LEN: usize = 4096;
+
+#[derive(Debug, Default)]
+struct Options {
+    case: hex::Case,
+    separator: String,
+    prefix: String,
+    wrap: usize,
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let command = args.next();
+    let options = match parse_options(args) {
+        Ok(options) => options,
+        Err(msg) => usage_error(&msg),
+    };
+
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let input = stdin.lock();
+    let mut output = BufWriter::new(stdout.lock());
+    let result = match command.as_deref() {
+        Some("encode") => encode(input, &mut output, &options),
+        Some("decode") => decode(input, &mut output, &options),
+        Some("dump") => dump(input, &mut output, &options),
+        Some("revert") => revert(input, &mut output),
+        Some("-h") | Some("--help") => {
+            print!("{}", USAGE);
+            return;
+        }
+        Some(command) => usage_error(&format!("unknown command `{}`", command)),
+        None => usage_error("missing command"),
+    };
+
+    if let Err(err) = result.and_then(|()| output.flush()) {
+        if err.kind() != io::ErrorKind::BrokenPipe {
+            eprintln!("hex: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+fn usage_error(msg: &str) -> ! {
+    eprint!("hex: {}\n\n{}", msg, USAGE);
+    process::exit(2);
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for `{}`", arg))
+        };
+        match arg.as_str() {
+            "-u" | "--upper" => options.case = hex::Case::Upper,
+            "-s" | "--separator" => options.separator = value()?,
+            "-p" | "--prefix" => options.prefix = value()?,
+            "-w" | "--wrap" => {
+                let wrap = value()?;
+                options.wrap = wrap
+                    .parse()
+                    .map_err(|_| format!("invalid line length `{}`", wrap))?;
+            }
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                process::exit(0);
+            }
+            _ => return Err(format!("unknown option `{}`", arg)),
+        }
+    }
+    Ok(options)
+}
+
+fn invalid_data<E: std::fmt::Display>(line: usize, err: E) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {}: {}", line, err),
+    )
+}
+
+// Calls `f` with consecutive chunks of `input`, which are only shorter than
+// `CHUNK_LEN` at the end of the input.
+fn for_each_chunk(
+    mut input: impl Read,
+    mut f: impl FnMut(&[u8]) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut buf = [0; CHUNK_LEN];
+    loop {
+        let mut len = 0;
+        while len < buf.len() {
+            match input.read(&mut buf[len..]) {
+                Ok(0) => break,
+                Ok(n) => len += n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        if len == 0 {
+            return Ok(());
+        }
+        f(&buf[..len])?;
+    }
+}
+
+fn encode(input: impl Read, output: &mut impl Write, options: &Options) -> io::Result<()> {
+    let mut hex = [0; 2 * CHUNK_LEN];
+    let mut count = 0_usize;
+    for_each_chunk(input, |chunk| {
+        let encode = match options.case {
+            hex::Case::Lower => hex::encode_to_slice,
+            hex::Case::Upper => hex::encode_to_slice_upper,
+        };
+        let hex = encode(chunk, &mut hex[..2 * chunk.len()]).unwrap();
+        if options.separator.is_empty() && options.prefix.is_empty() && options.wrap == 0 {
+            count += chunk.len();
+            return output.write_all(hex.as_bytes());
+        }
+
+        for pair in hex.as_bytes().chunks(2) {
+            if count > 0 {
+                if options.wrap != 0 && count % options.wrap == 0 {
+                    output.write_all(b"\n")?;
+                } else {
+                    output.write_all(options.separator.as_bytes())?;
+                }
+            }
+            output.write_all(options.prefix.as_bytes())?;
+            output.write_all(pair)?;
+            count += 1;
+        }
+        Ok(())
+    })?;
+    if count > 0 {
+        output.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+fn decode(input: impl BufRead, output: &mut impl Write, options: &Options) -> io::Result<()> {
+    let mut bytes = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let mut line = line?;
+        for pattern in [&options.prefix, &options.separator] {
+            if !pattern.is_empty() {
+                line = line.replace(pattern.as_str(), "");
+            }
+        }
+        line.retain(|c| !c.is_ascii_whitespace());
+
+        bytes.clear();
+        bytes.resize(line.len() / 2, 0);
+        hex::decode_to_slice(&line, &mut bytes).map_err(|err| invalid_data(i + 1, err))?;
+        output.write_all(&bytes)?;
+    }
+    Ok(())
+}
+
+fn dump(input: impl Read, output: &mut impl Write, options: &Options) -> io::Result<()> {
+    let mut offset = 0_usize;
+    for_each_chunk(input, |chunk| {
+        for line in hex::dump::lines(chunk, offset, options.case) {
+            writeln!(output, "{}", line)?;
+        }
+        offset += chunk.len();
+        Ok(())
+    })
+}
+
+fn revert(input: impl BufRead, output: &mut impl Write) -> io::Result<()> {
+    let mut bytes = Vec::new();
+    let mut digits = String::new();
+    for (i, line) in input.lines().enumerate() {
+        let line = line?;
+        // Skip the offset, and stop at the text column, which is separated
+        // from the hex by at least two spaces.
+        let hex = line.split_once(':').map_or(line.as_str(), |(_, hex)| hex);
+        let hex = hex.trim_start();
+        let hex = hex.split_once("  ").map_or(hex, |(hex, _)| hex);
+
+        digits.clear();
+        digits.extend(hex.chars().filter(|c| !c.is_ascii_whitespace()));
+        bytes.clear();
+        bytes.resize(digits.len() / 2, 0);
+        hex::decode_to_slice(&digits, &mut bytes).map_err(|err| invalid_data(i + 1, err))?;
+        output.write_all(&bytes)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn run(
+        f: impl FnOnce(&[u8], &mut Vec<u8>) -> io::Result<()>,
+        input: &[u8],
+    ) -> io::Result<Vec<u8>> {
+        let mut output = Vec::new();
+        f(input, &mut output)?;
+        Ok(output)
+    }
+
+    #[test]
+    fn test_encode() {
+        let options = Options {
+            case: hex::Case::Upper,
+            separator: ", ".to_string(),
+            prefix: "0x".to_string(),
+            wrap: 2,
+        };
+        let output = run(|i, o| encode(i, o, &options), b"\x01\xab\xff");
+        assert_eq!(output.unwrap(), b"0x01, 0xAB\n0xFF\n");
+
+        let long = vec![0xab; CHUNK_LEN + 1];
+        let output = run(|i, o| encode(i, o, &Options::default()), &long).unwrap();
+        assert_eq!(
+            output,
+            format!("{}\n", "ab".repeat(CHUNK_LEN + 1)).as_bytes()
+        );
+        assert_eq!(
+            run(|i, o| encode(i, o, &Options::default()), b"").unwrap(),
+            b""
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        let options = Options {
+            separator: ",".to_string(),
+            prefix: "0x".to_string(),
+            ..Options::default()
+        };
+        let output = run(|i, o| decode(i, o, &options), b"0x01, 0xAB,\n0xff\n");
+        assert_eq!(output.unwrap(), b"\x01\xab\xff");
+
+        let err = run(|i, o| decode(i, o, &Options::default()), b"00\n0g\n").unwrap_err();
+        #[cfg(not(feature = "tiny-errors"))]
+        assert_eq!(
+            err.to_string(),
+            "line 2: Invalid character 'g' at position 1"
+        );
+        #[cfg(feature = "tiny-errors")]
+        assert_eq!(err.to_string(), "line 2: Invalid character");
+    }
+
+    #[test]
+    fn test_dump_revert() {
+        let input = b"Hello world!\nThis is a test.\x00\xff";
+        let dump = run(|i, o| dump(i, o, &Options::default()), input).unwrap();
+        assert_eq!(
+            String::from_utf8(dump.clone()).unwrap(),
+            "00000000: 4865 6c6c 6f20 776f 726c 6421 0a54 6869  Hello world!.Thi\n\
+             00000010: 7320 6973 2061 2074 6573 742e 00ff       s is a test...\n"
+        );
+        assert_eq!(run(|i, o| revert(i, o), &dump).unwrap(), input);
+    }
+}
@@ -0,0 +1,221 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{decode_to_slice, decoded_len, FromHexError};
+
+/// A growable byte buffer which hex can be decoded into.
+///
+/// This is implemented for `Vec<u8>` and, with the features of the same
+/// name, for `bytes::BytesMut`, `smallvec::SmallVec` and `heapless::Vec`.
+/// Implementing it for another container makes [`decode_into`] and
+/// [`decode_extend`] work with it, without a dedicated [`FromHex`] impl.
+///
+/// [`FromHex`]: crate::FromHex
+pub trait HexBuffer {
+    /// Returns the number of bytes in the buffer.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the buffer holds no bytes.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends `additional` zero bytes and returns them, or returns `None`
+    /// without changing the buffer if it can't hold them.
+    fn extend_zeroed(&mut self, additional: usize) -> Option<&mut [u8]>;
+
+    /// Shortens the buffer to its first `len` bytes.
+    fn truncate(&mut self, len: usize);
+}
+
+/// Decodes a hex string into any [`HexBuffer`].
+///
+/// If the buffer has a fixed capacity which is too small,
+/// [`FromHexError::InvalidStringLength`] is returned.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// let bytes: Vec<u8> = hex::decode_into("6b697769").unwrap();
+/// assert_eq!(bytes, b"kiwi");
+/// # }
+/// ```
+pub fn decode_into<B, T>(data: T) -> Result<B, FromHexError>
+where
+    B: HexBuffer + Default,
+    T: AsRef<[u8]>,
+{
+    let mut out = B::default();
+    decode_extend(data, &mut out)?;
+    Ok(out)
+}
+
+/// Decodes a hex string, appending the bytes to a [`HexBuffer`].
+///
+/// Returns the number of bytes appended; on error `out` is left unchanged.
+/// If the buffer has a fixed capacity which is too small,
+/// [`FromHexError::InvalidStringLength`] is returned.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// let mut bytes = b"fruit: ".to_vec();
+/// assert_eq!(hex::decode_extend("6b697769", &mut bytes), Ok(4));
+/// assert_eq!(bytes, b"fruit: kiwi");
+/// # }
+/// ```
+pub fn decode_extend<B, T>(data: T, out: &mut B) -> Result<usize, FromHexError>
+where
+    B: HexBuffer + ?Sized,
+    T: AsRef<[u8]>,
+{
+    let data = data.as_ref();
+    let len = decoded_len(data.len())?;
+    let start = out.len();
+    let buf = out
+        .extend_zeroed(len)
+        .ok_or(FromHexError::InvalidStringLength)?;
+    if let Err(err) = decode_to_slice(data, buf) {
+        out.truncate(start);
+        return Err(err);
+    }
+    Ok(len)
+}
+
+#[cfg(feature = "alloc")]
+impl HexBuffer for Vec<u8> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn extend_zeroed(&mut self, additional: usize) -> Option<&mut [u8]> {
+        let start = self.len();
+        self.reserve_exact(additional);
+        self.resize(start + additional, 0);
+        Some(&mut self[start..])
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.truncate(len);
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl HexBuffer for bytes::BytesMut {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn extend_zeroed(&mut self, additional: usize) -> Option<&mut [u8]> {
+        let start = self.len();
+        self.resize(start + additional, 0);
+        Some(&mut self[start..])
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.truncate(len);
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array<Item = u8>> HexBuffer for smallvec::SmallVec<A> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn extend_zeroed(&mut self, additional: usize) -> Option<&mut [u8]> {
+        let start = self.len();
+        self.reserve_exact(additional);
+        self.resize(start + additional, 0);
+        Some(&mut self[start..])
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.truncate(len);
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> HexBuffer for heapless::Vec<u8, N> {
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    fn extend_zeroed(&mut self, additional: usize) -> Option<&mut [u8]> {
+        let start = self.len();
+        if additional > N - start {
+            return None;
+        }
+        self.resize(start + additional, 0).ok()?;
+        Some(&mut self[start..])
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.truncate(len);
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "alloc")]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_vec() {
+        assert_eq!(decode_into::<Vec<u8>, _>("0102"), Ok(vec![1, 2]));
+        assert_eq!(decode_into::<Vec<u8>, _>(""), Ok(vec![]));
+
+        let mut out = vec![0xff];
+        assert_eq!(decode_extend("01", &mut out), Ok(1));
+        assert_eq!(
+            decode_extend("010g", &mut out),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+        );
+        assert_eq!(decode_extend("010", &mut out), Err(FromHexError::OddLength));
+        assert_eq!(out, [0xff, 0x01]);
+    }
+
+    #[test]
+    #[cfg(feature = "smallvec")]
+    fn test_smallvec() {
+        use smallvec::SmallVec;
+
+        let out: SmallVec<[u8; 2]> = decode_into("0102").unwrap();
+        assert!(!out.spilled());
+        assert_eq!(out.as_slice(), [1, 2]);
+        let out: SmallVec<[u8; 2]> = decode_into("010203").unwrap();
+        assert!(out.spilled());
+        assert_eq!(out.as_slice(), [1, 2, 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "heapless")]
+    fn test_heapless() {
+        let mut out = heapless::Vec::<u8, 3>::new();
+        assert_eq!(decode_extend("0102", &mut out), Ok(2));
+        assert_eq!(
+            decode_extend("0304", &mut out),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            decode_extend("0g", &mut out),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 1 })
+        );
+        assert_eq!(out, [1, 2]);
+        assert_eq!(decode_extend("03", &mut out), Ok(1));
+        assert_eq!(out, [1, 2, 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn test_bytes_mut() {
+        let out: bytes::BytesMut = decode_into("0102").unwrap();
+        assert_eq!(&out[..], [1, 2]);
+    }
+}
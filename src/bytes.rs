@@ -0,0 +1,173 @@
+//! Hex encoding and decoding against the buffer abstractions of the `bytes`
+//! crate.
+//!
+//! The data doesn't need to be contiguous: a [`Buf`] made of several chunks,
+//! like a chain of frames in a codec, is decoded chunk by chunk, even when a
+//! pair of digits is split between two chunks.
+//!
+//! # Example
+//!
+//! ```
+//! use bytes::{Buf, BytesMut};
+//!
+//! let mut hex = BytesMut::new();
+//! hex::bytes::encode_to_bufmut(b"kiwi", &mut hex);
+//! assert_eq!(hex, "6b697769");
+//!
+//! let mut decoded = BytesMut::new();
+//! hex::bytes::decode_from_buf(&mut (&b"6b6"[..]).chain(&b"97769"[..]), &mut decoded)?;
+//! assert_eq!(decoded, "kiwi");
+//! # Ok::<(), hex::FromHexError>(())
+//! ```
+use bytes::{Buf, BufMut, BytesMut};
+
+use crate::{FromHexError, DECODE_TABLE};
+
+// Size of the stack buffer used for encoding.
+const BUF_LEN: usize = 256;
+
+/// Encodes `data` as lowercase hex, appending it to `out`.
+///
+/// # Panics
+///
+/// Panics if `out` doesn't have enough capacity left, like
+/// [`BufMut::put_slice`]. A [`BytesMut`] grows as needed.
+///
+/// # Example
+///
+/// ```
+/// let mut out = bytes::BytesMut::from("0x");
+/// hex::bytes::encode_to_bufmut([0xca, 0xfe], &mut out);
+/// assert_eq!(out, "0xcafe");
+/// ```
+pub fn encode_to_bufmut<T: AsRef<[u8]>, B: BufMut>(data: T, out: &mut B) {
+    let mut buf = [0; BUF_LEN];
+    for chunk in data.as_ref().chunks(BUF_LEN / 2) {
+        let hex = crate::encode_to_slice(chunk, &mut buf[..chunk.len() * 2]).unwrap();
+        out.put_slice(hex.as_bytes());
+    }
+}
+
+/// Decodes all hex remaining in `buf`, appending the bytes to `out`, and
+/// returns the number of bytes appended.
+///
+/// Indices of invalid characters are counted from the start of the
+/// remaining data. If an error is returned, nothing is appended to `out`,
+/// but `buf` may have been partially consumed. An odd number of digits is
+/// rejected before consuming anything.
+///
+/// # Example
+///
+/// ```
+/// use bytes::BytesMut;
+///
+/// let mut out = BytesMut::new();
+/// assert_eq!(hex::bytes::decode_from_buf(&mut &b"CAFE"[..], &mut out), Ok(2));
+/// assert_eq!(out, [0xca, 0xfe][..]);
+/// assert!(hex::bytes::decode_from_buf(&mut &b"ca fe"[..], &mut out).is_err());
+/// assert_eq!(out.len(), 2);
+/// ```
+pub fn decode_from_buf<B: Buf>(buf: &mut B, out: &mut BytesMut) -> Result<usize, FromHexError> {
+    let len = crate::decoded_len(buf.remaining())?;
+    let start = out.len();
+    out.reserve(len);
+    if let Err(err) = decode_chunks(buf, out) {
+        out.truncate(start);
+        return Err(err);
+    }
+    Ok(len)
+}
+
+// Decodes the chunks of `buf` into `out`, carrying a digit over when a pair
+// is split between two chunks.
+fn decode_chunks<B: Buf>(buf: &mut B, out: &mut BytesMut) -> Result<(), FromHexError> {
+    let mut index = 0;
+    let mut high = None;
+    while buf.has_remaining() {
+        let mut chunk = buf.chunk();
+        let consumed = chunk.len();
+        if let Some(high) = high.take() {
+            out.put_u8((high << 4) | digit(chunk[0], index)?);
+            chunk = &chunk[1..];
+            index += 1;
+        }
+
+        let pairs = chunk.len() / 2 * 2;
+        let at = out.len();
+        out.resize(at + pairs / 2, 0);
+        crate::decode_to_slice(&chunk[..pairs], &mut out[at..]).map_err(|err| err.offset(index))?;
+        index += pairs;
+
+        if let Some(&c) = chunk.get(pairs) {
+            high = Some(digit(c, index)?);
+            index += 1;
+        }
+        buf.advance(consumed);
+    }
+    Ok(())
+}
+
+// Returns the value of the digit `c` found at `index`.
+fn digit(c: u8, index: usize) -> Result<u8, FromHexError> {
+    match DECODE_TABLE[c as usize] {
+        u8::MAX => Err(FromHexError::InvalidHexCharacter {
+            c: c as char,
+            index,
+        }),
+        digit => Ok(digit),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_encode_to_bufmut() {
+        let data: Vec<u8> = (0..=255).cycle().take(BUF_LEN * 2 + 1).collect();
+        let mut out = BytesMut::new();
+        encode_to_bufmut(&data, &mut out);
+        assert_eq!(out, crate::encode(&data).as_bytes());
+
+        let mut out = Vec::new();
+        encode_to_bufmut([], &mut out);
+        assert_eq!(out, b"");
+    }
+
+    #[test]
+    fn test_decode_from_buf() {
+        let data: Vec<u8> = (0..=255).collect();
+        let hex = crate::encode(&data);
+        let (a, b) = hex.as_bytes().split_at(101);
+        let (b, c) = b.split_at(1);
+        let mut buf = a.chain(b).chain(c);
+        let mut out = BytesMut::from(&b"x"[..]);
+        assert_eq!(decode_from_buf(&mut buf, &mut out), Ok(256));
+        assert!(!buf.has_remaining());
+        assert_eq!(out[0], b'x');
+        assert_eq!(&out[1..], &data[..]);
+    }
+
+    #[test]
+    fn test_decode_from_buf_errors() {
+        let mut out = BytesMut::new();
+        let mut buf = &b"abc"[..];
+        assert_eq!(
+            decode_from_buf(&mut buf, &mut out),
+            Err(FromHexError::OddLength)
+        );
+        assert_eq!(buf, b"abc");
+
+        // indices are counted across chunks, including split pairs
+        for (a, b, index) in [("0", "g00", 1), ("00g", "0", 2), ("00", "0g", 3)] {
+            let mut buf = a.as_bytes().chain(b.as_bytes());
+            assert_eq!(
+                decode_from_buf(&mut buf, &mut out),
+                Err(FromHexError::InvalidHexCharacter { c: 'g', index })
+            );
+            assert!(out.is_empty());
+        }
+    }
+}
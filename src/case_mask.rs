@@ -0,0 +1,178 @@
+//! Round-tripping the casing of hex strings.
+//!
+//! Decoding loses the casing of the input, so re-encoding mixed-case hex such
+//! as an EIP-55 checksummed Ethereum address doesn't reproduce it. The
+//! functions in this module record the casing in a compact [`CaseMask`] when
+//! decoding, and apply it again when encoding.
+//!
+//! # Example
+//!
+//! ```
+//! let (bytes, mask) = hex::case_mask::decode("5aAeb6")?;
+//! assert_eq!(bytes, [0x5a, 0xae, 0xb6]);
+//! assert_eq!(hex::case_mask::encode(&bytes, &mask), "5aAeb6");
+//! # Ok::<(), hex::FromHexError>(())
+//! ```
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::FromHexError;
+
+/// The casing of the digits of a hex string, one bit per digit.
+///
+/// Digits not covered by the mask, as well as `0` to `9`, count as
+/// lowercase.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CaseMask {
+    bits: Vec<u8>,
+    len: usize,
+}
+
+impl CaseMask {
+    /// Creates an all-lowercase mask covering `len` digits.
+    #[must_use]
+    pub fn new(len: usize) -> Self {
+        CaseMask {
+            bits: vec![0; len / 8 + usize::from(len % 8 != 0)],
+            len,
+        }
+    }
+
+    /// Returns the number of digits covered by the mask.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the mask covers no digits.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns whether the digit at `index` is uppercase.
+    #[must_use]
+    pub fn is_upper(&self, index: usize) -> bool {
+        index < self.len && self.bits[index / 8] & (1 << (index % 8)) != 0
+    }
+
+    /// Sets whether the digit at `index` is uppercase.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` isn't covered by the mask.
+    pub fn set_upper(&mut self, index: usize, upper: bool) {
+        assert!(
+            index < self.len,
+            "index {} out of range for a mask of {} digits",
+            index,
+            self.len
+        );
+        let bit = 1 << (index % 8);
+        if upper {
+            self.bits[index / 8] |= bit;
+        } else {
+            self.bits[index / 8] &= !bit;
+        }
+    }
+
+    /// Returns `true` if no digit is uppercase.
+    #[must_use]
+    pub fn is_lowercase(&self) -> bool {
+        self.bits.iter().all(|&bits| bits == 0)
+    }
+}
+
+/// Decodes a hex string, returning the bytes along with the casing of its
+/// digits.
+///
+/// # Example
+///
+/// ```
+/// let (bytes, mask) = hex::case_mask::decode("Ab")?;
+/// assert_eq!(bytes, [0xab]);
+/// assert!(mask.is_upper(0));
+/// assert!(!mask.is_upper(1));
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+pub fn decode<T: AsRef<[u8]>>(hex: T) -> Result<(Vec<u8>, CaseMask), FromHexError> {
+    let hex = hex.as_ref();
+    let bytes = crate::decode(hex)?;
+    let mut mask = CaseMask::new(hex.len());
+    for (i, c) in hex.iter().enumerate() {
+        if c.is_ascii_uppercase() {
+            mask.set_upper(i, true);
+        }
+    }
+    Ok((bytes, mask))
+}
+
+/// Encodes `data` as hex, using uppercase letters for the digits marked in
+/// `mask` and lowercase letters otherwise.
+///
+/// # Example
+///
+/// ```
+/// let mut mask = hex::case_mask::CaseMask::new(4);
+/// mask.set_upper(1, true);
+/// mask.set_upper(2, true);
+/// assert_eq!(hex::case_mask::encode([0xab, 0xcd], &mask), "aBCd");
+/// ```
+#[must_use]
+pub fn encode<T: AsRef<[u8]>>(data: T, mask: &CaseMask) -> String {
+    let data = data.as_ref();
+    let mut out = vec![0; data.len() * 2];
+    crate::encode_to_slice(data, &mut out).unwrap();
+    if !mask.is_lowercase() {
+        for (i, c) in out.iter_mut().enumerate() {
+            if mask.is_upper(i) {
+                c.make_ascii_uppercase();
+            }
+        }
+    }
+    // Saftey: The output only holds hex digits
+    unsafe { String::from_utf8_unchecked(out) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_roundtrip() {
+        for hex in [
+            "",
+            "00",
+            "abcdef",
+            "ABCDEF",
+            "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        ] {
+            let (bytes, mask) = decode(hex).unwrap();
+            assert_eq!(mask.len(), hex.len());
+            assert_eq!(encode(&bytes, &mask), hex);
+        }
+        assert_eq!(decode("abc"), Err(FromHexError::OddLength));
+    }
+
+    #[test]
+    fn test_mask() {
+        let mut mask = CaseMask::new(9);
+        assert!(mask.is_lowercase());
+        mask.set_upper(8, true);
+        assert!(mask.is_upper(8));
+        assert!(!mask.is_upper(9));
+        mask.set_upper(8, false);
+        assert!(mask.is_lowercase());
+        assert!(CaseMask::new(0).is_empty());
+
+        // digits beyond the mask are lowercase
+        mask.set_upper(0, true);
+        assert_eq!(encode([0xaa; 6], &mask), "Aaaaaaaaaaaa");
+    }
+
+    #[test]
+    #[should_panic(expected = "index 2 out of range for a mask of 2 digits")]
+    fn test_mask_out_of_range() {
+        CaseMask::new(2).set_upper(2, true);
+    }
+}
@@ -0,0 +1,329 @@
+//! A `tokio-util` codec sending hex-encoded frames.
+//!
+//! [`HexCodec`] encodes outgoing frames as hex and decodes incoming hex
+//! frames, which are either terminated by newlines, e.g. for protocols
+//! typed into `nc`, or prefixed with their length like
+//! [`LengthDelimitedCodec`] does. Set it up with
+//! `Framed::new(stream, HexCodec::lines())`.
+//!
+//! # Example
+//!
+//! ```
+//! use bytes::BytesMut;
+//! use hex::codec::HexCodec;
+//! use tokio_util::codec::{Decoder, Encoder};
+//!
+//! let mut codec = HexCodec::lines();
+//! let mut buf = BytesMut::new();
+//! codec.encode(&b"kiwi"[..], &mut buf)?;
+//! assert_eq!(buf, "6b697769\n");
+//!
+//! let mut input = BytesMut::from("6b69\r\n77");
+//! assert_eq!(codec.decode(&mut input)?.unwrap(), "ki");
+//! assert_eq!(codec.decode(&mut input)?, None);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+use std::{cmp, io};
+
+use bytes::{Buf, BufMut, BytesMut};
+use tokio_util::codec::{Decoder, Encoder, LengthDelimitedCodec};
+
+use crate::FromHexError;
+
+// Wraps a decoding error into an `io::Error`.
+fn invalid_data(err: FromHexError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// The framing of the hex sent by a [`HexCodec`].
+#[derive(Debug)]
+enum Framing {
+    Lines {
+        // Where to resume looking for a newline in the buffered input.
+        next_index: usize,
+        max_length: usize,
+        // Whether the rest of a line that was too long is being skipped.
+        is_discarding: bool,
+    },
+    LengthDelimited(LengthDelimitedCodec),
+}
+
+/// A codec hex-encoding frames, delimited by newlines or by length prefixes.
+///
+/// Decoded frames are yielded as [`BytesMut`], and invalid hex is reported as
+/// an error of kind [`io::ErrorKind::InvalidData`] wrapping a
+/// [`FromHexError`], whose index is relative to the start of the frame.
+#[derive(Debug)]
+pub struct HexCodec {
+    framing: Framing,
+}
+
+impl HexCodec {
+    /// Creates a codec for frames terminated by `\n`.
+    ///
+    /// A `\r` before the newline is ignored when decoding, and a final
+    /// frame without a newline is decoded at the end of the stream.
+    ///
+    /// Lines can be of any length, so a peer that never sends a newline
+    /// makes the decoder buffer without bound. Use
+    /// [`HexCodec::lines_with_max_length`] for input that isn't trusted.
+    pub fn lines() -> Self {
+        HexCodec::lines_with_max_length(usize::MAX)
+    }
+
+    /// Creates a codec for frames terminated by `\n` of at most `max_length`
+    /// hex characters, not counting the newline.
+    ///
+    /// A longer line is reported as an error of kind
+    /// [`io::ErrorKind::InvalidData`], and the rest of it is skipped, so
+    /// decoding can go on with the next line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::BytesMut;
+    /// use tokio_util::codec::Decoder;
+    ///
+    /// let mut codec = hex::codec::HexCodec::lines_with_max_length(4);
+    /// let mut input = BytesMut::from("0102\n010203\n03\n");
+    /// assert_eq!(codec.decode(&mut input)?.unwrap(), b"\x01\x02"[..]);
+    /// assert!(codec.decode(&mut input).is_err());
+    /// assert_eq!(codec.decode(&mut input)?.unwrap(), b"\x03"[..]);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn lines_with_max_length(max_length: usize) -> Self {
+        HexCodec {
+            framing: Framing::Lines {
+                next_index: 0,
+                max_length,
+                is_discarding: false,
+            },
+        }
+    }
+
+    /// Creates a codec for frames prefixed with their length, as a 4-byte
+    /// big-endian number of hex characters.
+    ///
+    /// Use [`HexCodec::with_length_delimited`] for another prefix format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::BytesMut;
+    /// use tokio_util::codec::Encoder;
+    ///
+    /// let mut buf = BytesMut::new();
+    /// hex::codec::HexCodec::length_delimited().encode(&[0xab][..], &mut buf)?;
+    /// assert_eq!(buf, b"\0\0\0\x02ab"[..]);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn length_delimited() -> Self {
+        HexCodec::with_length_delimited(LengthDelimitedCodec::new())
+    }
+
+    /// Creates a codec for frames delimited by `codec`, e.g. one configured
+    /// with a different length field or maximum frame length.
+    pub fn with_length_delimited(codec: LengthDelimitedCodec) -> Self {
+        HexCodec {
+            framing: Framing::LengthDelimited(codec),
+        }
+    }
+}
+
+// Decodes a whole frame of hex.
+fn decode_frame(mut frame: BytesMut) -> io::Result<BytesMut> {
+    let mut out = BytesMut::with_capacity(frame.len() / 2);
+    crate::bytes::decode_from_buf(&mut frame, &mut out).map_err(invalid_data)?;
+    Ok(out)
+}
+
+impl Decoder for HexCodec {
+    type Item = BytesMut;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<BytesMut>> {
+        match &mut self.framing {
+            Framing::Lines {
+                next_index,
+                max_length,
+                is_discarding,
+            } => loop {
+                // only look one past the limit, to tell a line which is
+                // exactly `max_length` long from one which is too long
+                let read_to = cmp::min(max_length.saturating_add(1), src.len());
+                let newline = src[*next_index..read_to].iter().position(|&c| c == b'\n');
+                match (*is_discarding, newline) {
+                    (true, Some(offset)) => {
+                        src.advance(*next_index + offset + 1);
+                        *next_index = 0;
+                        *is_discarding = false;
+                    }
+                    (true, None) => {
+                        src.advance(read_to);
+                        *next_index = 0;
+                        if src.is_empty() {
+                            return Ok(None);
+                        }
+                    }
+                    (false, Some(offset)) => {
+                        let end = *next_index + offset;
+                        *next_index = 0;
+                        let mut line = src.split_to(end + 1);
+                        line.truncate(end);
+                        if line.last() == Some(&b'\r') {
+                            line.truncate(end - 1);
+                        }
+                        return decode_frame(line).map(Some);
+                    }
+                    (false, None) if src.len() > *max_length => {
+                        *is_discarding = true;
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "hex line is longer than the maximum length",
+                        ));
+                    }
+                    (false, None) => {
+                        *next_index = read_to;
+                        return Ok(None);
+                    }
+                }
+            },
+            Framing::LengthDelimited(codec) => match codec.decode(src)? {
+                Some(frame) => decode_frame(frame).map(Some),
+                None => Ok(None),
+            },
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> io::Result<Option<BytesMut>> {
+        if let Framing::LengthDelimited(codec) = &mut self.framing {
+            return codec.decode_eof(src)?.map(decode_frame).transpose();
+        }
+        match self.decode(src)? {
+            Some(line) => Ok(Some(line)),
+            None if src.is_empty() => Ok(None),
+            // the last line doesn't need a newline
+            None => {
+                if let Framing::Lines { next_index, .. } = &mut self.framing {
+                    *next_index = 0;
+                }
+                decode_frame(src.split()).map(Some)
+            }
+        }
+    }
+}
+
+impl<T: AsRef<[u8]>> Encoder<T> for HexCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> io::Result<()> {
+        let data = item.as_ref();
+        match &mut self.framing {
+            Framing::Lines { .. } => {
+                dst.reserve(data.len() * 2 + 1);
+                crate::bytes::encode_to_bufmut(data, dst);
+                dst.put_u8(b'\n');
+                Ok(())
+            }
+            Framing::LengthDelimited(codec) => {
+                let mut frame = BytesMut::with_capacity(data.len() * 2);
+                crate::bytes::encode_to_bufmut(data, &mut frame);
+                codec.encode(frame.freeze(), dst)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_lines() {
+        let mut codec = HexCodec::lines();
+        let mut buf = BytesMut::new();
+        codec.encode([0x01, 0x02], &mut buf).unwrap();
+        codec.encode([], &mut buf).unwrap();
+        codec.encode(vec![0xff], &mut buf).unwrap();
+        assert_eq!(buf, "0102\n\nff\n");
+
+        buf.extend_from_slice(b"ab");
+        assert_eq!(codec.decode(&mut buf).unwrap().unwrap(), &[1, 2][..]);
+        assert_eq!(codec.decode(&mut buf).unwrap().unwrap(), &[][..]);
+        assert_eq!(codec.decode(&mut buf).unwrap().unwrap(), &[0xff][..]);
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        assert_eq!(codec.decode_eof(&mut buf).unwrap().unwrap(), &[0xab][..]);
+        assert_eq!(codec.decode_eof(&mut buf).unwrap(), None);
+    }
+
+    #[test]
+    fn test_lines_partial() {
+        let mut codec = HexCodec::lines();
+        let mut buf = BytesMut::from("01");
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        buf.extend_from_slice(b"02");
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        buf.extend_from_slice(b"\r\nff");
+        assert_eq!(codec.decode(&mut buf).unwrap().unwrap(), &[1, 2][..]);
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        buf.extend_from_slice(b"\n");
+        assert_eq!(codec.decode(&mut buf).unwrap().unwrap(), &[0xff][..]);
+    }
+
+    #[test]
+    fn test_lines_max_length() {
+        let mut codec = HexCodec::lines_with_max_length(4);
+        let mut buf = BytesMut::from("0102\n0102");
+        assert_eq!(codec.decode(&mut buf).unwrap().unwrap(), &[1, 2][..]);
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+
+        // the overlong line is skipped, even when it comes in pieces
+        buf.extend_from_slice(b"03");
+        let err = codec.decode(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        assert!(buf.is_empty());
+        buf.extend_from_slice(b"0405\nab");
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        assert_eq!(buf, "ab");
+        assert_eq!(codec.decode_eof(&mut buf).unwrap().unwrap(), &[0xab][..]);
+
+        let mut buf = BytesMut::from("0102\r\n");
+        assert!(codec.decode(&mut buf).is_err());
+    }
+
+    #[test]
+    fn test_length_delimited() {
+        let mut codec = HexCodec::length_delimited();
+        let mut buf = BytesMut::new();
+        codec.encode(b"kiwi", &mut buf).unwrap();
+        assert_eq!(buf, &b"\0\0\0\x086b697769"[..]);
+
+        let mut partial = buf.split_to(6);
+        assert_eq!(codec.decode(&mut partial).unwrap(), None);
+        partial.unsplit(buf);
+        assert_eq!(codec.decode_eof(&mut partial).unwrap().unwrap(), "kiwi");
+        assert_eq!(codec.decode_eof(&mut partial).unwrap(), None);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut codec = HexCodec::lines();
+        let mut buf = BytesMut::from("00\n0g\n");
+        codec.decode(&mut buf).unwrap();
+        let err = codec.decode(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.into_inner().unwrap().downcast_ref::<FromHexError>(),
+            Some(&FromHexError::InvalidHexCharacter { c: 'g', index: 1 })
+        );
+
+        let mut buf = BytesMut::from("abc");
+        let err = codec.decode_eof(&mut buf).unwrap_err();
+        assert_eq!(
+            err.into_inner().unwrap().downcast_ref::<FromHexError>(),
+            Some(&FromHexError::OddLength)
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use core::{cmp::Ordering, iter};
+
+use crate::{lenient::Lenient, DECODE_TABLE};
+
+// Returns the digits of `hex` as nibble values. Non-digits map to
+// `tables::INVALID`, which is larger than every nibble.
+fn hex_nibbles(hex: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    hex.iter().map(|&c| DECODE_TABLE[c as usize])
+}
+
+// Returns the nibbles of `bytes`, most significant first.
+fn byte_nibbles(bytes: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    bytes
+        .iter()
+        .flat_map(|&byte| iter::once(byte >> 4).chain(iter::once(byte & 0x0f)))
+}
+
+/// Compares a hex string with raw bytes, as if the hex string was decoded.
+///
+/// The comparison is lexicographic, like the comparison of byte slices, and
+/// doesn't allocate. This allows probing a sorted index keyed by hex strings
+/// with raw byte keys. The case of the digits doesn't matter, and an odd
+/// number of digits orders like a prefix of the bytes they start. The input
+/// isn't validated; characters which aren't hex digits order after all
+/// digits.
+///
+/// # Example
+///
+/// ```
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(hex::cmp_hex_bytes("6B6977", b"kiw"), Ordering::Equal);
+/// assert_eq!(hex::cmp_hex_bytes("6b6977", b"kiwi"), Ordering::Less);
+/// assert_eq!(hex::cmp_hex_bytes("ff", b"kiwi"), Ordering::Greater);
+///
+/// let index = ["0010", "00ff", "0a00"];
+/// assert_eq!(index.binary_search_by(|key| hex::cmp_hex_bytes(key, &[0x00, 0xff])), Ok(1));
+/// ```
+pub fn cmp_hex_bytes<T: AsRef<[u8]>>(hex: T, bytes: &[u8]) -> Ordering {
+    hex_nibbles(hex.as_ref()).cmp(byte_nibbles(bytes))
+}
+
+/// Compares two hex strings, as if they were decoded.
+///
+/// Like [`cmp_hex_bytes`], this is lexicographic, ignores the case of the
+/// digits and doesn't validate the input.
+///
+/// # Example
+///
+/// ```
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(hex::cmp_hex("ABcd", "abCD"), Ordering::Equal);
+/// assert_eq!(hex::cmp_hex("0A", "0a00"), Ordering::Less);
+/// assert_eq!(hex::cmp_hex("b0", "a000"), Ordering::Greater);
+/// ```
+pub fn cmp_hex<T: AsRef<[u8]>, U: AsRef<[u8]>>(a: T, b: U) -> Ordering {
+    hex_nibbles(a.as_ref()).cmp(hex_nibbles(b.as_ref()))
+}
+
+/// Returns the offset of the first occurrence of `needle` in the hex string
+/// `haystack`, as if the haystack was decoded.
+///
+/// Only byte-aligned positions match, so the returned offset into `haystack`
+/// is always even; halve it for the offset into the decoded bytes. The case
+/// of the digits doesn't matter, and the haystack isn't decoded or validated
+/// as a whole: characters which aren't hex digits never match.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::find_bytes("00DEADbeef00", &[0xad, 0xbe]), Some(4));
+/// // "ea" isn't byte-aligned
+/// assert_eq!(hex::find_bytes("deadbeef", &[0xea]), None);
+/// assert_eq!(hex::find_bytes("cafe", &[]), Some(0));
+/// ```
+pub fn find_bytes<T: AsRef<[u8]>>(haystack: T, needle: &[u8]) -> Option<usize> {
+    let haystack = haystack.as_ref();
+    let len = needle.len() * 2;
+    (0..(haystack.len() / 2 + 1).saturating_sub(needle.len()))
+        .map(|i| i * 2)
+        .find(|&start| cmp_hex_bytes(&haystack[start..start + len], needle).is_eq())
+}
+
+/// Returns `true` if two hex strings encode the same bytes, ignoring their
+/// formatting.
+///
+/// The case of the digits doesn't matter, and everything the
+/// [lenient decoder](crate::lenient) skips is ignored: whitespace, the
+/// separators `:`, `-`, `_` and `,`, and `0x` prefixes. Nothing is
+/// allocated. Strings which wouldn't decode, because of an invalid character
+/// or an odd number of digits, are never equal.
+///
+/// # Example
+///
+/// ```
+/// assert!(hex::eq_ignore_format("0xDEADBEEF", "de:ad:be:ef"));
+/// assert!(hex::eq_ignore_format("de ad be ef", "DE-AD-BE-EF"));
+/// assert!(!hex::eq_ignore_format("deadbeef", "deadbe"));
+/// assert!(!hex::eq_ignore_format("abc", "abc"));
+/// ```
+pub fn eq_ignore_format(a: &str, b: &str) -> bool {
+    let lenient = Lenient::new();
+    let mut a = lenient.digits(a);
+    let mut b = lenient.digits(b);
+    let mut even = true;
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return even,
+            (Some(Ok(x)), Some(Ok(y))) if x == y => even = !even,
+            _ => return false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_cmp_hex_bytes() {
+        let keys: [&[u8]; 6] = [b"", b"\x00", b"\x00\x00", b"\x0f", b"\xf0", b"\xff\xff"];
+        for a in keys {
+            for b in keys {
+                let mut buf = [0; 4];
+                let hex = crate::encode_to_slice(a, &mut buf[..2 * a.len()]).unwrap();
+                assert_eq!(cmp_hex_bytes(&*hex, b), a.cmp(b), "{:?} {:?}", a, b);
+                hex.make_ascii_uppercase();
+                assert_eq!(cmp_hex_bytes(&*hex, b), a.cmp(b), "{:?} {:?}", a, b);
+            }
+        }
+
+        // a trailing nibble orders like a prefix
+        assert_eq!(cmp_hex_bytes("0", b"\x00"), Ordering::Less);
+        assert_eq!(cmp_hex_bytes("1", b"\x00"), Ordering::Greater);
+        assert_eq!(cmp_hex_bytes("0g", b"\x0f"), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_cmp_hex() {
+        assert_eq!(cmp_hex("", ""), Ordering::Equal);
+        assert_eq!(cmp_hex("", "00"), Ordering::Less);
+        assert_eq!(cmp_hex("fF", "Ff"), Ordering::Equal);
+        assert_eq!(cmp_hex("0f", "F0"), Ordering::Less);
+        assert_eq!(cmp_hex("0a0b", "0A"), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_find_bytes() {
+        assert_eq!(find_bytes("", &[]), Some(0));
+        assert_eq!(find_bytes("", &[0]), None);
+        assert_eq!(find_bytes("0", &[0]), None);
+        assert_eq!(find_bytes("ab", &[0xab]), Some(0));
+        assert_eq!(find_bytes("00aB", &[0xab]), Some(2));
+        assert_eq!(find_bytes("abcdef", &[0xbc]), None);
+        assert_eq!(find_bytes("abcdefbc", &[0xbc]), Some(6));
+        assert_eq!(find_bytes("ab0", &[0xb0]), None);
+        assert_eq!(find_bytes("zz01", &[0x01]), Some(2));
+        assert_eq!(find_bytes("0102", &[1, 2, 3]), None);
+    }
+
+    #[test]
+    fn test_eq_ignore_format() {
+        assert!(eq_ignore_format("", ""));
+        assert!(eq_ignore_format("", " : "));
+        assert!(eq_ignore_format("0x0a0B", "0A 0b"));
+        assert!(eq_ignore_format("0x0a, 0x0b", "0a0b"));
+        assert!(!eq_ignore_format("0a0b", "0a0c"));
+        assert!(!eq_ignore_format("0a0b", "0a0b0c"));
+        assert!(!eq_ignore_format("0a0g", "0a0g"));
+        assert!(!eq_ignore_format("0a0", "0a0"));
+        // "x" is only skipped as part of a prefix
+        assert!(!eq_ignore_format("0a", "0x0x0a"));
+    }
+}
@@ -0,0 +1,178 @@
+//! Functions named and shaped like those of the `faster-hex` crate.
+//!
+//! Projects moving to this crate can replace `faster_hex::` with
+//! `hex::compat::` instead of renaming every call. The signatures follow
+//! `faster-hex` 1.0, with [`FromHexError`] as the error type:
+//!
+//! | `faster_hex::Error`  | [`FromHexError`]                        |
+//! | -------------------- | --------------------------------------- |
+//! | `InvalidChar`        | [`FromHexError::InvalidHexCharacter`]   |
+//! | `OddLength`          | [`FromHexError::OddLength`]             |
+//! | `OutputTooSmall`     | [`FromHexError::InvalidStringLength`]   |
+//!
+//! Unlike `faster-hex`, decoding may have written to the destination when it
+//! fails on an invalid character.
+//!
+//! # Example
+//!
+//! ```
+//! use hex::compat::{hex_check, hex_decode, hex_encode};
+//!
+//! let mut encoded = [0; 16];
+//! let text = hex_encode(b"kiwi", &mut encoded)?;
+//! assert_eq!(text, "6b697769");
+//! assert!(hex_check(text.as_bytes()));
+//!
+//! let mut decoded = [0; 4];
+//! assert_eq!(hex_decode(text.as_bytes(), &mut decoded)?, b"kiwi");
+//! # Ok::<(), hex::FromHexError>(())
+//! ```
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::{Case, FromHexError, DECODE_TABLE};
+
+/// Encodes `src` as lowercase hex. This is [`encode`](crate::encode).
+#[must_use]
+#[cfg(feature = "alloc")]
+pub fn hex_string(src: &[u8]) -> String {
+    crate::encode(src)
+}
+
+/// Encodes `src` as uppercase hex. This is
+/// [`encode_upper`](crate::encode_upper).
+#[must_use]
+#[cfg(feature = "alloc")]
+pub fn hex_string_upper(src: &[u8]) -> String {
+    crate::encode_upper(src)
+}
+
+// Encodes `src` into the beginning of `dst`.
+fn encode_prefix<'a>(
+    src: &[u8],
+    dst: &'a mut [u8],
+    case: Case,
+) -> Result<&'a mut str, FromHexError> {
+    let dst = dst
+        .get_mut(..src.len() * 2)
+        .ok_or(FromHexError::InvalidStringLength)?;
+    crate::encode_to_slice_case(src, dst, case)
+}
+
+/// Encodes `src` as lowercase hex into the beginning of `dst`, returning the
+/// written part.
+///
+/// `dst` may be larger than needed. If it is too short,
+/// [`FromHexError::InvalidStringLength`] is returned and it is left
+/// unchanged.
+///
+/// # Example
+///
+/// ```
+/// let mut dst = [b'.'; 8];
+/// assert_eq!(hex::compat::hex_encode(&[0xab, 0xcd], &mut dst)?, "abcd");
+/// assert_eq!(&dst, b"abcd....");
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+pub fn hex_encode<'a>(src: &[u8], dst: &'a mut [u8]) -> Result<&'a mut str, FromHexError> {
+    encode_prefix(src, dst, Case::Lower)
+}
+
+/// Encodes `src` as uppercase hex into the beginning of `dst`, returning the
+/// written part.
+///
+/// See [`hex_encode`] for details.
+pub fn hex_encode_upper<'a>(src: &[u8], dst: &'a mut [u8]) -> Result<&'a mut str, FromHexError> {
+    encode_prefix(src, dst, Case::Upper)
+}
+
+/// Decodes all of `src` into the beginning of `dst`, returning the written
+/// part.
+///
+/// `dst` may be larger than needed. Errors are checked in the same order as
+/// by `faster-hex`: an odd length first, then the length of `dst`, then the
+/// characters.
+///
+/// # Example
+///
+/// ```
+/// let mut dst = [0; 4];
+/// assert_eq!(hex::compat::hex_decode(b"00aBcD", &mut dst)?, [0x00, 0xab, 0xcd]);
+/// assert_eq!(
+///     hex::compat::hex_decode(b"00aBcD", &mut dst[..2]),
+///     Err(hex::FromHexError::InvalidStringLength)
+/// );
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+pub fn hex_decode<'a>(src: &[u8], dst: &'a mut [u8]) -> Result<&'a mut [u8], FromHexError> {
+    let len = crate::decode_to_slice_prefix(src, dst)?;
+    Ok(&mut dst[..len])
+}
+
+/// Returns `true` if `src` consists of hex digits only.
+///
+/// Like in `faster-hex`, only the characters are checked, so the empty input
+/// and inputs of odd length are accepted.
+///
+/// # Example
+///
+/// ```
+/// use hex::compat::hex_check;
+///
+/// assert!(hex_check(b"00aBcD"));
+/// assert!(hex_check(b"a"));
+/// assert!(!hex_check(b"0x01"));
+/// ```
+#[must_use]
+pub fn hex_check(src: &[u8]) -> bool {
+    src.iter().all(|&c| DECODE_TABLE[c as usize] != u8::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_hex_encode() {
+        let mut dst = [0; 8];
+        assert_eq!(hex_encode(&[], &mut dst).unwrap(), "");
+        assert_eq!(hex_encode_upper(&[0xab, 0xcd], &mut dst).unwrap(), "ABCD");
+        assert_eq!(hex_encode(&[0; 4], &mut dst).unwrap(), "00000000");
+
+        let mut dst = [b'.'; 3];
+        assert_eq!(
+            hex_encode(&[0xab, 0xcd], &mut dst),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(&dst, b"...");
+    }
+
+    #[test]
+    fn test_hex_decode() {
+        let mut dst = [0; 4];
+        assert_eq!(hex_decode(b"", &mut dst), Ok(&mut [][..]));
+        assert_eq!(hex_decode(b"abc", &mut dst), Err(FromHexError::OddLength));
+        // the length of the output is checked before the characters
+        assert_eq!(
+            hex_decode(b"gggggggggg", &mut dst),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            hex_decode(b"00ff0g", &mut dst),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 5 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_faster_hex() {
+        let data: alloc::vec::Vec<u8> = (0..=255).collect();
+        assert_eq!(hex_string(&data), faster_hex::hex_string(&data).unwrap());
+        assert_eq!(hex_string_upper(&data), hex_string(&data).to_uppercase());
+
+        for c in 0..=255 {
+            assert_eq!(hex_check(&[c, c]), faster_hex::hex_check_fallback(&[c, c]));
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! Implementations of the [`diesel`] serialization traits for the wrapper
+//! types.
+//!
+//! [`HexString`] maps to `TEXT` columns, [`FixedHex`] to `BLOB`/`BYTEA`
+//! columns holding the raw bytes.
+use diesel::{
+    backend::Backend,
+    deserialize::{self, FromSql},
+    serialize::{self, Output, ToSql},
+    sql_types::{Binary, Text},
+};
+use std::convert::TryFrom;
+
+use crate::{FixedHex, HexString};
+
+impl<DB> ToSql<Text, DB> for HexString
+where
+    DB: Backend,
+    str: ToSql<Text, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        self.as_str().to_sql(out)
+    }
+}
+
+impl<DB> FromSql<Text, DB> for HexString
+where
+    DB: Backend,
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        Ok(HexString::new(String::from_sql(bytes)?)?)
+    }
+}
+
+impl<DB, const N: usize> ToSql<Binary, DB> for FixedHex<N>
+where
+    DB: Backend,
+    [u8]: ToSql<Binary, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        self.0[..].to_sql(out)
+    }
+}
+
+impl<DB, const N: usize> FromSql<Binary, DB> for FixedHex<N>
+where
+    DB: Backend,
+    Vec<u8>: FromSql<Binary, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        let bytes = Vec::from_sql(bytes)?;
+        let len = bytes.len();
+        match <[u8; N]>::try_from(bytes) {
+            Ok(bytes) => Ok(FixedHex(bytes)),
+            Err(_) => Err(format!("expected {} bytes, got {}", N, len).into()),
+        }
+    }
+}
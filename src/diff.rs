@@ -0,0 +1,360 @@
+//! Alignment-aware diffs of byte sequences, rendered as hex.
+//!
+//! Unlike a positional comparison (as done by [`assert_eq_hex!`]), a diff
+//! detects inserted and removed bytes, so a single extra byte near the start
+//! of the input does not make everything after it look different. This is
+//! useful for debugging encoders where data shifts rather than corrupts.
+//!
+//! The diff is computed using Myers' algorithm, which takes `O((N + M) * D)`
+//! time and `O(D²)` memory, where `D` is the number of differing bytes.
+//!
+//! # Example
+//!
+//! ```
+//! let old = b"Hello world!";
+//! let new = b"Hello, world!";
+//!
+//! let diff = hex::diff::diff(old, new);
+//! assert_eq!(
+//!     diff.to_string(),
+//!     "  00000000 00000000  48 65 6c 6c 6f\n\
+//!      +          00000005  2c\n\
+//!      \x20 00000005 00000006  20 77 6f 72 6c 64 21\n"
+//! );
+//! ```
+//!
+//! [`assert_eq_hex!`]: crate::assert_eq_hex
+use alloc::vec::Vec;
+use core::{fmt, ops::Range};
+
+// Number of bytes per rendered line, and number of lines shown at either end
+// of an unchanged run before it is collapsed.
+const LINE_LEN: usize = 16;
+const CONTEXT_LINES: usize = 2;
+
+/// A single step of a [`Diff`].
+///
+/// Ranges and positions are byte offsets into the old and the new input
+/// respectively.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Op {
+    /// `old[old]` and `new[new]` are equal.
+    Equal {
+        old: Range<usize>,
+        new: Range<usize>,
+    },
+    /// `old[old]` was removed; it was located before `new[new]`.
+    Delete { old: Range<usize>, new: usize },
+    /// `new[new]` was inserted; it is located before `old[old]`.
+    Insert { old: usize, new: Range<usize> },
+}
+
+/// The difference between two byte sequences.
+///
+/// This struct is created by [`diff()`]. Its `Display` implementation renders
+/// the diff as annotated hex: every line starts with ` `, `-` or `+`,
+/// followed by the offsets into the old and the new input and the bytes.
+#[derive(Debug, Clone)]
+pub struct Diff<'a> {
+    old: &'a [u8],
+    new: &'a [u8],
+    ops: Vec<Op>,
+}
+
+impl<'a> Diff<'a> {
+    /// Returns the steps transforming the old input into the new one.
+    #[must_use]
+    pub fn ops(&self) -> &[Op] {
+        &self.ops
+    }
+
+    /// Returns `true` if both inputs were equal.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ops.iter().all(|op| matches!(op, Op::Equal { .. }))
+    }
+}
+
+/// Computes the difference between `old` and `new`.
+#[must_use]
+pub fn diff<'a>(old: &'a [u8], new: &'a [u8]) -> Diff<'a> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut ops = Vec::new();
+    if prefix > 0 {
+        ops.push(Op::Equal {
+            old: 0..prefix,
+            new: 0..prefix,
+        });
+    }
+    let edits = myers(
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+    for (edit, (x, y)) in edits {
+        push_edit(&mut ops, edit, prefix + x, prefix + y);
+    }
+    if suffix > 0 {
+        ops.push(Op::Equal {
+            old: old.len() - suffix..old.len(),
+            new: new.len() - suffix..new.len(),
+        });
+    }
+
+    Diff { old, new, ops }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Edit {
+    Equal,
+    Delete,
+    Insert,
+}
+
+// Appends a single byte edit located at `(x, y)` to `ops`, merging it with
+// the previous op if possible.
+fn push_edit(ops: &mut Vec<Op>, edit: Edit, x: usize, y: usize) {
+    match (ops.last_mut(), edit) {
+        (Some(Op::Equal { old, new }), Edit::Equal) if old.end == x && new.end == y => {
+            old.end += 1;
+            new.end += 1;
+        }
+        (Some(Op::Delete { old, new }), Edit::Delete) if old.end == x && *new == y => {
+            old.end += 1;
+        }
+        (Some(Op::Insert { old, new }), Edit::Insert) if *old == x && new.end == y => {
+            new.end += 1;
+        }
+        (_, Edit::Equal) => ops.push(Op::Equal {
+            old: x..x + 1,
+            new: y..y + 1,
+        }),
+        (_, Edit::Delete) => ops.push(Op::Delete {
+            old: x..x + 1,
+            new: y,
+        }),
+        (_, Edit::Insert) => ops.push(Op::Insert {
+            old: x,
+            new: y..y + 1,
+        }),
+    }
+}
+
+// Returns the shortest edit script turning `a` into `b`, as a list of edits
+// and the positions they apply at, in order.
+fn myers(a: &[u8], b: &[u8]) -> Vec<(Edit, (usize, usize))> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = n + m;
+    let offset = max + 1;
+    let mut v = alloc::vec![0_isize; 2 * max as usize + 3];
+    // trace[d] holds v[-d - 1..=d + 1] as it was before step `d`.
+    let mut trace = Vec::new();
+
+    'outer: for d in 0..=max {
+        trace.push(v[(offset - d - 1) as usize..=(offset + d + 1) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let i = (offset + k) as usize;
+            let mut x = if k == -d || (k != d && v[i - 1] < v[i + 1]) {
+                v[i + 1]
+            } else {
+                v[i - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[i] = x;
+            if x >= n && y >= m {
+                break 'outer;
+            }
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let get = |k: isize| v[(k + d + 1) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && get(k - 1) < get(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = get(prev_k);
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            edits.push((Edit::Equal, (x as usize, y as usize)));
+        }
+        if d > 0 {
+            if x == prev_x {
+                edits.push((Edit::Insert, (x as usize, prev_y as usize)));
+            } else {
+                edits.push((Edit::Delete, (prev_x as usize, y as usize)));
+            }
+            x = prev_x;
+            y = prev_y;
+        }
+    }
+    edits.reverse();
+    edits
+}
+
+fn write_line(
+    f: &mut fmt::Formatter,
+    sign: char,
+    old: Option<usize>,
+    new: Option<usize>,
+    bytes: &[u8],
+) -> fmt::Result {
+    write!(f, "{} ", sign)?;
+    match old {
+        Some(offset) => write!(f, "{:08x} ", offset)?,
+        None => f.write_str("         ")?,
+    }
+    match new {
+        Some(offset) => write!(f, "{:08x} ", offset)?,
+        None => f.write_str("         ")?,
+    }
+    for byte in bytes {
+        write!(f, " {:02x}", byte)?;
+    }
+    f.write_str("\n")
+}
+
+impl fmt::Display for Diff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for op in &self.ops {
+            match op {
+                Op::Equal { old, new } => {
+                    let lines = (old.len() + LINE_LEN - 1) / LINE_LEN;
+                    for line in 0..lines {
+                        if lines > 2 * CONTEXT_LINES + 1
+                            && line >= CONTEXT_LINES
+                            && line < lines - CONTEXT_LINES
+                        {
+                            if line == CONTEXT_LINES {
+                                let skipped = (lines - 2 * CONTEXT_LINES) * LINE_LEN;
+                                writeln!(f, "  ... ({} equal bytes)", skipped)?;
+                            }
+                            continue;
+                        }
+                        let start = line * LINE_LEN;
+                        let end = (start + LINE_LEN).min(old.len());
+                        write_line(
+                            f,
+                            ' ',
+                            Some(old.start + start),
+                            Some(new.start + start),
+                            &self.old[old.start + start..old.start + end],
+                        )?;
+                    }
+                }
+                Op::Delete { old, .. } => {
+                    for start in old.clone().step_by(LINE_LEN) {
+                        let end = (start + LINE_LEN).min(old.end);
+                        write_line(f, '-', Some(start), None, &self.old[start..end])?;
+                    }
+                }
+                Op::Insert { new, .. } => {
+                    for start in new.clone().step_by(LINE_LEN) {
+                        let end = (start + LINE_LEN).min(new.end);
+                        write_line(f, '+', None, Some(start), &self.new[start..end])?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{string::ToString, vec};
+    use pretty_assertions::assert_eq;
+
+    // Applies the diff to `old`, which has to result in `new`.
+    fn apply(diff: &Diff) -> Vec<u8> {
+        let mut out = Vec::new();
+        for op in diff.ops() {
+            match op {
+                Op::Equal { old, new } => {
+                    assert_eq!(&diff.old[old.clone()], &diff.new[new.clone()]);
+                    out.extend_from_slice(&diff.old[old.clone()]);
+                }
+                Op::Delete { .. } => {}
+                Op::Insert { new, .. } => out.extend_from_slice(&diff.new[new.clone()]),
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_diff_ops() {
+        let diff = diff(b"abcdef", b"abXcdf");
+        assert_eq!(
+            diff.ops(),
+            [
+                Op::Equal {
+                    old: 0..2,
+                    new: 0..2
+                },
+                Op::Insert { old: 2, new: 2..3 },
+                Op::Equal {
+                    old: 2..4,
+                    new: 3..5
+                },
+                Op::Delete { old: 4..5, new: 5 },
+                Op::Equal {
+                    old: 5..6,
+                    new: 5..6
+                },
+            ]
+        );
+        assert!(!diff.is_empty());
+        assert!(super::diff(b"same", b"same").is_empty());
+        assert!(super::diff(b"", b"").ops().is_empty());
+    }
+
+    #[test]
+    fn test_diff_apply() {
+        let cases: &[(&[u8], &[u8])] = &[
+            (b"", b"abc"),
+            (b"abc", b""),
+            (b"kitten", b"sitting"),
+            (b"\x00\x01\x02\x03", b"\x03\x02\x01\x00"),
+            (b"ABCABBA", b"CBABAC"),
+        ];
+        for (old, new) in cases {
+            assert_eq!(apply(&diff(old, new)), *new);
+        }
+    }
+
+    #[test]
+    fn test_display_collapses_equal_runs() {
+        let old = vec![0_u8; 16 * 8];
+        let mut new = old.clone();
+        new.push(0xff);
+
+        assert_eq!(
+            diff(&old, &new).to_string(),
+            "  00000000 00000000  00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00\n\
+             \x20 00000010 00000010  00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00\n\
+             \x20 ... (64 equal bytes)\n\
+             \x20 00000060 00000060  00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00\n\
+             \x20 00000070 00000070  00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00\n\
+             +          00000080  ff\n"
+        );
+        assert_eq!(diff(b"\xab", b"").to_string(), "- 00000000           ab\n");
+    }
+}
@@ -0,0 +1,209 @@
+use core::{fmt, ops::Deref, ops::DerefMut};
+
+use crate::Case;
+
+/// A transparent wrapper which displays, debugs and (with the `serde`
+/// feature) serializes the wrapped bytes as lowercase hex.
+///
+/// Wrapping a field is enough to get hex output, without having to
+/// implement anything by hand or to add `#[serde(with = "hex")]` attributes.
+///
+/// # Example
+///
+/// ```
+/// use hex::Hex;
+///
+/// #[derive(Debug)]
+/// struct Packet {
+///     id: u32,
+///     payload: Hex<Vec<u8>>,
+/// }
+///
+/// let packet = Packet {
+///     id: 1,
+///     payload: Hex(vec![0xde, 0xad, 0xbe, 0xef]),
+/// };
+///
+/// assert_eq!(format!("{:?}", packet), "Packet { id: 1, payload: deadbeef }");
+/// assert_eq!(packet.payload.to_string(), "deadbeef");
+/// ```
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hex<T>(pub T);
+
+impl<T> Hex<T> {
+    /// Returns the wrapped value.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: AsRef<[u8]>> fmt::Display for Hex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        crate::fmt_hex(self.0.as_ref(), Case::Lower, f)
+    }
+}
+
+impl<T: AsRef<[u8]>> fmt::Debug for Hex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl<T> From<T> for Hex<T> {
+    fn from(value: T) -> Self {
+        Hex(value)
+    }
+}
+
+impl<T> Deref for Hex<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Hex<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: AsRef<[u8]>> AsRef<[u8]> for Hex<T> {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+// The size of the stack buffer used to encode chunks of an iterator.
+const BUF_LEN: usize = 128;
+
+/// Returns a value which displays the bytes yielded by `iter` as lowercase
+/// hex.
+///
+/// The iterator is cloned and consumed every time the value is formatted, so
+/// non-contiguous or generated byte sequences can be logged without
+/// collecting them first.
+///
+/// # Example
+///
+/// ```
+/// let header = [0xca, 0xfe];
+/// let body = [0xba, 0xbe];
+/// let packet = header.iter().chain(&body).copied();
+///
+/// assert_eq!(hex::display_iter(packet).to_string(), "cafebabe");
+/// assert_eq!(format!("{}", hex::display_iter(0..3)), "000102");
+/// ```
+pub fn display_iter<I>(iter: I) -> DisplayIter<I>
+where
+    I: IntoIterator<Item = u8> + Clone,
+{
+    DisplayIter(iter)
+}
+
+/// Displays the bytes yielded by an iterator as lowercase hex.
+///
+/// This struct is created by [`display_iter`].
+#[derive(Clone, Copy)]
+pub struct DisplayIter<I>(I);
+
+impl<I> fmt::Display for DisplayIter<I>
+where
+    I: IntoIterator<Item = u8> + Clone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut iter = self.0.clone().into_iter();
+        let mut buf = [0_u8; BUF_LEN];
+        loop {
+            let chunk = iter.by_ref().take(BUF_LEN / 2);
+            let hex = crate::encode_iter_to_slice(chunk, &mut buf).unwrap();
+            let done = hex.len() < BUF_LEN;
+            f.write_str(hex)?;
+            if done {
+                return Ok(());
+            }
+        }
+    }
+}
+
+impl<I> fmt::Debug for DisplayIter<I>
+where
+    I: IntoIterator<Item = u8> + Clone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Splits the hex encoding of `data` into pieces of at most `chunk_len`
+/// characters, displayed as lowercase hex.
+///
+/// The pieces never split a pair of digits, so an odd `chunk_len` is rounded
+/// down. Nothing is encoded up front; every piece is a [`Hex`] borrowing its
+/// part of `data`, which makes this suitable for splitting the encoding
+/// across fixed-size packets or lines.
+///
+/// # Panics
+///
+/// Panics if `chunk_len` is less than 2.
+///
+/// # Example
+///
+/// ```
+/// let lines: Vec<String> = hex::encode_chunks(b"kiwi!", 4).map(|c| c.to_string()).collect();
+/// assert_eq!(lines, ["6b69", "7769", "21"]);
+/// ```
+pub fn encode_chunks(data: &[u8], chunk_len: usize) -> impl ExactSizeIterator<Item = Hex<&[u8]>> {
+    assert!(
+        chunk_len >= 2,
+        "chunk_len must be at least 2, got {}",
+        chunk_len
+    );
+    data.chunks(chunk_len / 2).map(Hex)
+}
+
+#[cfg(test)]
+#[cfg(feature = "alloc")]
+mod tests {
+    use super::*;
+    use alloc::{format, string::ToString, vec, vec::Vec};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Hex(b"kiwi").to_string(), "6b697769");
+        assert_eq!(format!("{:?}", Hex(vec![1, 2, 255])), "0102ff");
+        assert_eq!(Hex("").to_string(), "");
+    }
+
+    #[test]
+    fn test_display_iter() {
+        let bytes = [0x5a_u8; 200];
+        let display = display_iter(bytes.iter().copied());
+        assert_eq!(display.to_string(), "5a".repeat(200));
+        // formatting again starts from the beginning
+        assert_eq!(format!("{:?}", display), "5a".repeat(200));
+
+        assert_eq!(display_iter(0..64).to_string(), crate::encode_iter(0..64));
+        assert_eq!(display_iter(None).to_string(), "");
+    }
+
+    #[test]
+    fn test_encode_chunks() {
+        let data = [0xab; 5];
+        let chunks: Vec<_> = encode_chunks(&data, 5).map(|c| c.to_string()).collect();
+        assert_eq!(chunks, ["abab", "abab", "ab"]);
+        assert_eq!(encode_chunks(&data, 2).len(), 5);
+        assert_eq!(encode_chunks(&data, 10).len(), 1);
+        assert_eq!(encode_chunks(&[], 2).len(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk_len must be at least 2, got 1")]
+    fn test_encode_chunks_too_short() {
+        let _ = encode_chunks(&[0], 1);
+    }
+}
@@ -0,0 +1,219 @@
+//! Hexdumps in the style of `xxd`, without allocating.
+//!
+//! Every line shows the offset of its first byte, up to [`LINE_LEN`] bytes
+//! as hex in groups of two, and the same bytes as text, with `.` for
+//! anything which isn't printable ASCII. This is the format of the `dump`
+//! command of the `hex` tool. Lines are rendered one at a time, into any
+//! [`fmt::Write`] or a caller-provided buffer, so memory regions can be
+//! dumped over a UART or RTT on embedded targets.
+//!
+//! # Example
+//!
+//! ```
+//! use hex::{dump, Case};
+//!
+//! let mut buf = [0; dump::MAX_LINE_LEN];
+//! let mut lines = dump::lines(b"Hello world!\nThis is a test.", 0x100, Case::Lower);
+//! assert_eq!(
+//!     lines.next().unwrap().write_to_slice(&mut buf)?,
+//!     "00000100: 4865 6c6c 6f20 776f 726c 6421 0a54 6869  Hello world!.Thi"
+//! );
+//! assert_eq!(
+//!     lines.next().unwrap().to_string(),
+//!     "00000110: 7320 6973 2061 2074 6573 742e            s is a test."
+//! );
+//! assert!(lines.next().is_none());
+//! # Ok::<(), hex::FromHexError>(())
+//! ```
+use core::{fmt, iter::FusedIterator, slice};
+
+use crate::{Case, FromHexError};
+
+/// The number of bytes shown per line.
+pub const LINE_LEN: usize = 16;
+
+/// The maximum length of a rendered line, which fits offsets up to
+/// `u64::MAX`.
+pub const MAX_LINE_LEN: usize = 16 + 1 + LINE_LEN / 2 * 5 + 2 + LINE_LEN;
+
+/// A single line of a hexdump, displayed without a trailing newline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Line<'a> {
+    offset: usize,
+    bytes: &'a [u8],
+    case: Case,
+}
+
+impl<'a> Line<'a> {
+    /// Creates a line showing `bytes`, which start at `offset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is longer than [`LINE_LEN`].
+    pub fn new(offset: usize, bytes: &'a [u8], case: Case) -> Self {
+        assert!(
+            bytes.len() <= LINE_LEN,
+            "a line holds at most {} bytes, got {}",
+            LINE_LEN,
+            bytes.len()
+        );
+        Line {
+            offset,
+            bytes,
+            case,
+        }
+    }
+
+    /// Renders the line into the beginning of `buf`, returning the rendered
+    /// part.
+    ///
+    /// If `buf` is too short, [`FromHexError::InvalidStringLength`] is
+    /// returned. A buffer of [`MAX_LINE_LEN`] bytes is always long enough.
+    pub fn write_to_slice<'b>(&self, buf: &'b mut [u8]) -> Result<&'b str, FromHexError> {
+        let mut writer = SliceWriter {
+            buf: &mut *buf,
+            len: 0,
+        };
+        fmt::write(&mut writer, format_args!("{}", self))
+            .map_err(|_| FromHexError::InvalidStringLength)?;
+        let len = writer.len;
+        // Saftey: The line only consists of ASCII characters
+        Ok(unsafe { core::str::from_utf8_unchecked(&buf[..len]) })
+    }
+}
+
+impl fmt::Display for Line<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:08x}:", self.offset)?;
+        let mut buf = [0; 2 * LINE_LEN];
+        let hex =
+            crate::encode_to_slice_case(self.bytes, &mut buf[..2 * self.bytes.len()], self.case)
+                .unwrap();
+        for start in (0..hex.len()).step_by(4) {
+            f.write_str(" ")?;
+            f.write_str(&hex[start..hex.len().min(start + 4)])?;
+        }
+        // Align the text column of a short line.
+        let missing = LINE_LEN - self.bytes.len();
+        write!(f, "{:1$}  ", "", missing * 2 + missing / 2)?;
+        for &byte in self.bytes {
+            let c = if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            };
+            fmt::Write::write_char(f, c)?;
+        }
+        Ok(())
+    }
+}
+
+// Writes into a fixed buffer, failing when it's full.
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl fmt::Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Returns the lines of a hexdump of `data`, the first byte of which is at
+/// `offset`.
+pub fn lines(data: &[u8], offset: usize, case: Case) -> Lines<'_> {
+    Lines {
+        chunks: data.chunks(LINE_LEN),
+        offset,
+        case,
+    }
+}
+
+/// An iterator over the lines of a hexdump.
+///
+/// This struct is created by [`lines`].
+#[derive(Debug, Clone)]
+pub struct Lines<'a> {
+    chunks: slice::Chunks<'a, u8>,
+    offset: usize,
+    case: Case,
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Line<'a>;
+
+    fn next(&mut self) -> Option<Line<'a>> {
+        let bytes = self.chunks.next()?;
+        let line = Line::new(self.offset, bytes, self.case);
+        self.offset = self.offset.wrapping_add(bytes.len());
+        Some(line)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Lines<'_> {}
+
+impl FusedIterator for Lines<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_write_to_slice() {
+        let mut buf = [0; MAX_LINE_LEN];
+        let line = Line::new(usize::MAX, &[0xff; LINE_LEN], Case::Upper);
+        let rendered = line.write_to_slice(&mut buf).unwrap();
+        assert!(rendered.starts_with("ffffffff"));
+        assert!(rendered.ends_with(": FFFF FFFF FFFF FFFF FFFF FFFF FFFF FFFF  ................"));
+        if cfg!(target_pointer_width = "64") {
+            assert_eq!(rendered.len(), MAX_LINE_LEN);
+        }
+
+        let line = Line::new(0, b"ab c", Case::Lower);
+        assert_eq!(
+            line.write_to_slice(&mut buf).unwrap(),
+            "00000000: 6162 2063                                ab c"
+        );
+        assert_eq!(
+            line.write_to_slice(&mut buf[..20]),
+            Err(FromHexError::InvalidStringLength)
+        );
+        let empty = Line::new(0x10, &[], Case::Lower);
+        let rendered = empty.write_to_slice(&mut buf).unwrap();
+        assert_eq!(rendered.trim_end(), "00000010:");
+        assert_eq!(rendered.len(), 9 + LINE_LEN / 2 * 5 + 2);
+    }
+
+    #[test]
+    fn test_lines() {
+        let data = [0; 2 * LINE_LEN + 1];
+        let lines = lines(&data, 8, Case::Lower);
+        assert_eq!(lines.len(), 3);
+        let offsets: [usize; 3] = {
+            let mut offsets = [0; 3];
+            for (offset, line) in offsets.iter_mut().zip(lines) {
+                *offset = line.offset;
+            }
+            offsets
+        };
+        assert_eq!(offsets, [8, 24, 40]);
+    }
+
+    #[test]
+    #[should_panic(expected = "a line holds at most 16 bytes, got 17")]
+    fn test_line_too_long() {
+        Line::new(0, &[0; LINE_LEN + 1], Case::Lower);
+    }
+}
@@ -0,0 +1,161 @@
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, string::String};
+
+#[cfg(feature = "alloc")]
+use crate::case_mask::CaseMask;
+use crate::Case;
+
+/// An encoding of bytes as hex, chosen at runtime.
+///
+/// Unlike [`ToHex`](crate::ToHex), this trait is object safe: it takes the
+/// data as a slice and writes to any [`fmt::Write`], so encoders can be
+/// stored as `Box<dyn HexWrite>` or `&dyn HexWrite`, e.g. in plugin
+/// registries or pipelines configured at runtime. It is implemented by
+/// [`Case`], by [`CaseMask`](crate::case_mask::CaseMask), and by references
+/// and boxes of implementors.
+///
+/// There is no blanket implementation for [`ToHex`](crate::ToHex) or
+/// [`FromHex`](crate::FromHex) types: those are implemented by the data
+/// being converted, while a `HexWrite` is the encoder, and is given the data.
+/// A blanket implementation would also overlap with the one for references,
+/// as references to byte containers implement `ToHex`.
+///
+/// # Example
+///
+/// ```
+/// use hex::{Case, HexWrite};
+///
+/// // A custom encoding, separating bytes with colons.
+/// struct Colons;
+///
+/// impl HexWrite for Colons {
+///     fn write_hex(&self, data: &[u8], out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+///         for (i, byte) in data.iter().enumerate() {
+///             if i > 0 {
+///                 out.write_char(':')?;
+///             }
+///             Case::Upper.write_hex(&[*byte], out)?;
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// let encoders: Vec<Box<dyn HexWrite>> = vec![Box::new(Case::Lower), Box::new(Colons)];
+/// let encoded: Vec<String> = encoders
+///     .iter()
+///     .map(|e| e.encode_to_string(b"\xca\xfe"))
+///     .collect::<Result<_, _>>()?;
+/// assert_eq!(encoded, ["cafe", "CA:FE"]);
+/// # }
+/// # Ok::<(), std::fmt::Error>(())
+/// ```
+pub trait HexWrite {
+    /// Writes `data` encoded as hex to `out`.
+    fn write_hex(&self, data: &[u8], out: &mut dyn fmt::Write) -> fmt::Result;
+
+    /// Encodes `data` as hex into a new string.
+    ///
+    /// Writing to a string can't fail, so an error can only come from the
+    /// implementation of [`HexWrite::write_hex`], and is passed on.
+    #[cfg(feature = "alloc")]
+    fn encode_to_string(&self, data: &[u8]) -> Result<String, fmt::Error> {
+        let mut out = String::with_capacity(data.len() * 2);
+        self.write_hex(data, &mut out)?;
+        Ok(out)
+    }
+}
+
+impl HexWrite for Case {
+    fn write_hex(&self, data: &[u8], out: &mut dyn fmt::Write) -> fmt::Result {
+        crate::fmt_hex(data, *self, out)
+    }
+}
+
+/// Applies the casing of the mask, like
+/// [`case_mask::encode`](crate::case_mask::encode).
+#[cfg(feature = "alloc")]
+impl HexWrite for CaseMask {
+    fn write_hex(&self, data: &[u8], out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_str(&crate::case_mask::encode(data, self))
+    }
+}
+
+impl<T: HexWrite + ?Sized> HexWrite for &T {
+    fn write_hex(&self, data: &[u8], out: &mut dyn fmt::Write) -> fmt::Result {
+        (**self).write_hex(data, out)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: HexWrite + ?Sized> HexWrite for Box<T> {
+    fn write_hex(&self, data: &[u8], out: &mut dyn fmt::Write) -> fmt::Result {
+        (**self).write_hex(data, out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    // Writes into a fixed buffer, failing once it is full.
+    struct Buf {
+        buf: [u8; 8],
+        len: usize,
+    }
+
+    impl fmt::Write for Buf {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            self.buf
+                .get_mut(self.len..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_case() {
+        let encoder: &dyn HexWrite = &Case::Upper;
+        let mut out = Buf {
+            buf: [0; 8],
+            len: 0,
+        };
+        encoder.write_hex(&[0xab, 0xcd], &mut out).unwrap();
+        (&encoder).write_hex(&[0x01], &mut out).unwrap();
+        assert_eq!(&out.buf[..out.len], b"ABCD01");
+        assert!(Case::Lower.write_hex(&[0; 2], &mut out).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_encode_to_string() {
+        let data: alloc::vec::Vec<u8> = (0..=255).collect();
+        assert_eq!(
+            Case::Lower.encode_to_string(&data).unwrap(),
+            crate::encode(&data)
+        );
+        assert_eq!(
+            Box::new(Case::Upper).encode_to_string(&data).unwrap(),
+            crate::encode_upper(&data)
+        );
+
+        let (bytes, mask) = crate::case_mask::decode("aBcD").unwrap();
+        let encoder: Box<dyn HexWrite> = Box::new(mask);
+        assert_eq!(encoder.encode_to_string(&bytes).unwrap(), "aBcD");
+
+        struct Failing;
+        impl HexWrite for Failing {
+            fn write_hex(&self, _: &[u8], _: &mut dyn fmt::Write) -> fmt::Result {
+                Err(fmt::Error)
+            }
+        }
+        assert_eq!(Failing.encode_to_string(&data), Err(fmt::Error));
+    }
+}
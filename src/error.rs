@@ -17,17 +17,191 @@ pub enum FromHexError {
     InvalidStringLength,
 }
 
+impl FromHexError {
+    /// Creates the error for the invalid character `c` at position `index`.
+    ///
+    /// This and the other constructors are meant for decoders built on top
+    /// of this crate, so they can report errors the same way it does.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hex::FromHexError;
+    ///
+    /// fn decode_digit(c: char, index: usize) -> Result<u8, FromHexError> {
+    ///     c.to_digit(16)
+    ///         .map(|digit| digit as u8)
+    ///         .ok_or(FromHexError::invalid_character(c, index))
+    /// }
+    ///
+    /// assert_eq!(decode_digit('f', 0), Ok(15));
+    /// assert_eq!(
+    ///     decode_digit('g', 3),
+    ///     Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn invalid_character(c: char, index: usize) -> Self {
+        FromHexError::InvalidHexCharacter { c, index }
+    }
+
+    /// Creates the error for input of `len` digits, which is odd.
+    ///
+    /// The length isn't stored in the error, as [`FromHexError::OddLength`]
+    /// has no fields, but debug builds check that it is odd.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hex::FromHexError;
+    ///
+    /// fn check_len(hex: &str) -> Result<(), FromHexError> {
+    ///     if hex.len() % 2 != 0 {
+    ///         return Err(FromHexError::odd_length(hex.len()));
+    ///     }
+    ///     Ok(())
+    /// }
+    ///
+    /// assert_eq!(check_len("abc"), Err(FromHexError::OddLength));
+    /// ```
+    #[must_use]
+    pub const fn odd_length(len: usize) -> Self {
+        debug_assert!(len % 2 != 0);
+        FromHexError::OddLength
+    }
+
+    /// Creates the error for input whose length doesn't match the length
+    /// of the output.
+    #[must_use]
+    pub const fn invalid_length() -> Self {
+        FromHexError::InvalidStringLength
+    }
+
+    // Moves the index of an invalid character by `offset`.
+    pub(crate) fn offset(self, offset: usize) -> Self {
+        match self {
+            FromHexError::InvalidHexCharacter { c, index } => FromHexError::InvalidHexCharacter {
+                c,
+                index: index + offset,
+            },
+            err => err,
+        }
+    }
+
+    /// Returns a suggestion for fixing the input, if there is an obvious one.
+    ///
+    /// This is included in the `Display` output, and is meant for users of
+    /// command line tools and the like, e.g. to point out a `0x` prefix.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let err = hex::decode_to_slice("0xff", &mut [0; 2]).unwrap_err();
+    /// assert_eq!(err.hint(), Some("strip the \"0x\" prefix first"));
+    /// # #[cfg(not(feature = "tiny-errors"))]
+    /// assert_eq!(err.to_string(), "Invalid character 'x' at position 1 (hint: strip the \"0x\" prefix first)");
+    ///
+    /// let err = hex::decode_to_slice("gg", &mut [0; 1]).unwrap_err();
+    /// assert_eq!(err.hint(), None);
+    /// ```
+    pub fn hint(&self) -> Option<&'static str> {
+        match *self {
+            FromHexError::InvalidHexCharacter {
+                c: 'x' | 'X',
+                index: 1,
+            } => Some("strip the \"0x\" prefix first"),
+            FromHexError::InvalidHexCharacter { c, .. }
+                if c.is_whitespace() || matches!(c, ':' | '-' | '_' | ',') =>
+            {
+                Some("use `hex::lenient` to skip whitespace and separators")
+            }
+            FromHexError::InvalidHexCharacter {
+                c: '\u{ff10}'..='\u{ff19}' | '\u{ff21}'..='\u{ff26}' | '\u{ff41}'..='\u{ff46}',
+                ..
+            } => Some("use `hex::lenient` with fullwidth forms enabled"),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 impl std::error::Error for FromHexError {}
 
+impl FromHexError {
+    // Describes the error without any details.
+    fn description(&self) -> &'static str {
+        match self {
+            FromHexError::InvalidHexCharacter { .. } => "Invalid character",
+            FromHexError::OddLength => "Odd number of digits",
+            FromHexError::InvalidStringLength => "Invalid string length",
+        }
+    }
+}
+
+/// With the `tiny-errors` feature, only a short static description is
+/// written, e.g. `Invalid character` without the character, its position or
+/// a hint.
 impl fmt::Display for FromHexError {
+    #[cfg(not(feature = "tiny-errors"))]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             FromHexError::InvalidHexCharacter { c, index } => {
-                write!(f, "Invalid character {:?} at position {}", c, index)
+                write!(f, "{} {:?} at position {}", self.description(), c, index)?
             }
-            FromHexError::OddLength => write!(f, "Odd number of digits"),
-            FromHexError::InvalidStringLength => write!(f, "Invalid string length"),
+            _ => f.write_str(self.description())?,
+        }
+        match self.hint() {
+            Some(hint) => write!(f, " (hint: {})", hint),
+            None => Ok(()),
+        }
+    }
+
+    #[cfg(feature = "tiny-errors")]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+/// The error type for decoding a hex string into a `CString`.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FromHexCStringError {
+    /// The input isn't valid hex.
+    Hex(FromHexError),
+
+    /// The decoded bytes contain a NUL byte before their end, at byte
+    /// `position`.
+    InteriorNul { position: usize },
+}
+
+#[cfg(feature = "std")]
+impl From<FromHexError> for FromHexCStringError {
+    fn from(err: FromHexError) -> Self {
+        FromHexCStringError::Hex(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FromHexCStringError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FromHexCStringError::Hex(err) => Some(err),
+            FromHexCStringError::InteriorNul { .. } => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for FromHexCStringError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FromHexCStringError::Hex(err) => fmt::Display::fmt(&err, f),
+            #[cfg(not(feature = "tiny-errors"))]
+            FromHexCStringError::InteriorNul { position } => {
+                write!(f, "Interior NUL byte at byte {}", position)
+            }
+            #[cfg(feature = "tiny-errors")]
+            FromHexCStringError::InteriorNul { .. } => f.write_str("Interior NUL byte"),
         }
     }
 }
@@ -43,11 +217,27 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     #[test]
-    #[cfg(feature = "alloc")]
+    fn test_constructors() {
+        const ERR: FromHexError = FromHexError::invalid_character('g', 3);
+        assert_eq!(ERR, FromHexError::InvalidHexCharacter { c: 'g', index: 3 });
+        assert_eq!(FromHexError::odd_length(3), FromHexError::OddLength);
+        assert_eq!(
+            FromHexError::invalid_length(),
+            FromHexError::InvalidStringLength
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "tiny-errors"))]
     fn test_display() {
         assert_eq!(
             FromHexError::InvalidHexCharacter { c: '\n', index: 5 }.to_string(),
-            "Invalid character '\\n' at position 5"
+            "Invalid character '\\n' at position 5 \
+             (hint: use `hex::lenient` to skip whitespace and separators)"
+        );
+        assert_eq!(
+            FromHexError::InvalidHexCharacter { c: 'g', index: 5 }.to_string(),
+            "Invalid character 'g' at position 5"
         );
 
         assert_eq!(FromHexError::OddLength.to_string(), "Odd number of digits");
@@ -56,4 +246,53 @@ mod tests {
             "Invalid string length"
         );
     }
+
+    #[test]
+    #[cfg(feature = "tiny-errors")]
+    fn test_display_tiny() {
+        assert_eq!(
+            FromHexError::InvalidHexCharacter { c: 'x', index: 1 }.to_string(),
+            "Invalid character"
+        );
+        assert_eq!(FromHexError::OddLength.to_string(), "Odd number of digits");
+        assert_eq!(
+            FromHexError::InvalidStringLength.to_string(),
+            "Invalid string length"
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", not(feature = "tiny-errors")))]
+    fn test_display_c_string() {
+        assert_eq!(
+            FromHexCStringError::InteriorNul { position: 3 }.to_string(),
+            "Interior NUL byte at byte 3"
+        );
+        assert_eq!(
+            FromHexCStringError::from(FromHexError::OddLength).to_string(),
+            "Odd number of digits"
+        );
+    }
+
+    #[test]
+    fn test_hint() {
+        let hint = |c, index| FromHexError::InvalidHexCharacter { c, index }.hint();
+        assert_eq!(hint('x', 1), Some("strip the \"0x\" prefix first"));
+        assert_eq!(hint('X', 1), Some("strip the \"0x\" prefix first"));
+        assert_eq!(hint('x', 3), None);
+        assert_eq!(
+            hint(':', 2),
+            Some("use `hex::lenient` to skip whitespace and separators")
+        );
+        assert_eq!(
+            hint('\u{3000}', 2),
+            Some("use `hex::lenient` to skip whitespace and separators")
+        );
+        assert_eq!(
+            hint('Ａ', 0),
+            Some("use `hex::lenient` with fullwidth forms enabled")
+        );
+        assert_eq!(hint('Ｇ', 0), None);
+        assert_eq!(FromHexError::OddLength.hint(), None);
+    }
 }
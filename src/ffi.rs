@@ -0,0 +1,241 @@
+//! A C interface to the encoding and decoding functions.
+//!
+//! The functions are exported unmangled, and declared in `include/hex.h` of
+//! the source distribution. All of them return [`HEX_OK`] on success and one
+//! of the negative `HEX_ERR_*` codes on failure. To link against them from C,
+//! build the crate as a static or dynamic library with the `ffi` feature
+//! enabled, e.g.:
+//!
+//! ```text
+//! cargo rustc --release --features ffi --crate-type staticlib
+//! ```
+use core::slice;
+
+use crate::FromHexError;
+
+/// The operation succeeded.
+pub const HEX_OK: i32 = 0;
+/// The input contains a character which is not a hex digit.
+pub const HEX_ERR_INVALID_CHAR: i32 = -1;
+/// The input has an odd length.
+pub const HEX_ERR_ODD_LENGTH: i32 = -2;
+/// The output buffer doesn't have the required length.
+pub const HEX_ERR_INVALID_LENGTH: i32 = -3;
+/// A required pointer was null.
+pub const HEX_ERR_NULL: i32 = -4;
+
+fn error_code(err: FromHexError) -> i32 {
+    match err {
+        FromHexError::InvalidHexCharacter { .. } => HEX_ERR_INVALID_CHAR,
+        FromHexError::OddLength => HEX_ERR_ODD_LENGTH,
+        FromHexError::InvalidStringLength => HEX_ERR_INVALID_LENGTH,
+    }
+}
+
+// Turns a pointer and a length into a slice, allowing null for empty slices.
+unsafe fn input<'a>(ptr: *const u8, len: usize) -> Option<&'a [u8]> {
+    if ptr.is_null() {
+        return if len == 0 { Some(&[]) } else { None };
+    }
+    Some(slice::from_raw_parts(ptr, len))
+}
+
+unsafe fn output<'a>(ptr: *mut u8, len: usize) -> Option<&'a mut [u8]> {
+    if ptr.is_null() {
+        return if len == 0 { Some(&mut []) } else { None };
+    }
+    Some(slice::from_raw_parts_mut(ptr, len))
+}
+
+/// Returns the length of the hex encoding of `len` bytes, or 0 on overflow.
+#[no_mangle]
+pub extern "C" fn hex_encoded_len(len: usize) -> usize {
+    crate::encoded_len(len).unwrap_or(0)
+}
+
+/// Returns the number of bytes encoded by `len` hex digits.
+///
+/// Odd lengths are rounded down; they are rejected by [`hex_decode`].
+#[no_mangle]
+pub extern "C" fn hex_decoded_len(len: usize) -> usize {
+    len / 2
+}
+
+unsafe fn encode(
+    src: *const u8,
+    src_len: usize,
+    dst: *mut u8,
+    dst_len: usize,
+    table: &'static [u8; 16],
+) -> i32 {
+    let (src, dst) = match (input(src, src_len), output(dst, dst_len)) {
+        (Some(src), Some(dst)) => (src, dst),
+        _ => return HEX_ERR_NULL,
+    };
+    match crate::encode_to_slice_inner(src, dst, table) {
+        Ok(()) => HEX_OK,
+        Err(err) => error_code(err),
+    }
+}
+
+/// Encodes `src_len` bytes at `src` as lowercase hex into `dst`.
+///
+/// `dst_len` has to be exactly `hex_encoded_len(src_len)`. No terminating
+/// null byte is written.
+///
+/// # Safety
+///
+/// `src` has to be valid for reads of `src_len` bytes, and `dst` for writes
+/// of `dst_len` bytes. Either may be null if the corresponding length is 0.
+/// The two buffers must not overlap.
+#[no_mangle]
+pub unsafe extern "C" fn hex_encode(
+    src: *const u8,
+    src_len: usize,
+    dst: *mut u8,
+    dst_len: usize,
+) -> i32 {
+    encode(src, src_len, dst, dst_len, crate::HEX_CHARS_LOWER)
+}
+
+/// Encodes `src_len` bytes at `src` as uppercase hex into `dst`.
+///
+/// This works like [`hex_encode`], apart from the case of the output.
+///
+/// # Safety
+///
+/// See [`hex_encode`].
+#[no_mangle]
+pub unsafe extern "C" fn hex_encode_upper(
+    src: *const u8,
+    src_len: usize,
+    dst: *mut u8,
+    dst_len: usize,
+) -> i32 {
+    encode(src, src_len, dst, dst_len, crate::HEX_CHARS_UPPER)
+}
+
+/// Decodes `src_len` hex digits at `src` into `dst`.
+///
+/// `dst_len` has to be exactly `hex_decoded_len(src_len)`. If `src` contains
+/// an invalid character and `error_index` isn't null, the index of the
+/// character is stored in `*error_index`.
+///
+/// # Safety
+///
+/// `src` has to be valid for reads of `src_len` bytes, and `dst` for writes
+/// of `dst_len` bytes. Either may be null if the corresponding length is 0.
+/// The two buffers must not overlap.
+/// `error_index` has to be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn hex_decode(
+    src: *const u8,
+    src_len: usize,
+    dst: *mut u8,
+    dst_len: usize,
+    error_index: *mut usize,
+) -> i32 {
+    let (src, dst) = match (input(src, src_len), output(dst, dst_len)) {
+        (Some(src), Some(dst)) => (src, dst),
+        _ => return HEX_ERR_NULL,
+    };
+    match crate::decode_to_slice(src, dst) {
+        Ok(()) => HEX_OK,
+        Err(err) => {
+            if let (FromHexError::InvalidHexCharacter { index, .. }, false) =
+                (err, error_index.is_null())
+            {
+                *error_index = index;
+            }
+            error_code(err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ptr;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_encode() {
+        let src = b"kiwi";
+        let mut dst = [0; 8];
+        unsafe {
+            assert_eq!(hex_encode(src.as_ptr(), 4, dst.as_mut_ptr(), 8), HEX_OK);
+            assert_eq!(&dst, b"6b697769");
+            assert_eq!(
+                hex_encode_upper(src.as_ptr(), 1, dst.as_mut_ptr(), 2),
+                HEX_OK
+            );
+            assert_eq!(&dst[..2], b"6B");
+            assert_eq!(
+                hex_encode(src.as_ptr(), 4, dst.as_mut_ptr(), 7),
+                HEX_ERR_INVALID_LENGTH
+            );
+            assert_eq!(hex_encode(ptr::null(), 0, ptr::null_mut(), 0), HEX_OK);
+            assert_eq!(
+                hex_encode(ptr::null(), 1, dst.as_mut_ptr(), 2),
+                HEX_ERR_NULL
+            );
+        }
+        assert_eq!(hex_encoded_len(4), 8);
+        assert_eq!(hex_encoded_len(usize::MAX), 0);
+    }
+
+    #[test]
+    fn test_decode() {
+        let mut dst = [0; 2];
+        let mut index = usize::MAX;
+        unsafe {
+            assert_eq!(
+                hex_decode(b"abCD".as_ptr(), 4, dst.as_mut_ptr(), 2, &mut index),
+                HEX_OK
+            );
+            assert_eq!(dst, [0xab, 0xcd]);
+            assert_eq!(index, usize::MAX);
+            assert_eq!(
+                hex_decode(b"abcx".as_ptr(), 4, dst.as_mut_ptr(), 2, &mut index),
+                HEX_ERR_INVALID_CHAR
+            );
+            assert_eq!(index, 3);
+            assert_eq!(
+                hex_decode(b"abc".as_ptr(), 3, dst.as_mut_ptr(), 2, ptr::null_mut()),
+                HEX_ERR_ODD_LENGTH
+            );
+            assert_eq!(
+                hex_decode(b"ab".as_ptr(), 2, ptr::null_mut(), 1, ptr::null_mut()),
+                HEX_ERR_NULL
+            );
+        }
+        assert_eq!(hex_decoded_len(5), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_header() {
+        use alloc::format;
+
+        let header = include_str!("../include/hex.h");
+        for (name, value) in [
+            ("HEX_OK", HEX_OK),
+            ("HEX_ERR_INVALID_CHAR", HEX_ERR_INVALID_CHAR),
+            ("HEX_ERR_ODD_LENGTH", HEX_ERR_ODD_LENGTH),
+            ("HEX_ERR_INVALID_LENGTH", HEX_ERR_INVALID_LENGTH),
+            ("HEX_ERR_NULL", HEX_ERR_NULL),
+        ] {
+            let define = format!("#define {} {}", name, value);
+            assert!(header.contains(&define), "missing `{}`", define);
+        }
+        for function in [
+            "hex_encoded_len(",
+            "hex_decoded_len(",
+            "hex_encode(",
+            "hex_encode_upper(",
+            "hex_decode(",
+        ] {
+            assert!(header.contains(function), "missing `{}`", function);
+        }
+    }
+}
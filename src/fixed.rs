@@ -0,0 +1,210 @@
+use core::{fmt, ops::Deref, ops::DerefMut, str::FromStr};
+
+use crate::{Case, FromHex, FromHexError};
+
+/// A fixed-size byte array which is displayed and parsed as hex.
+///
+/// This is meant for the common case of typed hashes, keys and identifiers
+/// (e.g. `FixedHex<20>` for an address or `FixedHex<32>` for a SHA-256
+/// digest). It displays as lowercase hex without a prefix, and can be parsed
+/// from hex with or without a leading `0x`.
+///
+/// # Example
+///
+/// ```
+/// use hex::FixedHex;
+///
+/// type H32 = FixedHex<4>;
+///
+/// let hash: H32 = "0x6b697769".parse()?;
+/// assert_eq!(hash, FixedHex(*b"kiwi"));
+/// assert_eq!(hash.to_string(), "6b697769");
+///
+/// assert!("6b6977".parse::<H32>().is_err());
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Binary)
+)]
+pub struct FixedHex<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> FixedHex<N> {
+    /// Decodes `hex`, which may start with a `0x` (or `0X`) prefix.
+    ///
+    /// Use [`FromHex::from_hex`] if the prefix should be rejected instead.
+    /// Indices in errors are relative to the start of `hex`, including the
+    /// prefix.
+    pub fn from_hex_prefixed<T: AsRef<[u8]>>(hex: T) -> Result<Self, FromHexError> {
+        let hex = hex.as_ref();
+        match hex {
+            [b'0', b'x', rest @ ..] | [b'0', b'X', rest @ ..] => {
+                FixedHex::from_hex(rest).map_err(|err| match err {
+                    FromHexError::InvalidHexCharacter { c, index } => {
+                        FromHexError::InvalidHexCharacter {
+                            c,
+                            index: index + 2,
+                        }
+                    }
+                    err => err,
+                })
+            }
+            _ => FixedHex::from_hex(hex),
+        }
+    }
+
+    /// Returns the underlying array.
+    #[must_use]
+    pub fn into_inner(self) -> [u8; N] {
+        self.0
+    }
+}
+
+impl<const N: usize> FromHex for FixedHex<N> {
+    type Error = FromHexError;
+
+    fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, Self::Error> {
+        <[u8; N]>::from_hex(hex).map(FixedHex)
+    }
+}
+
+impl<const N: usize> FromStr for FixedHex<N> {
+    type Err = FromHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FixedHex::from_hex_prefixed(s)
+    }
+}
+
+impl<const N: usize> Default for FixedHex<N> {
+    fn default() -> Self {
+        FixedHex([0; N])
+    }
+}
+
+impl<const N: usize> fmt::Display for FixedHex<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        crate::fmt_hex(&self.0, Case::Lower, f)
+    }
+}
+
+/// Formats the bytes as hex, like an `N`-byte big-endian integer.
+///
+/// Unlike integers, leading zeros are kept. The `#` flag adds a `0x` prefix.
+///
+/// # Example
+///
+/// ```
+/// let id = hex::FixedHex([0x00, 0xab]);
+/// assert_eq!(format!("{:x}", id), "00ab");
+/// assert_eq!(format!("{:#X}", id), "0x00AB");
+/// assert_eq!(format!("{:#08x}", id), "0x0000ab");
+/// ```
+impl<const N: usize> fmt::LowerHex for FixedHex<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        crate::pad_hex(f, 2 * N, |f| crate::fmt_hex(&self.0, Case::Lower, f))
+    }
+}
+
+/// Like the `LowerHex` implementation, but using uppercase letters.
+impl<const N: usize> fmt::UpperHex for FixedHex<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        crate::pad_hex(f, 2 * N, |f| crate::fmt_hex(&self.0, Case::Upper, f))
+    }
+}
+
+impl<const N: usize> fmt::Debug for FixedHex<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FixedHex({})", self)
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for FixedHex<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        FixedHex(bytes)
+    }
+}
+
+impl<const N: usize> From<FixedHex<N>> for [u8; N] {
+    fn from(hex: FixedHex<N>) -> Self {
+        hex.0
+    }
+}
+
+impl<const N: usize> Deref for FixedHex<N> {
+    type Target = [u8; N];
+
+    fn deref(&self) -> &[u8; N] {
+        &self.0
+    }
+}
+
+impl<const N: usize> DerefMut for FixedHex<N> {
+    fn deref_mut(&mut self) -> &mut [u8; N] {
+        &mut self.0
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for FixedHex<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> AsMut<[u8]> for FixedHex<N> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use alloc::{format, string::ToString};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_from_hex_prefixed() {
+        let expected = FixedHex([0x66, 0x6f, 0x6f]);
+        assert_eq!(FixedHex::from_hex_prefixed("666f6f"), Ok(expected));
+        assert_eq!(FixedHex::from_hex_prefixed("0x666F6F"), Ok(expected));
+        assert_eq!(FixedHex::from_hex_prefixed(b"0X666f6f"), Ok(expected));
+        assert_eq!("0x666f6f".parse(), Ok(expected));
+
+        assert_eq!(
+            FixedHex::<3>::from_hex("0x666f"),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 1 })
+        );
+        assert_eq!(
+            FixedHex::<3>::from_hex_prefixed("0x666g6f"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 5 })
+        );
+        assert_eq!(
+            FixedHex::<3>::from_hex_prefixed("0x666f"),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_display() {
+        let hex = FixedHex([0x66, 0x6f, 0x6f]);
+        assert_eq!(hex.to_string(), "666f6f");
+        assert_eq!(format!("{:?}", hex), "FixedHex(666f6f)");
+
+        assert_eq!(format!("{:x}", hex), "666f6f");
+        assert_eq!(format!("{:#X}", hex), "0x666F6F");
+        assert_eq!(format!("{:010x}", hex), "0000666f6f");
+        assert_eq!(format!("{:#010x}", hex), "0x00666f6f");
+        assert_eq!(format!("{:>9X}|{:<#9x}|", hex, hex), "   666F6F|0x666f6f |");
+        assert_eq!(format!("{:*^10x}", hex), "**666f6f**");
+        assert_eq!(format!("{:#4x}", hex), "0x666f6f");
+
+        let large = FixedHex([0xab; 100]);
+        assert_eq!(large.to_string(), "ab".repeat(100));
+        assert_eq!(FixedHex::<0>::default().to_string(), "");
+    }
+}
@@ -0,0 +1,120 @@
+//! Hex encoding and decoding of files.
+//!
+//! These functions stream through the adapters in [`crate::io`], so reading
+//! and writing works with bounded buffers regardless of the file size.
+//!
+//! # Example
+//!
+//! ```no_run
+//! hex::fs::write_encoded("key.hex", b"kiwi")?;
+//! assert_eq!(hex::fs::decode_file("key.hex")?, b"kiwi");
+//! # Ok::<(), std::io::Error>(())
+//! ```
+use std::{
+    convert::TryFrom,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use crate::{
+    io::{DecodeReader, EncodeWriter},
+    Case,
+};
+
+/// Reads the file at `path` and returns its contents hex-encoded, using
+/// lowercase characters.
+///
+/// # Example
+///
+/// ```no_run
+/// println!("{}", hex::fs::encode_file("/etc/hostname")?);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn encode_file<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    // The size is only a hint, which is ignored if it is out of range.
+    let capacity = file
+        .metadata()
+        .ok()
+        .and_then(|metadata| usize::try_from(metadata.len()).ok())
+        .and_then(crate::encoded_len)
+        .filter(|&capacity| capacity <= isize::MAX as usize)
+        .unwrap_or(0);
+    let mut writer = EncodeWriter::new(Vec::with_capacity(capacity), Case::Lower);
+    io::copy(&mut file, &mut writer)?;
+    // Saftey: `EncodeWriter` only writes hex digits into the vector
+    Ok(unsafe { String::from_utf8_unchecked(writer.into_inner()) })
+}
+
+/// Reads the file at `path` and decodes its contents as hex.
+///
+/// ASCII whitespace is skipped, see [`DecodeReader`]; invalid contents fail
+/// with [`io::ErrorKind::InvalidData`].
+///
+/// # Example
+///
+/// ```no_run
+/// let key = hex::fs::decode_file("key.hex")?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn decode_file<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    DecodeReader::new(BufReader::new(File::open(path)?)).read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Writes `data` hex-encoded to the file at `path`, using lowercase
+/// characters.
+///
+/// The file is created if it doesn't exist, and truncated otherwise.
+///
+/// # Example
+///
+/// ```no_run
+/// hex::fs::write_encoded("key.hex", [0xde, 0xad, 0xbe, 0xef])?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn write_encoded<P: AsRef<Path>, T: AsRef<[u8]>>(path: P, data: T) -> io::Result<()> {
+    let mut writer = EncodeWriter::new(BufWriter::new(File::create(path)?), Case::Lower);
+    writer.write_all(data.as_ref())?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::{env, fs, path::PathBuf};
+
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("hex-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let data: Vec<u8> = (0..10_000).map(|i| i as u8).collect();
+        let path = temp_path("roundtrip");
+        write_encoded(&path, &data).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), crate::encode(&data).into_bytes());
+        assert_eq!(decode_file(&path).unwrap(), data);
+
+        fs::write(&path, &data).unwrap();
+        assert_eq!(encode_file(&path).unwrap(), crate::encode(&data));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_errors() {
+        let path = temp_path("errors");
+        fs::write(&path, "6b69776\n").unwrap();
+        let err = decode_file(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            encode_file(&path).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+}
@@ -0,0 +1,54 @@
+//! Implementations of [`async_graphql::ScalarType`] for the wrapper types.
+//!
+//! Both types are represented as strings. Input is validated when it is
+//! parsed, so resolvers only ever see valid hex.
+use async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, TypeName, Value};
+use std::borrow::Cow;
+
+use crate::{FixedHex, FromHex, HexString};
+
+/// A hex encoded byte string, using lowercase digits.
+#[Scalar(name = "HexString")]
+impl ScalarType for HexString {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match value {
+            Value::String(s) => Ok(HexString::new(s)?),
+            value => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        matches!(value, Value::String(s) if crate::validate(s.as_bytes()).is_ok())
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.as_str().into())
+    }
+}
+
+// Every size gets its own scalar, e.g. `FixedHex32`, as a schema can't
+// contain two different types of the same name.
+impl<const N: usize> TypeName for FixedHex<N> {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Owned(format!("FixedHex{}", N))
+    }
+}
+
+/// A hex encoded byte string of fixed length, using lowercase digits.
+#[Scalar(name_type)]
+impl<const N: usize> ScalarType for FixedHex<N> {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match value {
+            Value::String(s) => Ok(FixedHex::from_hex(s)?),
+            value => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        matches!(value, Value::String(s) if s.len() == 2 * N && crate::validate(s.as_bytes()).is_ok())
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
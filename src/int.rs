@@ -0,0 +1,894 @@
+//! Hex encoding and decoding of integers.
+//!
+//! [`FromHex`] is implemented for all primitive integers, reading the hex as
+//! a big-endian number (the way it is usually written). Input doesn't need
+//! to be zero-padded to the full width of the type, so both `"00ff"` and
+//! `"ff"` are valid `u16`s, and odd numbers of digits are fine.
+//!
+//! Signed integers use two different conventions:
+//!
+//! - [`FromHex`], [`from_hex_be`], [`from_hex_le`] and [`encode_int`] treat
+//!   the hex as the two's complement bit pattern of the number, as shown by
+//!   debuggers and hexdumps. `"ff"` is `-1_i8`, but `255_i16`.
+//! - [`encode_minimal`] and [`decode_minimal`] use an explicit sign, like
+//!   `from_str_radix(_, 16)`: `-1_i8` is `"-1"`, and `"ff"` is out of range
+//!   for an `i8`.
+//!
+//! Use [`from_hex_be`] and [`from_hex_le`] to be explicit about the byte
+//! order. Register dumps and firmware images, which are sequences of
+//! fixed-width words, can be decoded with [`words_be`] and [`words_le`], or
+//! [`decode_words_be`] and [`decode_words_le`].
+//!
+//! For encoding, [`encode_u16`] and friends (or the generic [`encode_int`])
+//! produce zero-padded hex of the full width of the type, as required by
+//! many protocol fields, without allocating.
+//!
+//! The "minimal" forms, [`encode_minimal`] and [`decode_minimal`], omit
+//! leading zeros like `{:x}` and `from_str_radix(_, 16)` do. This is the
+//! format of e.g. JSON-RPC quantities (`0x1a`), see also [`encode_quantity`],
+//! [`encode_minimal_bytes`] and [`decode_minimal_bytes`].
+//!
+//! # Example
+//!
+//! ```
+//! use hex::FromHex;
+//!
+//! assert_eq!(u32::from_hex("deadbeef"), Ok(0xdeadbeef));
+//! assert_eq!(u32::from_hex("fff"), Ok(0xfff));
+//! assert_eq!(hex::int::from_hex_le::<u32>("efbeadde"), Ok(0xdeadbeef));
+//!
+//! assert!(u8::from_hex("100").is_err());
+//!
+//! assert_eq!(hex::int::encode_u32(0xfff, hex::Case::Lower), "00000fff");
+//! ```
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
+use core::{fmt, hash, iter::FusedIterator, marker::PhantomData, ops::Deref, slice::ChunksExact};
+
+#[cfg(feature = "alloc")]
+use crate::BytesToHexChars;
+use crate::{Case, FromHex, FromHexError, DECODE_TABLE};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Integer types which can be encoded as and decoded from hex.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait HexInt: Copy + private::Sealed {
+    /// The buffer the hex representation of this type is written to, e.g.
+    /// `HexBuf<8>` for `u32`.
+    type Buf: Copy + Deref<Target = str> + AsRef<str> + fmt::Debug + fmt::Display;
+
+    #[doc(hidden)]
+    const BYTES: usize;
+
+    #[doc(hidden)]
+    fn from_u128(value: u128) -> Self;
+
+    #[doc(hidden)]
+    fn to_u128(self) -> u128;
+
+    #[doc(hidden)]
+    fn write_fixed(self, case: Case) -> Self::Buf;
+
+    #[doc(hidden)]
+    fn write_minimal(self, case: Case) -> Self::Buf;
+
+    #[doc(hidden)]
+    fn parse_minimal(hex: &[u8]) -> Result<Self, FromHexError>;
+}
+
+macro_rules! impl_unsigned {
+    ($($ty:ty),*) => {
+        $(
+            impl private::Sealed for $ty {}
+
+            impl HexInt for $ty {
+                type Buf = HexBuf<{ 2 * core::mem::size_of::<$ty>() }>;
+
+                const BYTES: usize = core::mem::size_of::<$ty>();
+
+                #[inline]
+                fn from_u128(value: u128) -> Self {
+                    value as $ty
+                }
+
+                #[inline]
+                fn to_u128(self) -> u128 {
+                    self as u128
+                }
+
+                fn write_fixed(self, case: Case) -> Self::Buf {
+                    HexBuf::write(self.to_u128(), 2 * Self::BYTES, case)
+                }
+
+                fn write_minimal(self, case: Case) -> Self::Buf {
+                    let value = self.to_u128();
+                    HexBuf::write(value, minimal_digits(value), case)
+                }
+
+                fn parse_minimal(hex: &[u8]) -> Result<Self, FromHexError> {
+                    let (hex, offset) = strip_prefix(hex);
+                    from_hex_be(hex).map_err(|err| err.offset(offset))
+                }
+            }
+
+            impl FromHex for $ty {
+                type Error = FromHexError;
+
+                fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, Self::Error> {
+                    from_hex_be(hex)
+                }
+            }
+        )*
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128);
+
+macro_rules! impl_signed {
+    ($($ty:ty => $unsigned:ty),*) => {
+        $(
+            impl private::Sealed for $ty {}
+
+            impl HexInt for $ty {
+                // One more byte for the sign of the minimal form.
+                type Buf = HexBuf<{ 2 * core::mem::size_of::<$ty>() + 1 }>;
+
+                const BYTES: usize = core::mem::size_of::<$ty>();
+
+                #[inline]
+                fn from_u128(value: u128) -> Self {
+                    value as $unsigned as $ty
+                }
+
+                #[inline]
+                fn to_u128(self) -> u128 {
+                    self as $unsigned as u128
+                }
+
+                fn write_fixed(self, case: Case) -> Self::Buf {
+                    HexBuf::write(self.to_u128(), 2 * Self::BYTES, case)
+                }
+
+                fn write_minimal(self, case: Case) -> Self::Buf {
+                    let magnitude = self.unsigned_abs() as u128;
+                    let mut buf = HexBuf::write(magnitude, minimal_digits(magnitude), case);
+                    if self < 0 {
+                        buf.start -= 1;
+                        buf.bytes[buf.start] = b'-';
+                    }
+                    buf
+                }
+
+                fn parse_minimal(hex: &[u8]) -> Result<Self, FromHexError> {
+                    parse_signed(hex, Self::BYTES).map(Self::from_u128)
+                }
+            }
+
+            impl FromHex for $ty {
+                type Error = FromHexError;
+
+                fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, Self::Error> {
+                    from_hex_be(hex)
+                }
+            }
+        )*
+    };
+}
+
+impl_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128);
+
+// Returns the number of digits needed to represent `value`, at least 1.
+fn minimal_digits(value: u128) -> usize {
+    ((128 - value.leading_zeros() as usize + 3) / 4).max(1)
+}
+
+// Splits off an optional `0x` (or `0X`) prefix, returning its length.
+fn strip_prefix(hex: &[u8]) -> (&[u8], usize) {
+    match hex {
+        [b'0', b'x', rest @ ..] | [b'0', b'X', rest @ ..] => (rest, 2),
+        _ => (hex, 0),
+    }
+}
+
+// Parses up to `bytes * 2` digits as a big-endian number.
+fn parse_be(hex: &[u8], bytes: usize) -> Result<u128, FromHexError> {
+    if hex.is_empty() || hex.len() > bytes * 2 {
+        return Err(FromHexError::InvalidStringLength);
+    }
+    let mut value = 0_u128;
+    for (index, &c) in hex.iter().enumerate() {
+        let digit = DECODE_TABLE[c as usize];
+        if digit == u8::MAX {
+            return Err(FromHexError::InvalidHexCharacter {
+                c: c as char,
+                index,
+            });
+        }
+        value = (value << 4) | u128::from(digit);
+    }
+    Ok(value)
+}
+
+// Parses a number with an optional sign and `0x` prefix, returning its two's
+// complement representation. Values which don't fit into a signed integer of
+// `bytes` bytes are rejected.
+fn parse_signed(hex: &[u8], bytes: usize) -> Result<u128, FromHexError> {
+    let (negative, hex, sign) = match hex {
+        [b'-', rest @ ..] => (true, rest, 1),
+        _ => (false, hex, 0),
+    };
+    let (hex, prefix) = strip_prefix(hex);
+    let magnitude = parse_be(hex, bytes).map_err(|err| err.offset(sign + prefix))?;
+
+    let limit = 1_u128 << (8 * bytes - 1);
+    if negative && magnitude <= limit {
+        Ok(magnitude.wrapping_neg())
+    } else if !negative && magnitude < limit {
+        Ok(magnitude)
+    } else {
+        Err(FromHexError::InvalidStringLength)
+    }
+}
+
+// Parses up to `bytes` pairs of digits as a little-endian number.
+fn parse_le(hex: &[u8], bytes: usize) -> Result<u128, FromHexError> {
+    if hex.len() % 2 != 0 {
+        return Err(FromHexError::OddLength);
+    }
+    if hex.is_empty() || hex.len() > bytes * 2 {
+        return Err(FromHexError::InvalidStringLength);
+    }
+    let mut value = 0_u128;
+    for (i, pair) in hex.chunks_exact(2).enumerate() {
+        value |= u128::from(crate::val(pair, 2 * i)?) << (8 * i);
+    }
+    Ok(value)
+}
+
+/// Decodes a big-endian integer, i.e. the most significant digit comes first.
+///
+/// The input may be shorter than the full width of `T`, in which case it is
+/// treated as if padded with leading zeros. Odd numbers of digits are
+/// accepted. Empty input and input with more digits than `T` can hold are
+/// rejected with [`FromHexError::InvalidStringLength`].
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::int::from_hex_be::<u16>("1234"), Ok(0x1234));
+/// assert_eq!(hex::int::from_hex_be::<u16>("234"), Ok(0x234));
+/// ```
+pub fn from_hex_be<T: HexInt>(hex: impl AsRef<[u8]>) -> Result<T, FromHexError> {
+    parse_be(hex.as_ref(), T::BYTES).map(T::from_u128)
+}
+
+/// Decodes a little-endian integer, i.e. the least significant byte comes
+/// first.
+///
+/// The input has to consist of whole bytes, but may contain fewer bytes than
+/// `T`, in which case the missing most significant bytes are zero.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::int::from_hex_le::<u16>("3412"), Ok(0x1234));
+/// assert_eq!(hex::int::from_hex_le::<u32>("3412"), Ok(0x1234));
+/// assert!(hex::int::from_hex_le::<u16>("412").is_err());
+/// ```
+pub fn from_hex_le<T: HexInt>(hex: impl AsRef<[u8]>) -> Result<T, FromHexError> {
+    parse_le(hex.as_ref(), T::BYTES).map(T::from_u128)
+}
+
+/// Validates a hex string of big-endian words and returns an iterator which
+/// decodes them lazily.
+///
+/// Every word is made of exactly `2 * size_of::<T>()` digits, so e.g. the
+/// `u32` words of a register dump are read eight digits at a time. The whole
+/// string is validated up front: input which contains invalid characters or
+/// doesn't consist of whole words is rejected, the latter with
+/// [`FromHexError::OddLength`] or [`FromHexError::InvalidStringLength`].
+///
+/// # Example
+///
+/// ```
+/// let mut words = hex::int::words_be::<u16, _>("0102abcd")?;
+/// assert_eq!(words.len(), 2);
+/// assert_eq!(words.next(), Some(0x0102));
+/// assert_eq!(words.next(), Some(0xabcd));
+///
+/// assert!(hex::int::words_be::<u32, _>("0102abcd00").is_err());
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+pub fn words_be<T: HexInt, U: AsRef<[u8]> + ?Sized>(hex: &U) -> Result<Words<'_, T>, FromHexError> {
+    Words::new(hex.as_ref(), parse_be)
+}
+
+/// Validates a hex string of little-endian words and returns an iterator
+/// which decodes them lazily.
+///
+/// This is the little-endian counterpart of [`words_be`], for the words of
+/// e.g. a firmware image built for a little-endian target.
+///
+/// # Example
+///
+/// ```
+/// let words = hex::int::words_le::<u32, _>("78563412efbeadde")?;
+/// assert!(words.eq([0x12345678, 0xdeadbeef]));
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+pub fn words_le<T: HexInt, U: AsRef<[u8]> + ?Sized>(hex: &U) -> Result<Words<'_, T>, FromHexError> {
+    Words::new(hex.as_ref(), parse_le)
+}
+
+/// Decodes a hex string of big-endian words.
+///
+/// See [`words_be`] for the accepted input.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::int::decode_words_be::<u32>("deadbeef00000001"), Ok(vec![0xdeadbeef, 1]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_words_be<T: HexInt>(hex: impl AsRef<[u8]>) -> Result<Vec<T>, FromHexError> {
+    words_be(hex.as_ref()).map(Iterator::collect)
+}
+
+/// Decodes a hex string of little-endian words.
+///
+/// See [`words_be`] for the accepted input.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::int::decode_words_le::<u16>("34120100"), Ok(vec![0x1234, 1]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_words_le<T: HexInt>(hex: impl AsRef<[u8]>) -> Result<Vec<T>, FromHexError> {
+    words_le(hex.as_ref()).map(Iterator::collect)
+}
+
+/// An iterator decoding the words of a validated hex string.
+///
+/// This struct is created by [`words_be`] and [`words_le`].
+#[derive(Clone)]
+pub struct Words<'a, T> {
+    chunks: ChunksExact<'a, u8>,
+    parse: fn(&[u8], usize) -> Result<u128, FromHexError>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: HexInt> Words<'a, T> {
+    fn new(
+        hex: &'a [u8],
+        parse: fn(&[u8], usize) -> Result<u128, FromHexError>,
+    ) -> Result<Self, FromHexError> {
+        crate::validate(hex)?;
+        if hex.len() % (2 * T::BYTES) != 0 {
+            return Err(FromHexError::InvalidStringLength);
+        }
+        Ok(Words {
+            chunks: hex.chunks_exact(2 * T::BYTES),
+            parse,
+            marker: PhantomData,
+        })
+    }
+
+    fn word(&self, chunk: &[u8]) -> T {
+        // The input was validated, and every chunk is exactly one word long
+        T::from_u128((self.parse)(chunk, T::BYTES).unwrap())
+    }
+}
+
+impl<T> fmt::Debug for Words<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Words")
+            .field("remaining", &self.chunks.len())
+            .finish()
+    }
+}
+
+impl<T: HexInt> Iterator for Words<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.chunks.next().map(|chunk| self.word(chunk))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl<T: HexInt> DoubleEndedIterator for Words<'_, T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.chunks.next_back().map(|chunk| self.word(chunk))
+    }
+}
+
+impl<T: HexInt> ExactSizeIterator for Words<'_, T> {}
+
+impl<T: HexInt> FusedIterator for Words<'_, T> {}
+
+/// A stack-allocated buffer holding the hex representation of an integer.
+///
+/// This dereferences to `str`, and is returned by the encoding functions of
+/// this module.
+#[derive(Clone, Copy)]
+pub struct HexBuf<const N: usize> {
+    bytes: [u8; N],
+    // The digits are stored right-aligned, i.e. in `bytes[start..]`.
+    start: usize,
+}
+
+impl<const N: usize> HexBuf<N> {
+    // Writes the `digits` least significant digits of `value`.
+    fn write(mut value: u128, digits: usize, case: Case) -> Self {
+        let table = case.table();
+        let mut bytes = [0; N];
+        for byte in bytes[N - digits..].iter_mut().rev() {
+            *byte = table[(value & 0xf) as usize];
+            value >>= 4;
+        }
+        HexBuf {
+            bytes,
+            start: N - digits,
+        }
+    }
+
+    /// Returns the hex as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        // Saftey: Only hex digits are ever written into the buffer
+        unsafe { core::str::from_utf8_unchecked(&self.bytes[self.start..]) }
+    }
+}
+
+impl<const N: usize> Deref for HexBuf<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<str> for HexBuf<N> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for HexBuf<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_str().as_bytes()
+    }
+}
+
+impl<const N: usize> fmt::Display for HexBuf<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl<const N: usize> fmt::Debug for HexBuf<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> PartialEq for HexBuf<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const N: usize> Eq for HexBuf<N> {}
+
+impl<const N: usize> PartialEq<str> for HexBuf<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for HexBuf<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize> hash::Hash for HexBuf<N> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+/// Encodes `value` as zero-padded hex of the full width of `T`.
+///
+/// Negative numbers are written in two's complement.
+///
+/// # Example
+///
+/// ```
+/// use hex::{int::encode_int, Case};
+///
+/// assert_eq!(encode_int(0xab_u8, Case::Upper), "AB");
+/// assert_eq!(encode_int(1_u64, Case::Lower), "0000000000000001");
+/// assert_eq!(encode_int(-2_i16, Case::Lower), "fffe");
+/// ```
+#[must_use]
+pub fn encode_int<T: HexInt>(value: T, case: Case) -> T::Buf {
+    value.write_fixed(case)
+}
+
+macro_rules! encode_fns {
+    ($($name:ident: $ty:ty, $example:literal;)*) => {
+        $(
+            #[doc = concat!("Encodes a `", stringify!($ty), "` as zero-padded hex.")]
+            ///
+            /// # Example
+            ///
+            /// ```
+            #[doc = concat!("assert_eq!(hex::int::", stringify!($name), "(0xab, hex::Case::Lower), \"", $example, "\");")]
+            /// ```
+            #[must_use]
+            pub fn $name(value: $ty, case: Case) -> <$ty as HexInt>::Buf {
+                value.write_fixed(case)
+            }
+        )*
+    };
+}
+
+encode_fns! {
+    encode_u8: u8, "ab";
+    encode_u16: u16, "00ab";
+    encode_u32: u32, "000000ab";
+    encode_u64: u64, "00000000000000ab";
+    encode_u128: u128, "000000000000000000000000000000ab";
+}
+
+/// Encodes `value` as hex without leading zeros.
+///
+/// For unsigned integers this is the same as formatting with `{:x}` (or
+/// `{:X}`), but without allocating. Negative numbers are prefixed with a `-`
+/// instead of being written in two's complement. Zero is encoded as `"0"`.
+///
+/// # Example
+///
+/// ```
+/// use hex::{int::encode_minimal, Case};
+///
+/// assert_eq!(encode_minimal(0x1a_u64, Case::Lower), "1a");
+/// assert_eq!(encode_minimal(0xfff_u16, Case::Upper), "FFF");
+/// assert_eq!(encode_minimal(0_u32, Case::Lower), "0");
+/// assert_eq!(encode_minimal(-0x1a_i32, Case::Lower), "-1a");
+/// ```
+#[must_use]
+pub fn encode_minimal<T: HexInt>(value: T, case: Case) -> T::Buf {
+    value.write_minimal(case)
+}
+
+/// Encodes `value` as a JSON-RPC style quantity, i.e. as lowercase hex
+/// without leading zeros and with a `0x` prefix.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::int::encode_quantity(26_u64), "0x1a");
+/// assert_eq!(hex::int::encode_quantity(0_u64), "0x0");
+/// ```
+#[must_use]
+#[cfg(feature = "alloc")]
+pub fn encode_quantity<T: HexInt>(value: T) -> String {
+    let digits = value.write_minimal(Case::Lower);
+    let mut s = String::with_capacity(digits.len() + 2);
+    s.push_str("0x");
+    s.push_str(&digits);
+    s
+}
+
+/// Decodes an integer written as hex, with or without leading zeros and with
+/// an optional `0x` (or `0X`) prefix.
+///
+/// This accepts everything produced by [`encode_minimal`] and
+/// [`encode_quantity`], and by [`encode_int`] for unsigned integers. Signed
+/// integers may start with a `-`, which comes before the prefix. Indices in
+/// errors are relative to the start of `hex`, including the sign and prefix.
+///
+/// Values which don't fit into `T` are rejected with
+/// [`FromHexError::InvalidStringLength`].
+///
+/// # Example
+///
+/// ```
+/// use hex::int::decode_minimal;
+///
+/// assert_eq!(decode_minimal::<u64>("0x1a"), Ok(26));
+/// assert_eq!(decode_minimal::<u64>("1A"), Ok(26));
+/// assert_eq!(decode_minimal::<u64>("0x001a"), Ok(26));
+/// assert_eq!(decode_minimal::<i64>("-0x1a"), Ok(-26));
+/// assert!(decode_minimal::<u64>("0x").is_err());
+/// assert!(decode_minimal::<i8>("ff").is_err());
+/// ```
+pub fn decode_minimal<T: HexInt>(hex: impl AsRef<[u8]>) -> Result<T, FromHexError> {
+    T::parse_minimal(hex.as_ref())
+}
+
+/// Encodes big-endian bytes as hex without leading zeros.
+///
+/// This is the byte-oriented counterpart of [`encode_minimal`], for numbers
+/// which don't fit into a primitive integer. Empty input and input consisting
+/// of zeros is encoded as `"0"`.
+///
+/// # Example
+///
+/// ```
+/// use hex::{int::encode_minimal_bytes, Case};
+///
+/// assert_eq!(encode_minimal_bytes([0x00, 0x0a, 0xbc], Case::Lower), "abc");
+/// assert_eq!(encode_minimal_bytes([0x00, 0x00], Case::Lower), "0");
+/// ```
+#[must_use]
+#[cfg(feature = "alloc")]
+pub fn encode_minimal_bytes<T: AsRef<[u8]>>(data: T, case: Case) -> String {
+    let data = data.as_ref();
+    let data = match data.iter().position(|&byte| byte != 0) {
+        Some(start) => &data[start..],
+        None => return String::from("0"),
+    };
+
+    let mut s = String::with_capacity(data.len() * 2);
+    if data[0] < 0x10 {
+        s.push(case.table()[data[0] as usize] as char);
+        s.extend(BytesToHexChars::new(&data[1..], case));
+    } else {
+        s.extend(BytesToHexChars::new(data, case));
+    }
+    s
+}
+
+/// Decodes hex without leading zeros into big-endian bytes.
+///
+/// An optional `0x` (or `0X`) prefix is skipped, and an odd number of digits
+/// is treated as if there was an additional leading zero. Indices in errors
+/// are relative to the start of `hex`, including the prefix.
+///
+/// # Example
+///
+/// ```
+/// use hex::int::decode_minimal_bytes;
+///
+/// assert_eq!(decode_minimal_bytes("0xabc"), Ok(vec![0x0a, 0xbc]));
+/// assert_eq!(decode_minimal_bytes("1234"), Ok(vec![0x12, 0x34]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_minimal_bytes<T: AsRef<[u8]>>(hex: T) -> Result<Vec<u8>, FromHexError> {
+    let (hex, offset) = strip_prefix(hex.as_ref());
+    if hex.is_empty() {
+        return Err(FromHexError::InvalidStringLength);
+    }
+
+    let mut out = vec![0; (hex.len() + 1) / 2];
+    let (first, rest) = hex.split_at(hex.len() % 2);
+    if let [c] = *first {
+        out[0] = parse_be(&[c], 1).map_err(|err| err.offset(offset))? as u8;
+    }
+    for (i, (pair, byte)) in rest
+        .chunks_exact(2)
+        .zip(&mut out[first.len()..])
+        .enumerate()
+    {
+        *byte = crate::val(pair, offset + first.len() + 2 * i)?;
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_from_hex() {
+        assert_eq!(u8::from_hex("ff"), Ok(0xff));
+        assert_eq!(u8::from_hex("F"), Ok(0xf));
+        assert_eq!(u16::from_hex("0001"), Ok(1));
+        assert_eq!(u64::from_hex("DeadBeef"), Ok(0xdeadbeef));
+        assert_eq!(u128::from_hex("f".repeat(32)), Ok(u128::MAX));
+
+        assert_eq!(u8::from_hex(""), Err(FromHexError::InvalidStringLength));
+        assert_eq!(u8::from_hex("100"), Err(FromHexError::InvalidStringLength));
+        assert_eq!(
+            u32::from_hex("12g4"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 2 })
+        );
+    }
+
+    #[test]
+    fn test_from_hex_le() {
+        assert_eq!(from_hex_le::<u32>("78563412"), Ok(0x12345678));
+        assert_eq!(from_hex_le::<u64>("ff"), Ok(0xff));
+        assert_eq!(from_hex_le::<u128>("ff".repeat(16)), Ok(u128::MAX));
+
+        assert_eq!(from_hex_le::<u8>("f"), Err(FromHexError::OddLength));
+        assert_eq!(
+            from_hex_le::<u8>("0000"),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            from_hex_le::<u16>("00x0"),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 2 })
+        );
+    }
+
+    #[test]
+    fn test_encode_fixed() {
+        assert_eq!(encode_u8(0, Case::Lower), "00");
+        assert_eq!(encode_u16(0xbeef, Case::Upper), "BEEF");
+        assert_eq!(encode_u32(0x1a, Case::Lower), "0000001a");
+        assert_eq!(encode_u64(u64::MAX, Case::Lower), "ffffffffffffffff");
+        assert_eq!(
+            encode_u128(0x0123_4567_89ab_cdef, Case::Upper),
+            "00000000000000000123456789ABCDEF"
+        );
+        for value in [0, 1, 0xfe, 0x1234, u32::MAX] {
+            let hex = encode_int(value, Case::Lower);
+            assert_eq!(u32::from_hex(&*hex), Ok(value));
+        }
+    }
+
+    #[test]
+    fn test_minimal() {
+        assert_eq!(encode_minimal(0_u8, Case::Lower), "0");
+        assert_eq!(encode_minimal(0xf_u8, Case::Lower), "f");
+        assert_eq!(encode_minimal(0x10_u32, Case::Lower), "10");
+        assert_eq!(
+            encode_minimal(u128::MAX, Case::Upper),
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF"
+        );
+
+        assert_eq!(decode_minimal::<u8>("0XfF"), Ok(0xff));
+        assert_eq!(decode_minimal::<u16>("0x0"), Ok(0));
+        assert_eq!(
+            decode_minimal::<u8>("0x100"),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            decode_minimal::<u32>("0x1g"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+        );
+        for value in [0, 1, 0xf, 0x10, 0xabc, u64::MAX] {
+            let hex = encode_minimal(value, Case::Lower);
+            assert_eq!(decode_minimal(&*hex), Ok(value));
+        }
+    }
+
+    #[test]
+    fn test_signed() {
+        assert_eq!(i8::from_hex("ff"), Ok(-1));
+        assert_eq!(i16::from_hex("ff"), Ok(0xff));
+        assert_eq!(i32::from_hex("80000000"), Ok(i32::MIN));
+        assert_eq!(from_hex_le::<i16>("feff"), Ok(-2));
+        assert_eq!(encode_int(i64::MIN, Case::Lower), "8000000000000000");
+        assert_eq!(encode_int(-1_i8, Case::Lower), "ff");
+
+        assert_eq!(encode_minimal(-1_i8, Case::Lower), "-1");
+        assert_eq!(encode_minimal(i8::MIN, Case::Lower), "-80");
+        assert_eq!(encode_minimal(i8::MAX, Case::Lower), "7f");
+        assert_eq!(
+            encode_minimal(i128::MIN, Case::Upper),
+            "-80000000000000000000000000000000"
+        );
+
+        assert_eq!(decode_minimal::<i8>("-80"), Ok(i8::MIN));
+        assert_eq!(decode_minimal::<i8>("7f"), Ok(i8::MAX));
+        assert_eq!(decode_minimal::<i8>("-0"), Ok(0));
+        assert_eq!(
+            decode_minimal::<i8>("80"),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            decode_minimal::<i8>("-81"),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            decode_minimal::<i32>("-0x1g"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 4 })
+        );
+        assert_eq!(
+            decode_minimal::<u32>("-1"),
+            Err(FromHexError::InvalidHexCharacter { c: '-', index: 0 })
+        );
+        for value in [0, 1, -1, 0x7fff, -0x8000, i64::MAX, i64::MIN] {
+            let hex = encode_minimal(value, Case::Lower);
+            assert_eq!(decode_minimal(&*hex), Ok(value));
+            assert_eq!(i64::from_hex(&*encode_int(value, Case::Lower)), Ok(value));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_minimal_bytes() {
+        use alloc::vec;
+
+        assert_eq!(encode_quantity(0x400_u32), "0x400");
+        assert_eq!(encode_minimal_bytes([], Case::Lower), "0");
+        assert_eq!(encode_minimal_bytes([0x01, 0x00], Case::Lower), "100");
+        assert_eq!(encode_minimal_bytes([0xab, 0xcd], Case::Upper), "ABCD");
+
+        assert_eq!(decode_minimal_bytes("0x0"), Ok(vec![0]));
+        assert_eq!(decode_minimal_bytes("100"), Ok(vec![0x01, 0x00]));
+        assert_eq!(
+            decode_minimal_bytes("0x"),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            decode_minimal_bytes("0xg00"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 2 })
+        );
+        assert_eq!(
+            decode_minimal_bytes("0x10g"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 4 })
+        );
+    }
+
+    #[test]
+    fn test_words() {
+        assert_eq!(words_be::<u32, _>("").unwrap().len(), 0);
+        assert!(words_be::<u32, _>("0000000aFFFFFFFF")
+            .unwrap()
+            .eq([10, u32::MAX]));
+        assert!(words_le::<u16, _>("01000200").unwrap().rev().eq([2, 1]));
+        assert!(words_be::<i16, _>("ffff8000").unwrap().eq([-1, i16::MIN]));
+        assert!(words_le::<u64, _>("0100000000000080")
+            .unwrap()
+            .eq([0x8000_0000_0000_0001]));
+
+        assert_eq!(
+            words_be::<u16, _>("012").unwrap_err(),
+            FromHexError::OddLength
+        );
+        assert_eq!(
+            words_be::<u16, _>("0102ab").unwrap_err(),
+            FromHexError::InvalidStringLength
+        );
+        assert_eq!(
+            words_le::<u16, _>("0102abcg").unwrap_err(),
+            FromHexError::InvalidHexCharacter { c: 'g', index: 7 }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_decode_words() {
+        use alloc::vec;
+
+        assert_eq!(decode_words_be::<u8>("0102"), Ok(vec![1, 2]));
+        assert_eq!(
+            decode_words_be::<u32>(b"1234567800000001"),
+            Ok(vec![0x12345678, 1])
+        );
+        assert_eq!(
+            decode_words_le::<u32>("1234567800000001"),
+            Ok(vec![0x78563412, 0x01000000])
+        );
+        assert_eq!(
+            decode_words_le::<u32>("12345678000000"),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_hex_buf_fmt() {
+        use alloc::format;
+
+        let hex = encode_u16(0xab, Case::Lower);
+        assert_eq!(format!("{}", hex), "00ab");
+        assert_eq!(format!("{:>6}", hex), "  00ab");
+        assert_eq!(format!("{:?}", hex), "\"00ab\"");
+    }
+}
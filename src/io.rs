@@ -0,0 +1,466 @@
+//! Adapters for hex encoding and decoding through [`std::io`].
+//!
+//! [`EncodeWriter`] hex-encodes everything written to it into another writer,
+//! and [`DecodeReader`] decodes hex read from another reader. Both work on
+//! bounded buffers, so arbitrarily large inputs can be converted without
+//! holding them in memory.
+//!
+//! # Example
+//!
+//! ```
+//! use std::io::{Read, Write};
+//! use hex::io::{DecodeReader, EncodeWriter};
+//!
+//! let mut writer = EncodeWriter::new(Vec::new(), hex::Case::Lower);
+//! writer.write_all(b"kiwi")?;
+//! let encoded = writer.into_inner();
+//! assert_eq!(encoded, b"6b697769");
+//!
+//! let mut decoded = Vec::new();
+//! DecodeReader::new(&encoded[..]).read_to_end(&mut decoded)?;
+//! assert_eq!(decoded, b"kiwi");
+//! # Ok::<(), std::io::Error>(())
+//! ```
+use std::{
+    fmt,
+    io::{self, Read, Write},
+};
+
+use crate::{encode_to_slice_inner, Case, FromHexError, DECODE_TABLE};
+
+// Size of the stack buffers used for the conversion.
+const BUF_LEN: usize = 4096;
+
+// Wraps a decoding error into an `io::Error`.
+fn invalid_data(err: FromHexError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// The progress of a streaming conversion, as passed to the callbacks set
+/// with [`EncodeWriter::with_progress`] and [`DecodeReader::with_progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Progress {
+    /// The number of input bytes processed so far.
+    pub processed: u64,
+    /// The total number of input bytes, if known.
+    pub total: Option<u64>,
+}
+
+// Invokes a progress callback every `every` processed bytes.
+struct Reporter {
+    callback: Box<dyn FnMut(Progress) + Send>,
+    every: u64,
+    next: u64,
+    progress: Progress,
+}
+
+impl Reporter {
+    fn new<F: FnMut(Progress) + Send + 'static>(
+        every: u64,
+        total: Option<u64>,
+        callback: F,
+    ) -> Self {
+        let every = every.max(1);
+        Reporter {
+            callback: Box::new(callback),
+            every,
+            next: every,
+            progress: Progress {
+                processed: 0,
+                total,
+            },
+        }
+    }
+
+    fn advance(reporter: &mut Option<Reporter>, len: usize) {
+        if let Some(reporter) = reporter {
+            reporter.progress.processed += len as u64;
+            if reporter.progress.processed >= reporter.next {
+                reporter.next = (reporter.progress.processed / reporter.every + 1) * reporter.every;
+                (reporter.callback)(reporter.progress);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Reporter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Reporter")
+            .field("every", &self.every)
+            .field("progress", &self.progress)
+            .finish()
+    }
+}
+
+/// A writer which hex-encodes all data written to it into another writer.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Write;
+///
+/// let mut writer = hex::io::EncodeWriter::new(Vec::new(), hex::Case::Upper);
+/// write!(writer, "{}", 255)?;
+/// assert_eq!(writer.into_inner(), b"323535");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct EncodeWriter<W> {
+    inner: W,
+    table: &'static [u8; 16],
+    reporter: Option<Reporter>,
+}
+
+impl<W: Write> EncodeWriter<W> {
+    /// Creates a writer encoding into `inner` using `case`.
+    pub fn new(inner: W, case: Case) -> Self {
+        EncodeWriter {
+            inner,
+            table: case.table(),
+            reporter: None,
+        }
+    }
+
+    /// Calls `callback` whenever another `every` bytes were encoded.
+    ///
+    /// `total` is passed on to the callback, for computing percentages. The
+    /// callback is invoked at most once per call of `write`, so with a small
+    /// `every` it may skip some multiples.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::{io::Write, sync::mpsc};
+    ///
+    /// let (sender, receiver) = mpsc::channel();
+    /// let mut writer = hex::io::EncodeWriter::new(Vec::new(), hex::Case::Lower)
+    ///     .with_progress(4096, Some(10_000), move |progress| sender.send(progress.processed).unwrap());
+    /// writer.write_all(&[0; 10_000])?;
+    /// assert_eq!(receiver.try_iter().collect::<Vec<_>>(), [4096, 8192]);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn with_progress<F>(mut self, every: u64, total: Option<u64>, callback: F) -> Self
+    where
+        F: FnMut(Progress) + Send + 'static,
+    {
+        self.reporter = Some(Reporter::new(every, total, callback));
+        self
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for EncodeWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut out = [0; BUF_LEN];
+        let len = buf.len().min(BUF_LEN / 2);
+        let out = &mut out[..2 * len];
+        encode_to_slice_inner(&buf[..len], out, self.table).unwrap();
+        self.inner.write_all(out)?;
+        Reporter::advance(&mut self.reporter, len);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A reader which decodes hex read from another reader.
+///
+/// ASCII whitespace in the input is skipped, so line-wrapped hex and trailing
+/// newlines are accepted. Invalid characters and a digit left over at the end
+/// of the input fail the read with [`io::ErrorKind::InvalidData`], wrapping a
+/// [`FromHexError`] whose index is the position in the input. Bytes decoded
+/// before an invalid character are returned first, and the error is reported
+/// by the next read, and by every read after it.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Read;
+///
+/// let mut decoded = String::new();
+/// hex::io::DecodeReader::new(&b"6b69\n7769\n"[..]).read_to_string(&mut decoded)?;
+/// assert_eq!(decoded, "kiwi");
+///
+/// let err = hex::io::DecodeReader::new(&b"6b6"[..]).read_to_end(&mut Vec::new()).unwrap_err();
+/// assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct DecodeReader<R> {
+    inner: R,
+    // The high digit of a byte whose low digit hasn't been read yet.
+    high: Option<u8>,
+    // The position of the next input character.
+    index: usize,
+    // The error to report from now on.
+    error: Option<FromHexError>,
+    reporter: Option<Reporter>,
+}
+
+impl<R: Read> DecodeReader<R> {
+    /// Creates a reader decoding the hex read from `inner`.
+    pub fn new(inner: R) -> Self {
+        DecodeReader {
+            inner,
+            high: None,
+            index: 0,
+            error: None,
+            reporter: None,
+        }
+    }
+
+    /// Calls `callback` whenever another `every` bytes of hex were read.
+    ///
+    /// `total` is passed on to the callback, for computing percentages, e.g.
+    /// using the size of the input file. The callback is invoked at most once
+    /// per call of `read`.
+    pub fn with_progress<F>(mut self, every: u64, total: Option<u64>, callback: F) -> Self
+    where
+        F: FnMut(Progress) + Send + 'static,
+    {
+        self.reporter = Some(Reporter::new(every, total, callback));
+        self
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for DecodeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut input = [0; BUF_LEN];
+        loop {
+            if let Some(err) = self.error {
+                return Err(invalid_data(err));
+            }
+            if buf.is_empty() {
+                return Ok(0);
+            }
+            // Never read more digits than fit into `buf`.
+            let max = (2 * buf.len() - self.high.is_some() as usize).min(BUF_LEN);
+            let n = match self.inner.read(&mut input[..max]) {
+                Ok(0) if self.high.is_some() => {
+                    self.error = Some(FromHexError::OddLength);
+                    continue;
+                }
+                Ok(n) => n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            if n == 0 {
+                return Ok(0);
+            }
+            Reporter::advance(&mut self.reporter, n);
+
+            let mut len = 0;
+            for &c in &input[..n] {
+                self.index += 1;
+                if c.is_ascii_whitespace() {
+                    continue;
+                }
+                let digit = DECODE_TABLE[c as usize];
+                if digit == u8::MAX {
+                    self.error = Some(FromHexError::InvalidHexCharacter {
+                        c: c as char,
+                        index: self.index - 1,
+                    });
+                    break;
+                }
+                match self.high.take() {
+                    Some(high) => {
+                        buf[len] = (high << 4) | digit;
+                        len += 1;
+                    }
+                    None => self.high = Some(digit),
+                }
+            }
+            if len > 0 {
+                return Ok(len);
+            }
+        }
+    }
+}
+
+/// Checks that `reader` yields valid hex, returning the length of the
+/// decoded data.
+///
+/// The input is validated like [`DecodeReader`] would decode it, without
+/// keeping any of it, so arbitrarily large streams can be checked before
+/// allocating storage for them. Invalid hex is reported as an error of kind
+/// [`io::ErrorKind::InvalidData`] wrapping a [`FromHexError`].
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::io::validate_reader(&b"6b69\n7769\n"[..])?, 4);
+///
+/// let err = hex::io::validate_reader(&b"6b6"[..]).unwrap_err();
+/// assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn validate_reader<R: Read>(reader: R) -> io::Result<u64> {
+    io::copy(&mut DecodeReader::new(reader), &mut io::sink())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_encode_writer() {
+        let data: Vec<u8> = (0..BUF_LEN * 2 + 3).map(|i| i as u8).collect();
+        let mut writer = EncodeWriter::new(Vec::new(), Case::Lower);
+        writer.write_all(&data).unwrap();
+        assert_eq!(writer.get_ref().len(), data.len() * 2);
+        assert_eq!(writer.into_inner(), crate::encode(&data).into_bytes());
+    }
+
+    #[test]
+    fn test_decode_reader() {
+        let data: Vec<u8> = (0..BUF_LEN * 2 + 3).map(|i| i as u8).collect();
+        let mut decoded = Vec::new();
+        DecodeReader::new(crate::encode(&data).as_bytes())
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, data);
+
+        // digit pairs may be split across reads and whitespace
+        let mut reader = DecodeReader::new(&b" 0 1\n2\r\n3"[..]);
+        let mut byte = [0];
+        assert_eq!(reader.read(&mut byte).unwrap(), 1);
+        assert_eq!(byte, [0x01]);
+        assert_eq!(reader.read(&mut byte).unwrap(), 1);
+        assert_eq!(byte, [0x23]);
+        assert_eq!(reader.read(&mut byte).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_progress() {
+        use std::sync::{Arc, Mutex};
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reports);
+        let mut reader = DecodeReader::new(&[b'a'; 2 * BUF_LEN + 2][..]).with_progress(
+            BUF_LEN as u64,
+            Some(2 * BUF_LEN as u64 + 2),
+            move |progress| sink.lock().unwrap().push(progress),
+        );
+        reader.read_to_end(&mut Vec::new()).unwrap();
+        let total = Some(2 * BUF_LEN as u64 + 2);
+        assert_eq!(
+            *reports.lock().unwrap(),
+            [
+                Progress {
+                    processed: BUF_LEN as u64,
+                    total
+                },
+                Progress {
+                    processed: 2 * BUF_LEN as u64,
+                    total
+                },
+            ]
+        );
+
+        let sink = Arc::clone(&reports);
+        reports.lock().unwrap().clear();
+        let mut writer =
+            EncodeWriter::new(io::sink(), Case::Lower)
+                .with_progress(0, None, move |progress| sink.lock().unwrap().push(progress));
+        writer.write_all(&[0; 3]).unwrap();
+        writer.write_all(&[]).unwrap();
+        writer.write_all(&[0; 2]).unwrap();
+        assert_eq!(
+            reports
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|p| p.processed)
+                .collect::<Vec<_>>(),
+            [3, 5]
+        );
+    }
+
+    #[test]
+    fn test_decode_reader_errors() {
+        let decode = |input: &[u8]| {
+            let err = DecodeReader::new(input)
+                .read_to_end(&mut Vec::new())
+                .unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            *err.into_inner()
+                .unwrap()
+                .downcast::<FromHexError>()
+                .unwrap()
+        };
+        assert_eq!(decode(b"01 2"), FromHexError::OddLength);
+        assert_eq!(
+            decode(b"01\n0x"),
+            FromHexError::InvalidHexCharacter { c: 'x', index: 4 }
+        );
+
+        // the bytes before the error are returned first, then it sticks
+        let mut reader = DecodeReader::new(&b"0102x304"[..]);
+        let mut buf = [0; 4];
+        assert_eq!(reader.read(&mut buf).unwrap(), 2);
+        assert_eq!(&buf[..2], [0x01, 0x02]);
+        for _ in 0..2 {
+            let err = reader.read(&mut buf).unwrap_err();
+            assert_eq!(
+                *err.into_inner()
+                    .unwrap()
+                    .downcast::<FromHexError>()
+                    .unwrap(),
+                FromHexError::InvalidHexCharacter { c: 'x', index: 4 }
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_reader() {
+        let hex = crate::encode([0xab; BUF_LEN * 3]);
+        assert_eq!(validate_reader(hex.as_bytes()).unwrap(), BUF_LEN as u64 * 3);
+        assert_eq!(validate_reader(&b""[..]).unwrap(), 0);
+        assert_eq!(validate_reader(&b" \n"[..]).unwrap(), 0);
+
+        let err = validate_reader(&b"0a 0g"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.into_inner().unwrap().downcast_ref::<FromHexError>(),
+            Some(&FromHexError::InvalidHexCharacter { c: 'g', index: 4 })
+        );
+        let err = validate_reader(&b"0a0"[..]).unwrap_err();
+        assert_eq!(
+            err.into_inner().unwrap().downcast_ref::<FromHexError>(),
+            Some(&FromHexError::OddLength)
+        );
+    }
+}
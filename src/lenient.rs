@@ -0,0 +1,317 @@
+//! Decoding of hex written for humans rather than machines.
+//!
+//! Hex copied from documents, terminals or config files is often formatted:
+//! split by whitespace or separators like `de:ad:be:ef`, or written as a
+//! list of `0x`-prefixed bytes. The lenient decoder skips all of that and
+//! only looks at the digits. It still rejects any other character, and an
+//! odd number of digits in total.
+//!
+//! [`normalize`] converts such input into canonical hex, i.e. lowercase
+//! digits only, e.g. before storing or comparing it.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! assert_eq!(hex::lenient::decode("DE:AD be-ef"), Ok(vec![0xde, 0xad, 0xbe, 0xef]));
+//! assert_eq!(hex::lenient::decode("0x01, 0x02,\n0x03"), Ok(vec![1, 2, 3]));
+//! # }
+//! ```
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+use core::iter;
+
+#[cfg(feature = "alloc")]
+use crate::HEX_CHARS_LOWER;
+use crate::{FromHexError, DECODE_TABLE};
+
+// Characters ignored between digits, in addition to ASCII whitespace.
+const SEPARATORS: [char; 4] = [':', '-', '_', ','];
+
+/// Options for lenient decoding.
+///
+/// By default, ASCII whitespace, the separators `:`, `-`, `_` and `,`, and a
+/// `0x` or `0X` prefix at the start of every group of digits are skipped.
+///
+/// # Example
+///
+/// ```
+/// use hex::lenient::Lenient;
+///
+/// let mut out = [0; 8];
+/// let len = Lenient::new().fullwidth(true).decode_to_slice("ＤＥ ad", &mut out)?;
+/// assert_eq!(&out[..len], [0xde, 0xad]);
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Lenient {
+    fullwidth: bool,
+}
+
+impl Lenient {
+    /// Creates the default options.
+    #[must_use]
+    pub fn new() -> Self {
+        Lenient::default()
+    }
+
+    /// Sets whether Unicode fullwidth forms (e.g. `０`–`９`, `Ａ`–`Ｆ` and
+    /// `：`) are treated like their ASCII equivalents.
+    ///
+    /// These frequently show up in text scraped from East Asian documents
+    /// and PDFs. Disabled by default.
+    #[must_use]
+    pub fn fullwidth(mut self, fullwidth: bool) -> Self {
+        self.fullwidth = fullwidth;
+        self
+    }
+
+    // Maps fullwidth forms to ASCII, if enabled.
+    fn normalize_char(&self, c: char) -> char {
+        match c {
+            '\u{ff01}'..='\u{ff5e}' if self.fullwidth => {
+                char::from_u32(c as u32 - 0xfee0).unwrap_or(c)
+            }
+            '\u{3000}' if self.fullwidth => ' ',
+            c => c,
+        }
+    }
+
+    // Returns the values of the digits in `input`, or an error for every
+    // invalid character.
+    pub(crate) fn digits<'a>(
+        &'a self,
+        input: &'a str,
+    ) -> impl Iterator<Item = Result<u8, FromHexError>> + 'a {
+        let mut chars = input.char_indices().peekable();
+        let mut group_start = true;
+        iter::from_fn(move || {
+            while let Some((index, original)) = chars.next() {
+                let c = self.normalize_char(original);
+                if c.is_ascii_whitespace() || SEPARATORS.contains(&c) {
+                    group_start = true;
+                    continue;
+                }
+                if group_start
+                    && c == '0'
+                    && matches!(chars.peek(), Some(&(_, x)) if matches!(self.normalize_char(x), 'x' | 'X'))
+                {
+                    chars.next();
+                    group_start = false;
+                    continue;
+                }
+                group_start = false;
+
+                let digit = DECODE_TABLE.get(c as usize).copied().unwrap_or(u8::MAX);
+                if digit == u8::MAX {
+                    return Some(Err(FromHexError::InvalidHexCharacter {
+                        c: original,
+                        index,
+                    }));
+                }
+                return Some(Ok(digit));
+            }
+            None
+        })
+    }
+
+    // Calls `f` with the value of every digit in `input`.
+    fn for_each_digit(
+        &self,
+        input: &str,
+        mut f: impl FnMut(u8) -> Result<(), FromHexError>,
+    ) -> Result<(), FromHexError> {
+        self.digits(input).try_for_each(|digit| f(digit?))
+    }
+
+    /// Decodes `input` into the beginning of `out`, returning the number of
+    /// bytes written.
+    ///
+    /// Indices of invalid characters are byte offsets into `input`. If `out`
+    /// is too short, [`FromHexError::InvalidStringLength`] is returned.
+    pub fn decode_to_slice(&self, input: &str, out: &mut [u8]) -> Result<usize, FromHexError> {
+        let mut digits = 0;
+        self.for_each_digit(input, |digit| {
+            let byte = out
+                .get_mut(digits / 2)
+                .ok_or(FromHexError::InvalidStringLength)?;
+            if digits % 2 == 0 {
+                *byte = digit << 4;
+            } else {
+                *byte |= digit;
+            }
+            digits += 1;
+            Ok(())
+        })?;
+        crate::decoded_len(digits)
+    }
+
+    /// Decodes `input` into a vector.
+    ///
+    /// Indices of invalid characters are byte offsets into `input`.
+    #[cfg(feature = "alloc")]
+    pub fn decode(&self, input: &str) -> Result<Vec<u8>, FromHexError> {
+        let mut out = Vec::with_capacity(input.len() / 2);
+        let mut high = None;
+        self.for_each_digit(input, |digit| {
+            match high.take() {
+                Some(high) => out.push((high << 4) | digit),
+                None => high = Some(digit),
+            }
+            Ok(())
+        })?;
+        match high {
+            Some(_) => Err(FromHexError::OddLength),
+            None => Ok(out),
+        }
+    }
+
+    /// Converts `input` into canonical hex: lowercase digits only, without
+    /// prefixes, whitespace or separators.
+    ///
+    /// Input which already is canonical is borrowed, so the common case of
+    /// clean input doesn't allocate. Indices of invalid characters are byte
+    /// offsets into `input`.
+    #[cfg(feature = "alloc")]
+    pub fn normalize<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, FromHexError> {
+        let canonical = input
+            .bytes()
+            .all(|c| matches!(c, b'0'..=b'9' | b'a'..=b'f'));
+        if canonical && input.len() % 2 == 0 {
+            return Ok(Cow::Borrowed(input));
+        }
+        let mut out = String::with_capacity(input.len());
+        self.for_each_digit(input, |digit| {
+            out.push(HEX_CHARS_LOWER[digit as usize] as char);
+            Ok(())
+        })?;
+        crate::decoded_len(out.len())?;
+        Ok(Cow::Owned(out))
+    }
+}
+
+/// Decodes `input` using the default [`Lenient`] options.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::lenient::decode("0xCAFE babe"), Ok(vec![0xca, 0xfe, 0xba, 0xbe]));
+/// assert!(hex::lenient::decode("ca fe b").is_err());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode(input: &str) -> Result<Vec<u8>, FromHexError> {
+    Lenient::new().decode(input)
+}
+
+/// Converts `input` into canonical hex using the default [`Lenient`]
+/// options, borrowing it if it already is canonical.
+///
+/// # Example
+///
+/// ```
+/// use std::borrow::Cow;
+///
+/// assert_eq!(hex::lenient::normalize("0xCA:FE"), Ok(Cow::Owned("cafe".to_string())));
+/// assert!(matches!(hex::lenient::normalize("cafe"), Ok(Cow::Borrowed("cafe"))));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn normalize(input: &str) -> Result<Cow<'_, str>, FromHexError> {
+    Lenient::new().normalize(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_decode_to_slice() {
+        let mut out = [0; 4];
+        let lenient = Lenient::new();
+        assert_eq!(lenient.decode_to_slice(" 0x0a:0B-0c_0d\n", &mut out), Ok(4));
+        assert_eq!(out, [0x0a, 0x0b, 0x0c, 0x0d]);
+        assert_eq!(
+            lenient.decode_to_slice("0x0x", &mut out),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 3 })
+        );
+        assert_eq!(lenient.decode_to_slice("", &mut out), Ok(0));
+
+        assert_eq!(
+            lenient.decode_to_slice("01 02 03 04 05", &mut out),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            lenient.decode_to_slice("01 2", &mut out),
+            Err(FromHexError::OddLength)
+        );
+        // "x" is only skipped as part of a prefix
+        assert_eq!(
+            lenient.decode_to_slice("01x2", &mut out),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 2 })
+        );
+    }
+
+    #[test]
+    fn test_fullwidth() {
+        let mut out = [0; 2];
+        let input = "０ｘＡ０：ｆｆ";
+        assert_eq!(
+            Lenient::new().decode_to_slice(input, &mut out),
+            Err(FromHexError::InvalidHexCharacter { c: '０', index: 0 })
+        );
+        let lenient = Lenient::new().fullwidth(true);
+        assert_eq!(lenient.decode_to_slice(input, &mut out), Ok(2));
+        assert_eq!(out, [0xa0, 0xff]);
+        assert_eq!(lenient.decode_to_slice("ab\u{3000}cd", &mut out), Ok(2));
+        assert_eq!(out, [0xab, 0xcd]);
+
+        // other fullwidth forms are still rejected, at their original index
+        assert_eq!(
+            lenient.decode_to_slice("ａｇ", &mut out),
+            Err(FromHexError::InvalidHexCharacter { c: 'ｇ', index: 3 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_decode() {
+        use alloc::vec;
+
+        assert_eq!(
+            decode("0xDE 0xAD,\n0xbeef"),
+            Ok(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(decode(""), Ok(vec![]));
+        assert_eq!(decode("a"), Err(FromHexError::OddLength));
+        assert_eq!(
+            decode("ab ; cd"),
+            Err(FromHexError::InvalidHexCharacter { c: ';', index: 3 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_normalize() {
+        use alloc::string::ToString;
+
+        for input in ["", "00", "0123456789abcdef"] {
+            assert_eq!(normalize(input), Ok(Cow::Borrowed(input)));
+        }
+        assert_eq!(
+            normalize("0xAB, 0xcd"),
+            Ok(Cow::Owned::<str>("abcd".to_string()))
+        );
+        assert_eq!(normalize("0A"), Ok(Cow::Owned::<str>("0a".to_string())));
+        assert_eq!(
+            Lenient::new().fullwidth(true).normalize("ＡＢ"),
+            Ok(Cow::Owned::<str>("ab".to_string()))
+        );
+        assert_eq!(normalize("abc"), Err(FromHexError::OddLength));
+        assert_eq!(
+            normalize("ab:cg"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 4 })
+        );
+    }
+}
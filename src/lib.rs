@@ -28,25 +28,214 @@
 //!
 //! # assert_eq!(hex_string, "48656c6c6f20776f726c6421");
 //! ```
+//!
+//! # Nightly features
+//!
+//! These features use unstable language or library features, so they only
+//! build with a nightly compiler:
+//!
+//! - `ascii-char`: encoding into `core::ascii::Char` (`hex::ascii`).
+//! - `generic-const-exprs`: decoding byte string literals into arrays whose
+//!   length is inferred from the input (`hex::from_hex_exact`).
+//! - `allocator-api`: decoding into vectors in a custom allocator
+//!   (`hex::decode_in`).
+//! - `portable-simd`: encoding and decoding long inputs with `core::simd`.
 
 #![doc(html_root_url = "https://docs.rs/hex/0.5")]
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(feature = "ascii-char", feature(ascii_char))]
+#![cfg_attr(feature = "allocator-api", feature(allocator_api))]
+#![cfg_attr(
+    all(feature = "portable-simd", not(feature = "force-scalar")),
+    feature(portable_simd)
+)]
+#![cfg_attr(
+    feature = "generic-const-exprs",
+    feature(generic_const_exprs),
+    allow(incomplete_features)
+)]
 #![allow(clippy::unreadable_literal)]
+// Avoid raising the minimum supported Rust version for these.
+#![allow(
+    clippy::manual_is_multiple_of,
+    clippy::manual_div_ceil,
+    clippy::manual_repeat_n
+)]
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
 #[cfg(feature = "alloc")]
-use alloc::{string::String, vec, vec::Vec};
+use alloc::{string::String, vec::Vec};
 
-use core::{iter, u8};
+use core::{fmt, iter, mem::MaybeUninit};
 
 mod error;
+#[cfg(feature = "std")]
+pub use crate::error::FromHexCStringError;
 pub use crate::error::FromHexError;
 
+pub mod abbrev;
+
+mod adapters;
+pub use crate::adapters::{EncodeHex, HexToBytes, IterHexExt};
+
+pub mod backend;
+
+#[cfg(feature = "alloc")]
+pub mod batch;
+
+pub mod bcd;
+
+mod buffer;
+pub use crate::buffer::{decode_extend, decode_into, HexBuffer};
+
+#[cfg(feature = "alloc")]
+pub mod case_mask;
+
+pub mod compat;
+
+mod compare;
+pub use crate::compare::{cmp_hex, cmp_hex_bytes, eq_ignore_format, find_bytes};
+
+mod macros;
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::macros::{assert_failed, decode_source, decoded_len};
+}
+
+pub mod string;
+pub use crate::string::HexStr;
+#[cfg(feature = "alloc")]
+pub use crate::string::{HexString, HexStringBuilder};
+
+pub mod tables;
+use crate::tables::{DECODE_TABLE, HEX_CHARS_LOWER, HEX_CHARS_UPPER};
+
+#[cfg(not(feature = "force-scalar"))]
+mod unrolled;
+
+#[cfg(all(feature = "simd", not(feature = "force-scalar")))]
+mod simd;
+
+#[cfg(all(feature = "portable-simd", not(feature = "force-scalar")))]
+mod portable;
+
+#[cfg(not(feature = "force-scalar"))]
+mod swar;
+
+#[cfg(feature = "alloc")]
+pub mod diff;
+
+pub mod dump;
+
+mod dynamic;
+pub use crate::dynamic::HexWrite;
+
+mod display;
+pub use crate::display::{display_iter, encode_chunks, DisplayIter, Hex};
+
+mod fixed;
+pub use crate::fixed::FixedHex;
+
+pub mod int;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod io;
+
+pub mod lenient;
+
+pub mod nibble;
+
+pub mod prelude;
+
+pub mod sql;
+
+pub mod utf16;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
+#[cfg(feature = "ascii-char")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ascii-char")))]
+pub mod ascii;
+
+#[cfg(feature = "base32hex")]
+#[cfg_attr(docsrs, doc(cfg(feature = "base32hex")))]
+pub mod base32hex;
+
+#[cfg(any(feature = "primitive-types", feature = "ruint"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "primitive-types", feature = "ruint"))))]
+pub mod bigint;
+
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+pub mod bytes;
+
+#[cfg(feature = "tokio-util")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-util")))]
+pub mod codec;
+
+#[cfg(feature = "diesel")]
+mod diesel;
+
+#[cfg(feature = "ffi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ffi")))]
+pub mod ffi;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod fs;
+
+#[cfg(feature = "async-graphql")]
+mod graphql;
+
+#[cfg(feature = "mmap")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+pub mod mmap;
+
+#[cfg(feature = "nom")]
+#[cfg_attr(docsrs, doc(cfg(feature = "nom")))]
+pub mod nom;
+
+#[cfg(feature = "bytemuck")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+pub mod pod;
+
+#[cfg(feature = "proptest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "proptest")))]
+pub mod proptest;
+
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "rayon")]
+pub use crate::parallel::{decode_parallel, encode_parallel};
+
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(feature = "rand")]
+pub use crate::random::{random_bytes_hex, random_hex};
+
 #[cfg(feature = "serde")]
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
 pub mod serde;
+
+#[cfg(feature = "sqlx")]
+mod sqlx;
+
+#[cfg(feature = "test-utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-utils")))]
+pub mod test_vectors;
+
+#[cfg(feature = "tracing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+pub mod tracing;
+
+#[cfg(feature = "winnow")]
+#[cfg_attr(docsrs, doc(cfg(feature = "winnow")))]
+pub mod winnow;
+
 #[cfg(feature = "serde")]
 pub use crate::serde::deserialize;
 #[cfg(all(feature = "alloc", feature = "serde"))]
@@ -66,7 +255,11 @@ pub use crate::serde::{serialize, serialize_upper};
 /// # assert_eq!("Hello world!".encode_hex::<String>(), "48656c6c6f20776f726c6421".to_string());
 /// ```
 ///
-/// *Note*: instead of using this trait, you might want to use [`encode()`].
+/// *Note*: instead of using this trait, you might want to use [`encode()`],
+/// or [`encode_to()`] to append to a string, which encode the whole slice at
+/// once instead of collecting the digits one by one. Within the trait,
+/// [`encode_hex_string`](ToHex::encode_hex_string) and
+/// [`encode_hex_bytes`](ToHex::encode_hex_bytes) do the same.
 pub trait ToHex {
     /// Encode the hex strict representing `self` into the result. Lower case
     /// letters are used (e.g. `f9b4ca`)
@@ -75,24 +268,104 @@ pub trait ToHex {
     /// Encode the hex strict representing `self` into the result. Upper case
     /// letters are used (e.g. `F9B4CA`)
     fn encode_hex_upper<T: iter::FromIterator<char>>(&self) -> T;
+
+    /// Encodes `self` into a `String`, using the given case.
+    ///
+    /// This is the same as collecting [`encode_hex`](ToHex::encode_hex) or
+    /// [`encode_hex_upper`](ToHex::encode_hex_upper) into a `String`, but for
+    /// byte slices the whole slice is encoded at once, instead of the digits
+    /// being pushed one by one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hex::{Case, ToHex};
+    ///
+    /// assert_eq!(b"kiwi".encode_hex_string(Case::Upper), "6B697769");
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn encode_hex_string(&self, case: Case) -> String {
+        match case {
+            Case::Lower => self.encode_hex(),
+            Case::Upper => self.encode_hex_upper(),
+        }
+    }
+
+    /// Encodes `self` into ASCII digits in a byte vector, using the given
+    /// case.
+    ///
+    /// This is [`encode_hex_string`](ToHex::encode_hex_string), for code
+    /// which works with bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hex::{Case, ToHex};
+    ///
+    /// assert_eq!(b"kiwi".encode_hex_bytes(Case::Lower), b"6b697769");
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn encode_hex_bytes(&self, case: Case) -> Vec<u8> {
+        self.encode_hex_string(case).into_bytes()
+    }
 }
 
-const HEX_CHARS_LOWER: &[u8; 16] = b"0123456789abcdef";
-const HEX_CHARS_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+/// The case of the letters `a-f` in encoded hex.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Case {
+    /// Lowercase letters, e.g. `f9b4ca`.
+    #[default]
+    Lower,
+    /// Uppercase letters, e.g. `F9B4CA`.
+    Upper,
+}
+
+impl Case {
+    #[inline]
+    fn table(self) -> &'static [u8; 16] {
+        match self {
+            Case::Lower => HEX_CHARS_LOWER,
+            Case::Upper => HEX_CHARS_UPPER,
+        }
+    }
+}
 
-struct BytesToHexChars<'a> {
+/// An iterator over the hex characters encoding a byte slice.
+///
+/// This can be iterated from either end, and knows its exact length.
+///
+/// # Example
+///
+/// ```
+/// use hex::{BytesToHexChars, Case};
+///
+/// let chars = BytesToHexChars::new(b"\x01\xab", Case::Upper);
+/// assert_eq!(chars.len(), 4);
+/// assert_eq!(chars.clone().collect::<String>(), "01AB");
+/// assert_eq!(chars.rev().collect::<String>(), "BA10");
+/// ```
+#[derive(Debug, Clone)]
+pub struct BytesToHexChars<'a> {
     inner: ::core::slice::Iter<'a, u8>,
     table: &'static [u8; 16],
     next: Option<char>,
+    next_back: Option<char>,
 }
 
 impl<'a> BytesToHexChars<'a> {
+    /// Creates an iterator encoding `data` using `case`.
+    #[inline]
+    pub fn new(data: &'a [u8], case: Case) -> BytesToHexChars<'a> {
+        BytesToHexChars::from_table(data, case.table())
+    }
+
     #[inline(always)]
-    fn new(inner: &'a [u8], table: &'static [u8; 16]) -> BytesToHexChars<'a> {
+    fn from_table(inner: &'a [u8], table: &'static [u8; 16]) -> BytesToHexChars<'a> {
         BytesToHexChars {
             inner: inner.iter(),
             table,
             next: None,
+            next_back: None,
         }
     }
 }
@@ -104,11 +377,14 @@ impl<'a> Iterator for BytesToHexChars<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         match self.next.take() {
             Some(current) => Some(current),
-            None => self.inner.next().map(|byte| {
-                let current = self.table[(byte >> 4) as usize] as char;
-                self.next = Some(self.table[(byte & 0x0F) as usize] as char);
-                current
-            }),
+            None => match self.inner.next() {
+                Some(byte) => {
+                    let current = self.table[(byte >> 4) as usize] as char;
+                    self.next = Some(self.table[(byte & 0x0F) as usize] as char);
+                    Some(current)
+                }
+                None => self.next_back.take(),
+            },
         }
     }
 
@@ -116,21 +392,56 @@ impl<'a> Iterator for BytesToHexChars<'a> {
         let length = self.len();
         (length, Some(length))
     }
+
+    // Collecting and `for_each` go through this, so encode in bulk into a
+    // buffer instead of taking the bytes one at a time.
+    #[inline]
+    fn fold<B, F: FnMut(B, char) -> B>(self, init: B, mut f: F) -> B {
+        let mut acc = init;
+        if let Some(current) = self.next {
+            acc = f(acc, current);
+        }
+        let mut buf = [0_u8; 128];
+        for chunk in self.inner.as_slice().chunks(buf.len() / 2) {
+            let buf = &mut buf[..chunk.len() * 2];
+            encode_unchecked_inner(chunk, buf, self.table);
+            acc = buf.iter().fold(acc, |acc, &digit| f(acc, digit as char));
+        }
+        if let Some(current) = self.next_back {
+            acc = f(acc, current);
+        }
+        acc
+    }
+}
+
+impl<'a> iter::DoubleEndedIterator for BytesToHexChars<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.next_back.take() {
+            Some(current) => Some(current),
+            None => match self.inner.next_back() {
+                Some(byte) => {
+                    let current = self.table[(byte & 0x0F) as usize] as char;
+                    self.next_back = Some(self.table[(byte >> 4) as usize] as char);
+                    Some(current)
+                }
+                None => self.next.take(),
+            },
+        }
+    }
 }
 
 impl<'a> iter::ExactSizeIterator for BytesToHexChars<'a> {
     #[inline(always)]
     fn len(&self) -> usize {
-        let mut length = self.inner.len() * 2;
-        if self.next.is_some() {
-            length += 1;
-        }
-        length
+        self.inner.len() * 2 + self.next.is_some() as usize + self.next_back.is_some() as usize
     }
 }
 
+impl<'a> iter::FusedIterator for BytesToHexChars<'a> {}
+
 fn encode_to_iter<T: iter::FromIterator<char>>(table: &'static [u8; 16], source: &[u8]) -> T {
-    BytesToHexChars::new(source, table).collect()
+    BytesToHexChars::from_table(source, table).collect()
 }
 
 impl<T: AsRef<[u8]>> ToHex for T {
@@ -141,11 +452,25 @@ impl<T: AsRef<[u8]>> ToHex for T {
     fn encode_hex_upper<U: iter::FromIterator<char>>(&self) -> U {
         encode_to_iter(HEX_CHARS_UPPER, self.as_ref())
     }
+
+    #[cfg(feature = "alloc")]
+    fn encode_hex_string(&self, case: Case) -> String {
+        encode_case(self, case)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn encode_hex_bytes(&self, case: Case) -> Vec<u8> {
+        let data = self.as_ref();
+        let mut out = Vec::with_capacity(data.len() * 2);
+        encode_append_inner(data, &mut out, case.table());
+        out
+    }
 }
 
 /// Types that can be decoded from a hex string.
 ///
-/// This trait is implemented for `Vec<u8>` and small `u8`-arrays.
+/// This trait is implemented for `Vec<u8>`, `u8`-arrays and the primitive
+/// integers (see the [`int`] module).
 ///
 /// # Example
 ///
@@ -171,32 +496,16 @@ pub trait FromHex: Sized {
     fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, Self::Error>;
 }
 
-const __: u8 = u8::MAX;
-
-// Lookup table for ascii to hex decoding.
-#[rustfmt::skip]
-static DECODE_TABLE: [u8; 256] = [
-    //   1   2   3   4   5   6   7   8   9   a   b   c   d   e   f
-    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 0
-    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 1
-    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 2
-     0,  1,  2,  3,  4,  5,  6,  7,  8,  9, __, __, __, __, __, __, // 3
-    __, 10, 11, 12, 13, 14, 15, __, __, __, __, __, __, __, __, __, // 4
-    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 5
-    __, 10, 11, 12, 13, 14, 15, __, __, __, __, __, __, __, __, __, // 6
-    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 7
-    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 8
-    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 9
-    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // a
-    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // b
-    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // c
-    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // d
-    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // e
-    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // f
-];
-
 #[inline]
 fn val(bytes: &[u8], idx: usize) -> Result<u8, FromHexError> {
+    #[cfg(feature = "pair-table")]
+    {
+        let pair = u16::from_ne_bytes([bytes[0], bytes[1]]);
+        let decoded = tables::DECODE_PAIRS[usize::from(pair)];
+        if decoded <= 0xff {
+            return Ok(decoded as u8);
+        }
+    }
     let upper = DECODE_TABLE[bytes[0] as usize];
     let lower = DECODE_TABLE[bytes[1] as usize];
     if upper == u8::MAX {
@@ -214,22 +523,65 @@ fn val(bytes: &[u8], idx: usize) -> Result<u8, FromHexError> {
     Ok((upper << 4) | lower)
 }
 
+// Checks that `data` is a valid hex string, without decoding it.
+fn validate(data: &[u8]) -> Result<(), FromHexError> {
+    if data.len() % 2 != 0 {
+        return Err(FromHexError::OddLength);
+    }
+    for (i, pair) in data.chunks_exact(2).enumerate() {
+        val(pair, 2 * i)?;
+    }
+    Ok(())
+}
+
 #[cfg(feature = "alloc")]
 impl FromHex for Vec<u8> {
     type Error = FromHexError;
 
     fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, Self::Error> {
         let hex = hex.as_ref();
-        if hex.len() % 2 != 0 {
-            return Err(FromHexError::OddLength);
-        }
-
-        let mut out = vec![0; hex.len() / 2];
-        decode_to_slice(hex, &mut out)?;
+        let len = decoded_len(hex.len())?;
+        let mut out = Vec::with_capacity(len);
+        // Saftey: decoding writes every byte of the output if it succeeds.
+        unsafe { extend_uninit(&mut out, len, |out| decode_uninit(hex, out)) }?;
         Ok(out)
     }
 }
 
+/// Decodes a C string, which must not contain NUL bytes.
+///
+/// A single trailing NUL byte, as included when encoding a C string together
+/// with its terminator, is accepted and stripped. Any other NUL byte results
+/// in [`FromHexCStringError::InteriorNul`].
+///
+/// # Example
+///
+/// ```
+/// use hex::{FromHex, FromHexCStringError};
+/// use std::ffi::CString;
+///
+/// assert_eq!(CString::from_hex("6b697769"), Ok(CString::new("kiwi").unwrap()));
+/// assert_eq!(CString::from_hex("6b69776900"), Ok(CString::new("kiwi").unwrap()));
+/// assert_eq!(
+///     CString::from_hex("6b00776900"),
+///     Err(FromHexCStringError::InteriorNul { position: 1 })
+/// );
+/// ```
+#[cfg(feature = "std")]
+impl FromHex for std::ffi::CString {
+    type Error = FromHexCStringError;
+
+    fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, Self::Error> {
+        let mut bytes = Vec::from_hex(hex)?;
+        if bytes.last() == Some(&0) {
+            bytes.pop();
+        }
+        std::ffi::CString::new(bytes).map_err(|err| FromHexCStringError::InteriorNul {
+            position: err.nul_position(),
+        })
+    }
+}
+
 impl<const N: usize> FromHex for [u8; N] {
     type Error = FromHexError;
 
@@ -257,10 +609,7 @@ impl<const N: usize> FromHex for [u8; N] {
 #[must_use]
 #[cfg(feature = "alloc")]
 pub fn encode<T: AsRef<[u8]>>(data: T) -> String {
-    let data = data.as_ref();
-    let mut out = vec![0; data.len() * 2];
-    encode_to_slice(data, &mut out).unwrap();
-    String::from_utf8(out).unwrap()
+    encode_case(data, Case::Lower)
 }
 
 /// Encodes `data` as hex string using lowercase characters, appending to target string.
@@ -281,7 +630,8 @@ pub fn encode<T: AsRef<[u8]>>(data: T) -> String {
 /// ```
 #[cfg(feature = "alloc")]
 pub fn encode_to<T: AsRef<[u8]>>(data: T, s: &mut String) {
-    s.extend(BytesToHexChars::new(data.as_ref(), HEX_CHARS_LOWER))
+    // Saftey: only ASCII hex digits are appended
+    encode_append_inner(data.as_ref(), unsafe { s.as_mut_vec() }, HEX_CHARS_LOWER)
 }
 
 /// Encodes `data` as hex string using uppercase characters.
@@ -297,10 +647,7 @@ pub fn encode_to<T: AsRef<[u8]>>(data: T, s: &mut String) {
 #[must_use]
 #[cfg(feature = "alloc")]
 pub fn encode_upper<T: AsRef<[u8]>>(data: T) -> String {
-    let data = data.as_ref();
-    let mut out = vec![0; data.len() * 2];
-    encode_to_slice_upper(data, &mut out).unwrap();
-    String::from_utf8(out).unwrap()
+    encode_case(data, Case::Upper)
 }
 
 /// Encodes `data` as hex string using uppercase characters, appending to target string.
@@ -316,144 +663,908 @@ pub fn encode_upper<T: AsRef<[u8]>>(data: T) -> String {
 /// ```
 #[cfg(feature = "alloc")]
 pub fn encode_upper_to<T: AsRef<[u8]>>(data: T, s: &mut String) {
-    s.extend(BytesToHexChars::new(data.as_ref(), HEX_CHARS_UPPER))
+    // Saftey: only ASCII hex digits are appended
+    encode_append_inner(data.as_ref(), unsafe { s.as_mut_vec() }, HEX_CHARS_UPPER)
 }
 
-/// Decodes a hex string into raw bytes.
+/// Encodes `data` as hex string using the given case.
 ///
-/// Both, upper and lower case characters are valid in the input string and can
-/// even be mixed (e.g. `f9b4ca`, `F9B4CA` and `f9B4Ca` are all valid strings).
+/// This is the same as [`encode`] or [`encode_upper`], for when the case is
+/// only known at runtime.
 ///
 /// # Example
 ///
 /// ```
-/// assert_eq!(
-///     hex::decode("48656c6c6f20776f726c6421"),
-///     Ok("Hello world!".to_owned().into_bytes())
-/// );
+/// use hex::Case;
 ///
-/// assert_eq!(hex::decode("123"), Err(hex::FromHexError::OddLength));
-/// assert!(hex::decode("foo").is_err());
+/// assert_eq!(hex::encode_case("kiwi", Case::Lower), "6b697769");
+/// assert_eq!(hex::encode_case([0xab, 0xcd], Case::Upper), "ABCD");
 /// ```
+#[must_use]
 #[cfg(feature = "alloc")]
-pub fn decode<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, FromHexError> {
-    FromHex::from_hex(data)
+pub fn encode_case<T: AsRef<[u8]>>(data: T, case: Case) -> String {
+    let data = data.as_ref();
+    let mut out = Vec::with_capacity(data.len() * 2);
+    encode_append_inner(data, &mut out, case.table());
+    // Saftey: We just wrote valid utf8 hex string into the buffer
+    unsafe { String::from_utf8_unchecked(out) }
 }
 
-/// Decode a hex string into a mutable bytes slice.
+/// Encodes `data` as hex using lowercase characters, into a shared string.
 ///
-/// Both, upper and lower case characters are valid in the input string and can
-/// even be mixed (e.g. `f9b4ca`, `F9B4CA` and `f9B4Ca` are all valid strings).
+/// The digits are written directly into the allocation of the `Arc`, instead
+/// of into a `String` which is then copied, e.g. for hashes whose display is
+/// cached and shared between threads.
 ///
 /// # Example
 ///
 /// ```
-/// let mut bytes = [0u8; 4];
-/// assert_eq!(hex::decode_to_slice("6b697769", &mut bytes as &mut [u8]), Ok(()));
-/// assert_eq!(&bytes, b"kiwi");
+/// use std::sync::Arc;
+///
+/// let hex: Arc<str> = hex::encode_arc("kiwi");
+/// assert_eq!(&*hex, "6b697769");
 /// ```
-#[inline]
-pub fn decode_to_slice<T: AsRef<[u8]>>(data: T, out: &mut [u8]) -> Result<(), FromHexError> {
-    let data = data.as_ref();
-
-    if data.len() % 2 != 0 {
-        return Err(FromHexError::OddLength);
-    }
-    if data.len() / 2 != out.len() {
-        return Err(FromHexError::InvalidStringLength);
-    }
-
-    for (i, (data, byte)) in data.chunks_exact(2).zip(out).enumerate() {
-        *byte = val(data, 2 * i)?;
-    }
-
-    Ok(())
-}
-
-// the inverse of `val`.
-#[inline(always)]
 #[must_use]
-fn byte2hex(byte: u8, table: &[u8; 16]) -> (u8, u8) {
-    let high = table[((byte & 0xf0) >> 4) as usize];
-    let low = table[(byte & 0x0f) as usize];
+#[cfg(feature = "alloc")]
+pub fn encode_arc<T: AsRef<[u8]>>(data: T) -> alloc::sync::Arc<str> {
+    use alloc::sync::Arc;
 
-    (high, low)
+    let data = data.as_ref();
+    let mut out = Arc::<[u8]>::new_uninit_slice(data.len() * 2);
+    encode_uninit(data, Arc::get_mut(&mut out).unwrap(), HEX_CHARS_LOWER);
+    // Saftey: `encode_uninit` initialized every byte
+    let out = unsafe { out.assume_init() };
+    // Saftey: The slice is valid utf8, and `str` has the same layout as `[u8]`
+    unsafe { Arc::from_raw(Arc::into_raw(out) as *const str) }
 }
 
-#[inline(always)]
-fn encode_to_slice_inner<'a>(
-    input: &[u8],
-    output: &'a mut [u8],
-    table: &[u8; 16],
-) -> Result<(), FromHexError> {
-    if input.len() * 2 != output.len() {
-        return Err(FromHexError::InvalidStringLength);
-    }
-
-    for (byte, output) in input.iter().zip(output.chunks_exact_mut(2)) {
-        let (high, low) = byte2hex(*byte, table);
-        output[0] = high;
-        output[1] = low;
-    }
+/// Encodes `data` as hex using lowercase characters, into a reference
+/// counted string.
+///
+/// This is the same as [`encode_arc`], for data shared within one thread.
+///
+/// # Example
+///
+/// ```
+/// use std::rc::Rc;
+///
+/// let hex: Rc<str> = hex::encode_rc("kiwi");
+/// assert_eq!(&*hex, "6b697769");
+/// ```
+#[must_use]
+#[cfg(feature = "alloc")]
+pub fn encode_rc<T: AsRef<[u8]>>(data: T) -> alloc::rc::Rc<str> {
+    use alloc::rc::Rc;
 
-    Ok(())
+    let data = data.as_ref();
+    let mut out = Rc::<[u8]>::new_uninit_slice(data.len() * 2);
+    encode_uninit(data, Rc::get_mut(&mut out).unwrap(), HEX_CHARS_LOWER);
+    // Saftey: `encode_uninit` initialized every byte
+    let out = unsafe { out.assume_init() };
+    // Saftey: The slice is valid utf8, and `str` has the same layout as `[u8]`
+    unsafe { Rc::from_raw(Rc::into_raw(out) as *const str) }
 }
 
-/// Encodes some bytes into a mutable slice of bytes using lowercase characters.
+/// Encodes `data` as hex using lowercase characters, appending the ASCII
+/// digits to a byte buffer.
 ///
-/// The output buffer, has to be able to hold exactly `input.len() * 2` bytes,
-/// otherwise this function will return an error.
+/// This is the same as [`encode_to`], but for protocol code assembling frames
+/// in a `Vec<u8>`, which would otherwise have to go through a `String`.
 ///
 /// # Example
 ///
 /// ```
-/// # use hex::FromHexError;
-/// # fn main() -> Result<(), FromHexError> {
-/// let mut bytes = [0u8; 4 * 2];
-///
-/// let hex_str = hex::encode_to_slice(b"kiwi", &mut bytes)?;
-/// assert_eq!(hex_str, "6b697769");
-/// assert_eq!(&bytes, b"6b697769");
-/// # Ok(())
-/// # }
+/// let mut frame = b"ID ".to_vec();
+/// hex::encode_append(b"kiwi", &mut frame);
+/// assert_eq!(frame, b"ID 6b697769");
 /// ```
+#[cfg(feature = "alloc")]
+pub fn encode_append<T: AsRef<[u8]>>(data: T, out: &mut Vec<u8>) {
+    encode_append_inner(data.as_ref(), out, HEX_CHARS_LOWER)
+}
+
+/// Encodes `data` as hex using uppercase characters, appending the ASCII
+/// digits to a byte buffer.
 ///
-/// If the buffer is too large, an error is returned:
+/// This is the same as [`encode_append`], but uses uppercase characters.
+///
+/// # Example
 ///
 /// ```
-/// use hex::FromHexError;
-/// # fn main() -> Result<(), FromHexError> {
-/// let mut bytes = [0_u8; 5 * 2];
+/// let mut frame = b"ID ".to_vec();
+/// hex::encode_upper_append(b"kiwi", &mut frame);
+/// assert_eq!(frame, b"ID 6B697769");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode_upper_append<T: AsRef<[u8]>>(data: T, out: &mut Vec<u8>) {
+    encode_append_inner(data.as_ref(), out, HEX_CHARS_UPPER)
+}
+
+#[cfg(feature = "alloc")]
+fn encode_append_inner(data: &[u8], out: &mut Vec<u8>, table: &[u8; 16]) {
+    // Saftey: encoding writes every byte of the output.
+    unsafe {
+        extend_uninit(out, data.len() * 2, |out| {
+            encode_uninit(data, out, table);
+            Ok::<_, core::convert::Infallible>(())
+        })
+    }
+    .unwrap_or_else(|never| match never {});
+}
+
+// Extends `out` by `len` bytes, which are written by `write` into the spare
+// capacity, without zeroing it first. The length is only updated if `write`
+// succeeds.
+//
+// Saftey: `write` must initialize every byte of the slice it is given if it
+// returns `Ok`.
+#[cfg(feature = "alloc")]
+unsafe fn extend_uninit<E>(
+    out: &mut Vec<u8>,
+    len: usize,
+    write: impl FnOnce(&mut [MaybeUninit<u8>]) -> Result<(), E>,
+) -> Result<(), E> {
+    out.reserve(len);
+    write(&mut out.spare_capacity_mut()[..len])?;
+    out.set_len(out.len() + len);
+    Ok(())
+}
+
+/// Encodes the bytes yielded by an iterator as hex string using lowercase
+/// characters.
 ///
-/// assert_eq!(hex::encode_to_slice(b"kiwi", &mut bytes), Err(FromHexError::InvalidStringLength));
+/// Unlike [`encode`], this doesn't need the input to be contiguous in
+/// memory, so it works with generators and chained sources.
+///
+/// # Example
 ///
-/// // you can do this instead:
-/// let hex_str = hex::encode_to_slice(b"kiwi", &mut bytes[..4 * 2])?;
-/// assert_eq!(hex_str, "6b697769");
-/// assert_eq!(&bytes, b"6b697769\0\0");
-/// # Ok(())
-/// # }
 /// ```
-pub fn encode_to_slice<T: AsRef<[u8]>>(input: T, output: &mut [u8]) -> Result<&mut str, FromHexError> {
-    encode_to_slice_inner(input.as_ref(), output, HEX_CHARS_LOWER)?;
-    if cfg!(debug_assertions) {
-        Ok(core::str::from_utf8_mut(output).unwrap())
-    } else {
-        // Saftey: We just wrote valid utf8 hex string into the output
-        Ok(unsafe { core::str::from_utf8_unchecked_mut(output) })
-    }
+/// assert_eq!(hex::encode_iter((0..4).map(|i| i * 0x11)), "00112233");
+/// assert_eq!(hex::encode_iter(b"ab".iter().chain(b"cd").copied()), "61626364");
+/// ```
+#[must_use]
+#[cfg(feature = "alloc")]
+pub fn encode_iter<I: IntoIterator<Item = u8>>(data: I) -> String {
+    let data = data.into_iter();
+    let mut s = String::with_capacity(data.size_hint().0.saturating_mul(2));
+    s.extend(EncodeHex::new(data, Case::Lower));
+    s
 }
 
-/// Encodes some bytes into a mutable slice of bytes using uppercase characters.
+/// Encodes the bytes yielded by an iterator into a mutable slice of bytes,
+/// using lowercase characters.
 ///
-/// The output buffer, has to be able to hold exactly `input.len() * 2` bytes,
-/// otherwise this function will return an error.
+/// As the length of the input isn't known in advance, `output` may be larger
+/// than needed. The returned string is the part of `output` which was
+/// written to. If `output` is too short to hold the encoded input,
+/// [`FromHexError::InvalidStringLength`] is returned.
 ///
 /// # Example
 ///
 /// ```
 /// # use hex::FromHexError;
-/// # fn main() -> Result<(), FromHexError> {
+/// let mut buffer = [0_u8; 16];
+/// let hex = hex::encode_iter_to_slice((1..=3).rev(), &mut buffer)?;
+/// assert_eq!(hex, "030201");
+///
+/// assert_eq!(
+///     hex::encode_iter_to_slice(0..9, &mut buffer),
+///     Err(FromHexError::InvalidStringLength)
+/// );
+/// # Ok::<(), FromHexError>(())
+/// ```
+pub fn encode_iter_to_slice<I: IntoIterator<Item = u8>>(
+    input: I,
+    output: &mut [u8],
+) -> Result<&mut str, FromHexError> {
+    let mut len = 0;
+    for byte in input {
+        let pair = output
+            .get_mut(len..len + 2)
+            .ok_or(FromHexError::InvalidStringLength)?;
+        let (high, low) = byte2hex(byte, HEX_CHARS_LOWER);
+        pair[0] = high;
+        pair[1] = low;
+        len += 2;
+    }
+    // Saftey: We just wrote valid utf8 hex string into the output
+    Ok(unsafe { core::str::from_utf8_unchecked_mut(&mut output[..len]) })
+}
+
+/// Decodes a hex string into raw bytes.
+///
+/// Both, upper and lower case characters are valid in the input string and can
+/// even be mixed (e.g. `f9b4ca`, `F9B4CA` and `f9B4Ca` are all valid strings).
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(
+///     hex::decode("48656c6c6f20776f726c6421"),
+///     Ok("Hello world!".to_owned().into_bytes())
+/// );
+///
+/// assert_eq!(hex::decode("123"), Err(hex::FromHexError::OddLength));
+/// assert!(hex::decode("foo").is_err());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, FromHexError> {
+    FromHex::from_hex(data)
+}
+
+/// Decodes a hex string, appending the bytes to `out`.
+///
+/// This is otherwise the same as [`decode`], but allows reusing the
+/// allocation of a vector, e.g. when decoding many records in a loop. Exactly
+/// as much space as needed is reserved. Returns the number of bytes appended;
+/// on error `out` is left unchanged.
+///
+/// # Example
+///
+/// ```
+/// let mut bytes = b"fruit: ".to_vec();
+/// assert_eq!(hex::decode_append("6b697769", &mut bytes), Ok(4));
+/// assert_eq!(bytes, b"fruit: kiwi");
+///
+/// assert!(hex::decode_append("6b69776", &mut bytes).is_err());
+/// assert_eq!(bytes, b"fruit: kiwi");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_append<T: AsRef<[u8]>>(data: T, out: &mut Vec<u8>) -> Result<usize, FromHexError> {
+    let data = data.as_ref();
+    let len = decoded_len(data.len())?;
+    out.reserve_exact(len);
+    // Saftey: decoding writes every byte of the output if it succeeds.
+    unsafe { extend_uninit(out, len, |out| decode_uninit(data, out)) }?;
+    Ok(len)
+}
+
+/// Decodes a hex string into a vector in the given allocator.
+///
+/// This is otherwise the same as [`decode`], and lets decoded buffers live
+/// in e.g. a per-request arena instead of the global allocator. This requires
+/// the `allocator-api` feature, which needs a nightly compiler.
+///
+/// # Example
+///
+/// ```
+/// #![feature(allocator_api)]
+/// use std::alloc::System;
+///
+/// let bytes: Vec<u8, System> = hex::decode_in("6b697769", System)?;
+/// assert_eq!(bytes, b"kiwi");
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+#[cfg(feature = "allocator-api")]
+#[cfg_attr(docsrs, doc(cfg(feature = "allocator-api")))]
+pub fn decode_in<T: AsRef<[u8]>, A: core::alloc::Allocator>(
+    data: T,
+    alloc: A,
+) -> Result<Vec<u8, A>, FromHexError> {
+    let mut out = Vec::new_in(alloc);
+    decode_append_in(data, &mut out)?;
+    Ok(out)
+}
+
+/// Decodes a hex string, appending the bytes to a vector in any allocator.
+///
+/// This is the same as [`decode_append`] for vectors created with
+/// [`Vec::new_in`]. This requires the `allocator-api` feature, which needs a
+/// nightly compiler.
+#[cfg(feature = "allocator-api")]
+#[cfg_attr(docsrs, doc(cfg(feature = "allocator-api")))]
+pub fn decode_append_in<T: AsRef<[u8]>, A: core::alloc::Allocator>(
+    data: T,
+    out: &mut Vec<u8, A>,
+) -> Result<usize, FromHexError> {
+    let data = data.as_ref();
+    let len = decoded_len(data.len())?;
+    out.reserve_exact(len);
+    // Like `extend_uninit`, which only takes vectors in the global
+    // allocator.
+    decode_uninit(data, &mut out.spare_capacity_mut()[..len])?;
+    // Saftey: decoding wrote every byte of the spare capacity it was given.
+    unsafe { out.set_len(out.len() + len) };
+    Ok(len)
+}
+
+/// Decodes hex split into fragments, as if they were concatenated.
+///
+/// Fragments may have any length, so the two digits of a byte can end up in
+/// different fragments. This decodes ropes and chunked reads without copying
+/// them into one string first. Indices of invalid characters are positions in
+/// the concatenated input.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::decode_fragments(["6b6", "97", "769"]), Ok(b"kiwi".to_vec()));
+/// assert_eq!(hex::decode_fragments(["6b6", "9", "7"]), Err(hex::FromHexError::OddLength));
+/// assert_eq!(
+///     hex::decode_fragments(["6b", "6g"]),
+///     Err(hex::FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_fragments<I>(fragments: I) -> Result<Vec<u8>, FromHexError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    let mut out = Vec::new();
+    // The first digit of a byte split across fragments, and its index.
+    let mut pending = None;
+    let mut index = 0;
+    for fragment in fragments {
+        let mut fragment = fragment.as_ref();
+        if let (Some((high, start)), [low, rest @ ..]) = (pending, fragment) {
+            out.push(val(&[high, *low], start)?);
+            pending = None;
+            fragment = rest;
+            index += 1;
+        }
+        let (pairs, rest) = fragment.split_at(fragment.len() - fragment.len() % 2);
+        decode_append(pairs, &mut out).map_err(|err| err.offset(index))?;
+        index += pairs.len();
+        if let [high] = *rest {
+            if DECODE_TABLE[high as usize] == u8::MAX {
+                return Err(FromHexError::InvalidHexCharacter {
+                    c: high as char,
+                    index,
+                });
+            }
+            pending = Some((high, index));
+            index += 1;
+        }
+    }
+    match pending {
+        Some(_) => Err(FromHexError::OddLength),
+        None => Ok(out),
+    }
+}
+
+/// Decodes a list of hex strings separated by `delimiter`.
+///
+/// ASCII whitespace around the items is ignored. If `delimiter` is itself
+/// whitespace, items are separated by any run of whitespace instead, so
+/// `b' '` splits lines and columns alike. Indices of invalid characters are
+/// relative to the whole input. This is the inverse of joining encoded items.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::decode_list("01, 02ff,", b','), Ok(vec![vec![0x01], vec![0x02, 0xff], vec![]]));
+/// assert_eq!(hex::decode_list("01 02\n03", b' '), Ok(vec![vec![0x01], vec![0x02], vec![0x03]]));
+///
+/// assert_eq!(
+///     hex::decode_list("01,0x", b','),
+///     Err(hex::FromHexError::InvalidHexCharacter { c: 'x', index: 4 })
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_list<T: AsRef<[u8]>>(data: T, delimiter: u8) -> Result<Vec<Vec<u8>>, FromHexError> {
+    let data = data.as_ref();
+    let whitespace = delimiter.is_ascii_whitespace();
+    let mut list = Vec::new();
+    if data.iter().all(u8::is_ascii_whitespace) {
+        return Ok(list);
+    }
+
+    let mut offset = 0;
+    for item in data.split(|&b| b == delimiter || (whitespace && b.is_ascii_whitespace())) {
+        let start = offset;
+        offset += item.len() + 1;
+
+        let leading = item.iter().take_while(|b| b.is_ascii_whitespace()).count();
+        let trailing = item[leading..]
+            .iter()
+            .rev()
+            .take_while(|b| b.is_ascii_whitespace())
+            .count();
+        let item = &item[leading..item.len() - trailing];
+        if whitespace && item.is_empty() {
+            continue;
+        }
+        list.push(Vec::from_hex(item).map_err(|err| err.offset(start + leading))?);
+    }
+    Ok(list)
+}
+
+/// Validates a hex string and returns an iterator which decodes it lazily.
+///
+/// The returned iterator knows its exact length and can be consumed from
+/// either end, so e.g. the least significant bytes of a big-endian number can
+/// be processed first without reversing an intermediate buffer.
+///
+/// # Example
+///
+/// ```
+/// let bytes = hex::decode_iter("0102ff")?;
+/// assert_eq!(bytes.len(), 3);
+/// assert!(bytes.rev().eq([0xff, 0x02, 0x01]));
+///
+/// assert!(hex::decode_iter("123").is_err());
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+pub fn decode_iter<T: AsRef<[u8]> + ?Sized>(data: &T) -> Result<string::Bytes<'_>, FromHexError> {
+    HexStr::from_bytes(data.as_ref()).map(HexStr::bytes)
+}
+
+/// Decode a hex string into a mutable bytes slice.
+///
+/// Both, upper and lower case characters are valid in the input string and can
+/// even be mixed (e.g. `f9b4ca`, `F9B4CA` and `f9B4Ca` are all valid strings).
+///
+/// # Example
+///
+/// ```
+/// let mut bytes = [0u8; 4];
+/// assert_eq!(hex::decode_to_slice("6b697769", &mut bytes as &mut [u8]), Ok(()));
+/// assert_eq!(&bytes, b"kiwi");
+/// ```
+#[inline]
+pub fn decode_to_slice<T: AsRef<[u8]>>(data: T, out: &mut [u8]) -> Result<(), FromHexError> {
+    // Saftey: decoding only writes initialized bytes.
+    decode_uninit(data.as_ref(), unsafe { as_uninit(out) })
+}
+
+// Decodes `data` into `out`, which may be uninitialized. Every byte of `out`
+// is written if this succeeds.
+#[inline(always)]
+fn decode_uninit(data: &[u8], out: &mut [MaybeUninit<u8>]) -> Result<(), FromHexError> {
+    if decoded_len(data.len())? != out.len() {
+        return Err(FromHexError::InvalidStringLength);
+    }
+    let done = decode_prefix(data, out);
+    for (i, (data, byte)) in data.chunks_exact(2).zip(out).enumerate().skip(done) {
+        byte.write(val(data, 2 * i)?);
+    }
+
+    Ok(())
+}
+
+/// Decodes a hex string into a mutable bytes slice, without checking the
+/// length or the characters.
+///
+/// This is the same as [`decode_to_slice`], for hot loops where the input is
+/// known to be valid, e.g. because its length is fixed by its type and it was
+/// validated earlier, so the checks and the error handling can be skipped.
+///
+/// # Safety
+///
+/// `data` must be exactly twice as long as `out`. If `data` contains
+/// characters which aren't hex digits, the bytes written to `out` are
+/// unspecified, but this doesn't cause undefined behavior.
+///
+/// # Example
+///
+/// ```
+/// let mut bytes = [0u8; 4];
+/// // Saftey: the input is twice as long as the output.
+/// unsafe { hex::decode_to_slice_unchecked("6b697769", &mut bytes) };
+/// assert_eq!(&bytes, b"kiwi");
+/// ```
+#[inline]
+pub unsafe fn decode_to_slice_unchecked<T: AsRef<[u8]>>(data: T, out: &mut [u8]) {
+    let data = data.as_ref();
+    debug_assert_eq!(data.len(), 2 * out.len());
+
+    // Saftey: decoding only writes initialized bytes.
+    let done = decode_prefix(data, as_uninit(out));
+    for i in done..out.len() {
+        let high = DECODE_TABLE[*data.get_unchecked(2 * i) as usize];
+        let low = DECODE_TABLE[*data.get_unchecked(2 * i + 1) as usize];
+        *out.get_unchecked_mut(i) = (high << 4) | low;
+    }
+}
+
+// Decodes as much of `data` as the unrolled, vector and word-wide paths
+// handle, into `out`, which is half as long. Returns the number of bytes
+// decoded, leaving the rest, and reporting invalid characters, to the byte
+// loop.
+#[inline(always)]
+#[cfg_attr(feature = "force-scalar", allow(unused_variables))]
+fn decode_prefix(data: &[u8], out: &mut [MaybeUninit<u8>]) -> usize {
+    #[cfg(not(feature = "force-scalar"))]
+    if unrolled::decode(data, out) {
+        return out.len();
+    }
+    #[cfg(all(
+        any(feature = "simd", feature = "portable-simd"),
+        not(feature = "force-scalar")
+    ))]
+    let done = backend::decode(data, out);
+    #[cfg(not(all(
+        any(feature = "simd", feature = "portable-simd"),
+        not(feature = "force-scalar")
+    )))]
+    let done = 0;
+    #[cfg(not(feature = "force-scalar"))]
+    let done = done + swar::decode(&data[2 * done..], &mut out[done..]);
+    done
+}
+
+/// Decodes a hex string of known length into an array of half its length.
+///
+/// The length of the output is derived from the length of the input, so it
+/// doesn't have to be spelled out, and there is no length to check at
+/// runtime. Input with an odd number of digits fails to compile.
+///
+/// This requires the `generic-const-exprs` feature, which needs a nightly
+/// compiler.
+///
+/// # Example
+///
+/// ```
+/// #![feature(generic_const_exprs)]
+/// # #![allow(incomplete_features)]
+///
+/// let kiwi = hex::from_hex_exact(b"6b697769")?;
+/// assert_eq!(&kiwi, b"kiwi");
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+///
+/// ```compile_fail
+/// #![feature(generic_const_exprs)]
+/// # #![allow(incomplete_features)]
+///
+/// let odd = hex::from_hex_exact(b"6b6");
+/// ```
+#[cfg(feature = "generic-const-exprs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generic-const-exprs")))]
+pub fn from_hex_exact<const S: usize>(hex: &[u8; S]) -> Result<[u8; S / 2], FromHexError>
+where
+    [(); S / 2]:,
+{
+    #[allow(clippy::let_unit_value)]
+    let () = EvenLength::<S>::ASSERT;
+    let mut out = [0; S / 2];
+    for (i, (pair, byte)) in hex.chunks_exact(2).zip(&mut out).enumerate() {
+        *byte = val(pair, 2 * i)?;
+    }
+    Ok(out)
+}
+
+// Fails to compile when instantiated with an odd length.
+#[cfg(feature = "generic-const-exprs")]
+struct EvenLength<const S: usize>;
+
+#[cfg(feature = "generic-const-exprs")]
+impl<const S: usize> EvenLength<S> {
+    const ASSERT: () = assert!(S % 2 == 0, "a hex string has an even number of digits");
+}
+
+/// Decode a hex string into a mutable bytes slice, returning the slice.
+///
+/// This is the same as [`decode_to_slice`], but returns the decoded bytes,
+/// like [`encode_to_slice`] returns the encoded string, so the result can be
+/// passed on directly.
+///
+/// # Example
+///
+/// ```
+/// let mut bytes = [0u8; 4];
+/// let kiwi = hex::decode_to_slice_mut("6b697769", &mut bytes)?;
+/// kiwi.make_ascii_uppercase();
+/// assert_eq!(&bytes, b"KIWI");
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+pub fn decode_to_slice_mut<T: AsRef<[u8]>>(
+    data: T,
+    out: &mut [u8],
+) -> Result<&mut [u8], FromHexError> {
+    decode_to_slice(data, out)?;
+    Ok(out)
+}
+
+/// Decode a hex string into the beginning of a mutable bytes slice.
+///
+/// Unlike [`decode_to_slice`], `out` may be larger than needed, which is handy
+/// when reusing a fixed scratch buffer. Returns the number of bytes written;
+/// if `out` is too short, [`FromHexError::InvalidStringLength`] is returned.
+///
+/// # Example
+///
+/// ```
+/// let mut buffer = [0u8; 16];
+/// let len = hex::decode_to_slice_prefix("6b697769", &mut buffer)?;
+/// assert_eq!(&buffer[..len], b"kiwi");
+///
+/// assert!(hex::decode_to_slice_prefix("6b697769", &mut buffer[..3]).is_err());
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+pub fn decode_to_slice_prefix<T: AsRef<[u8]>>(
+    data: T,
+    out: &mut [u8],
+) -> Result<usize, FromHexError> {
+    let data = data.as_ref();
+    let len = decoded_len(data.len())?;
+    let out = out
+        .get_mut(..len)
+        .ok_or(FromHexError::InvalidStringLength)?;
+    decode_to_slice(data, out)?;
+    Ok(len)
+}
+
+/// How far [`decode_to_slices`] filled its output slices.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Filled {
+    /// The number of output slices which were filled completely.
+    pub full: usize,
+    /// The number of bytes written to the slice following the full ones.
+    pub partial: usize,
+}
+
+/// Decode a hex string into a sequence of mutable bytes slices.
+///
+/// The slices are filled in order, e.g. a header buffer followed by a body
+/// buffer, so a single hex blob can be decoded directly into pre-laid-out
+/// structures. Decoding stops once the input is consumed, so the slices may
+/// be larger than needed; [`Filled`] tells how far they were filled. If the
+/// slices are too short in total, [`FromHexError::InvalidStringLength`] is
+/// returned. Error indices are relative to the whole input.
+///
+/// # Example
+///
+/// ```
+/// use hex::Filled;
+///
+/// let mut header = [0u8; 2];
+/// let mut body = [0u8; 8];
+/// let filled = hex::decode_to_slices("00046b697769", &mut [&mut header, &mut body])?;
+/// assert_eq!(filled, Filled { full: 1, partial: 4 });
+/// assert_eq!(header, [0x00, 0x04]);
+/// assert_eq!(&body[..filled.partial], b"kiwi");
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+pub fn decode_to_slices<T: AsRef<[u8]>>(
+    data: T,
+    outs: &mut [&mut [u8]],
+) -> Result<Filled, FromHexError> {
+    let data = data.as_ref();
+    let mut remaining = decoded_len(data.len())?;
+    if outs.iter().map(|out| out.len()).sum::<usize>() < remaining {
+        return Err(FromHexError::InvalidStringLength);
+    }
+
+    let mut filled = Filled::default();
+    let mut offset = 0;
+    for out in outs.iter_mut() {
+        if remaining == 0 {
+            break;
+        }
+        let len = remaining.min(out.len());
+        let pairs = data[offset..offset + 2 * len].chunks_exact(2);
+        for (i, (pair, byte)) in pairs.zip(out.iter_mut()).enumerate() {
+            *byte = val(pair, offset + 2 * i)?;
+        }
+        offset += 2 * len;
+        remaining -= len;
+
+        if len == out.len() {
+            filled.full += 1;
+        } else {
+            filled.partial = len;
+        }
+    }
+    Ok(filled)
+}
+
+/// Returns the length of the hex encoding of `len` bytes, or `None` if it
+/// doesn't fit into a `usize`.
+///
+/// This is useful to size buffers from untrusted length metadata, e.g. a
+/// length prefix read from the network, without risking an overflow on 16
+/// or 32-bit targets.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::encoded_len(4), Some(8));
+/// assert_eq!(hex::encoded_len(usize::MAX), None);
+/// ```
+#[must_use]
+pub const fn encoded_len(len: usize) -> Option<usize> {
+    len.checked_mul(2)
+}
+
+/// Returns the number of bytes encoded by `len` hex digits.
+///
+/// An odd `len` is rejected with [`FromHexError::OddLength`].
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::decoded_len(8), Ok(4));
+/// assert_eq!(hex::decoded_len(7), Err(hex::FromHexError::OddLength));
+/// ```
+pub const fn decoded_len(len: usize) -> Result<usize, FromHexError> {
+    if len % 2 != 0 {
+        return Err(FromHexError::OddLength);
+    }
+    Ok(len / 2)
+}
+
+// the inverse of `val`.
+#[inline(always)]
+#[must_use]
+fn byte2hex(byte: u8, table: &[u8; 16]) -> (u8, u8) {
+    let high = table[((byte & 0xf0) >> 4) as usize];
+    let low = table[(byte & 0x0f) as usize];
+
+    (high, low)
+}
+
+#[inline(always)]
+fn encode_to_slice_inner(
+    input: &[u8],
+    output: &mut [u8],
+    table: &[u8; 16],
+) -> Result<(), FromHexError> {
+    if encoded_len(input.len()) != Some(output.len()) {
+        return Err(FromHexError::InvalidStringLength);
+    }
+    encode_unchecked_inner(input, output, table);
+    Ok(())
+}
+
+// Encodes `input` into `output`, which must be twice as long for all of it
+// to be written.
+#[inline(always)]
+fn encode_unchecked_inner(input: &[u8], output: &mut [u8], table: &[u8; 16]) {
+    // Saftey: encoding only writes initialized bytes.
+    encode_uninit(input, unsafe { as_uninit(output) }, table)
+}
+
+// Encodes `input` into `output`, which may be uninitialized, and must be
+// twice as long for all of it to be written.
+#[inline(always)]
+fn encode_uninit(input: &[u8], output: &mut [MaybeUninit<u8>], table: &[u8; 16]) {
+    debug_assert_eq!(encoded_len(input.len()), Some(output.len()));
+    #[cfg(not(feature = "force-scalar"))]
+    if unrolled::encode(input, output, table) {
+        return;
+    }
+    #[cfg(all(
+        any(feature = "simd", feature = "portable-simd"),
+        not(feature = "force-scalar")
+    ))]
+    let (input, output) = {
+        let done = backend::encode(input, output, table);
+        (&input[done..], &mut output[2 * done..])
+    };
+
+    let pairs = if table == HEX_CHARS_UPPER {
+        &tables::ENCODE_PAIRS_UPPER
+    } else {
+        debug_assert_eq!(table, HEX_CHARS_LOWER);
+        &tables::ENCODE_PAIRS_LOWER
+    };
+    for (byte, output) in input.iter().zip(output.chunks_exact_mut(2)) {
+        write_slice(output, &pairs[*byte as usize].to_ne_bytes());
+    }
+}
+
+// Views `out` as uninitialized memory, for the kernels, which write to their
+// output without reading it.
+//
+// Saftey: only initialized bytes may be written to the returned slice.
+#[inline(always)]
+unsafe fn as_uninit(out: &mut [u8]) -> &mut [MaybeUninit<u8>] {
+    &mut *(out as *mut [u8] as *mut [MaybeUninit<u8>])
+}
+
+// Copies `src` into the start of `out`, like `copy_from_slice`, for
+// uninitialized memory.
+#[inline(always)]
+fn write_slice(out: &mut [MaybeUninit<u8>], src: &[u8]) {
+    for (out, &byte) in out.iter_mut().zip(src) {
+        out.write(byte);
+    }
+}
+
+// Writes `data` as hex into a formatter, without allocating.
+fn fmt_hex<W: fmt::Write + ?Sized>(data: &[u8], case: Case, f: &mut W) -> fmt::Result {
+    let mut buf = [0_u8; 128];
+    for chunk in data.chunks(buf.len() / 2) {
+        let buf = &mut buf[..chunk.len() * 2];
+        encode_to_slice_inner(chunk, buf, case.table()).unwrap();
+        // Saftey: We just wrote valid utf8 hex string into the buffer
+        f.write_str(unsafe { core::str::from_utf8_unchecked(buf) })?;
+    }
+    Ok(())
+}
+
+// Writes `digits` hex digits using `write_digits`, handling the `#` flag,
+// width, fill, alignment and zero-padding like `{:x}` does for integers.
+fn pad_hex(
+    f: &mut fmt::Formatter,
+    digits: usize,
+    write_digits: impl FnOnce(&mut fmt::Formatter) -> fmt::Result,
+) -> fmt::Result {
+    use core::fmt::Write;
+
+    let prefix = if f.alternate() { "0x" } else { "" };
+    let padding = f
+        .width()
+        .map_or(0, |width| width.saturating_sub(prefix.len() + digits));
+    if f.sign_aware_zero_pad() {
+        f.write_str(prefix)?;
+        for _ in 0..padding {
+            f.write_char('0')?;
+        }
+        return write_digits(f);
+    }
+
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Left) => (0, padding),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (padding, 0),
+    };
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    f.write_str(prefix)?;
+    write_digits(f)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+/// Encodes some bytes into a mutable slice of bytes using lowercase characters.
+///
+/// The output buffer, has to be able to hold exactly `input.len() * 2` bytes,
+/// otherwise this function will return an error.
+///
+/// # Example
+///
+/// ```
+/// # use hex::FromHexError;
+/// # fn main() -> Result<(), FromHexError> {
+/// let mut bytes = [0u8; 4 * 2];
+///
+/// let hex_str = hex::encode_to_slice(b"kiwi", &mut bytes)?;
+/// assert_eq!(hex_str, "6b697769");
+/// assert_eq!(&bytes, b"6b697769");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// If the buffer is too large, an error is returned:
+///
+/// ```
+/// use hex::FromHexError;
+/// # fn main() -> Result<(), FromHexError> {
+/// let mut bytes = [0_u8; 5 * 2];
+///
+/// assert_eq!(hex::encode_to_slice(b"kiwi", &mut bytes), Err(FromHexError::InvalidStringLength));
+///
+/// // you can do this instead:
+/// let hex_str = hex::encode_to_slice(b"kiwi", &mut bytes[..4 * 2])?;
+/// assert_eq!(hex_str, "6b697769");
+/// assert_eq!(&bytes, b"6b697769\0\0");
+/// # Ok(())
+/// # }
+/// ```
+pub fn encode_to_slice<T: AsRef<[u8]>>(
+    input: T,
+    output: &mut [u8],
+) -> Result<&mut str, FromHexError> {
+    encode_to_slice_case(input, output, Case::Lower)
+}
+
+/// Encodes some bytes into a mutable slice of bytes using uppercase characters.
+///
+/// The output buffer, has to be able to hold exactly `input.len() * 2` bytes,
+/// otherwise this function will return an error.
+///
+/// # Example
+///
+/// ```
+/// # use hex::FromHexError;
+/// # fn main() -> Result<(), FromHexError> {
 /// let mut bytes = [0u8; 4 * 2];
 ///
 /// hex::encode_to_slice_upper(b"kiwi", &mut bytes)?;
@@ -465,7 +1576,32 @@ pub fn encode_to_slice_upper<T: AsRef<[u8]>>(
     input: T,
     output: &mut [u8],
 ) -> Result<&mut str, FromHexError> {
-    encode_to_slice_inner(input.as_ref(), output, HEX_CHARS_UPPER)?;
+    encode_to_slice_case(input, output, Case::Upper)
+}
+
+/// Encodes some bytes into a mutable slice of bytes using the given case.
+///
+/// This is the same as [`encode_to_slice`] or [`encode_to_slice_upper`], for
+/// when the case is only known at runtime.
+///
+/// # Example
+///
+/// ```
+/// # use hex::{Case, FromHexError};
+/// # fn main() -> Result<(), FromHexError> {
+/// let mut bytes = [0u8; 4 * 2];
+///
+/// hex::encode_to_slice_case(b"kiwi", &mut bytes, Case::Upper)?;
+/// assert_eq!(&bytes, b"6B697769");
+/// # Ok(())
+/// # }
+/// ```
+pub fn encode_to_slice_case<T: AsRef<[u8]>>(
+    input: T,
+    output: &mut [u8],
+    case: Case,
+) -> Result<&mut str, FromHexError> {
+    encode_to_slice_inner(input.as_ref(), output, case.table())?;
     if cfg!(debug_assertions) {
         Ok(core::str::from_utf8_mut(output).unwrap())
     } else {
@@ -474,11 +1610,35 @@ pub fn encode_to_slice_upper<T: AsRef<[u8]>>(
     }
 }
 
+/// Encodes some bytes into a mutable slice of bytes using lowercase
+/// characters, without checking the length of the output.
+///
+/// This is the same as [`encode_to_slice`], for hot loops where the lengths
+/// are known to match, so the check and the error handling can be skipped.
+///
+/// # Safety
+///
+/// `output` must be exactly twice as long as `input`.
+///
+/// # Example
+///
+/// ```
+/// let mut bytes = [0u8; 4 * 2];
+/// // Saftey: the output is twice as long as the input.
+/// let hex_str = unsafe { hex::encode_to_slice_unchecked(b"kiwi", &mut bytes) };
+/// assert_eq!(hex_str, "6b697769");
+/// ```
+#[inline]
+pub unsafe fn encode_to_slice_unchecked<T: AsRef<[u8]>>(input: T, output: &mut [u8]) -> &mut str {
+    encode_unchecked_inner(input.as_ref(), output, HEX_CHARS_LOWER);
+    core::str::from_utf8_unchecked_mut(output)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     #[cfg(feature = "alloc")]
-    use alloc::string::ToString;
+    use alloc::{string::ToString, vec};
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -527,10 +1687,193 @@ mod test {
         );
     }
 
+    #[test]
+    #[cfg(feature = "generic-const-exprs")]
+    fn test_from_hex_exact() {
+        assert_eq!(from_hex_exact(b""), Ok([]));
+        assert_eq!(from_hex_exact(b"6B697769"), Ok(*b"kiwi"));
+        assert_eq!(
+            from_hex_exact(b"6b69776g"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 7 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_from_hex_c_string() {
+        use std::ffi::CString;
+
+        assert_eq!(CString::from_hex(""), Ok(CString::default()));
+        assert_eq!(CString::from_hex("00"), Ok(CString::default()));
+        assert_eq!(CString::from_hex("4869"), Ok(CString::new("Hi").unwrap()));
+        assert_eq!(
+            CString::from_hex("0000"),
+            Err(FromHexCStringError::InteriorNul { position: 0 })
+        );
+        assert_eq!(
+            CString::from_hex("486"),
+            Err(FromHexCStringError::Hex(FromHexError::OddLength))
+        );
+        assert_eq!(
+            CString::from_hex("48zz"),
+            Err(FromHexCStringError::Hex(
+                FromHexError::InvalidHexCharacter { c: 'z', index: 2 }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_lengths() {
+        assert_eq!(encoded_len(0), Some(0));
+        assert_eq!(encoded_len(usize::MAX / 2), Some(usize::MAX - 1));
+        assert_eq!(encoded_len(usize::MAX / 2 + 1), None);
+
+        assert_eq!(decoded_len(0), Ok(0));
+        assert_eq!(decoded_len(usize::MAX - 1), Ok(usize::MAX / 2));
+        assert_eq!(decoded_len(usize::MAX), Err(FromHexError::OddLength));
+    }
+
+    #[test]
+    fn test_decode_to_slice_mut() {
+        let mut out = [0; 2];
+        assert_eq!(
+            decode_to_slice_mut("01ff", &mut out),
+            Ok(&mut [0x01, 0xff][..])
+        );
+        assert_eq!(
+            decode_to_slice_mut("01", &mut out),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+
+    #[test]
+    fn test_decode_to_slices() {
+        let (mut a, mut b, mut c) = ([0; 1], [0; 0], [0; 3]);
+        assert_eq!(
+            decode_to_slices("01020304", &mut [&mut a, &mut b, &mut c]),
+            Ok(Filled {
+                full: 3,
+                partial: 0
+            })
+        );
+        assert_eq!((a, c), ([1], [2, 3, 4]));
+
+        assert_eq!(
+            decode_to_slices("ff", &mut [&mut a, &mut b, &mut c]),
+            Ok(Filled {
+                full: 1,
+                partial: 0
+            })
+        );
+        assert_eq!(
+            decode_to_slices("0506", &mut [&mut c, &mut a]),
+            Ok(Filled {
+                full: 0,
+                partial: 2
+            })
+        );
+        assert_eq!(decode_to_slices("", &mut []), Ok(Filled::default()));
+
+        assert_eq!(
+            decode_to_slices("0102030405", &mut [&mut a, &mut c]),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            decode_to_slices("0102x3", &mut [&mut a, &mut c]),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 4 })
+        );
+        assert_eq!(
+            decode_to_slices("010", &mut [&mut a, &mut c]),
+            Err(FromHexError::OddLength)
+        );
+    }
+
+    #[test]
+    fn test_decode_to_slice_prefix() {
+        let mut out = [0; 4];
+        assert_eq!(decode_to_slice_prefix("0102", &mut out), Ok(2));
+        assert_eq!(out, [1, 2, 0, 0]);
+        assert_eq!(decode_to_slice_prefix("01020304", &mut out), Ok(4));
+        assert_eq!(decode_to_slice_prefix("", &mut out), Ok(0));
+
+        assert_eq!(
+            decode_to_slice_prefix("0102030405", &mut out),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            decode_to_slice_prefix("012", &mut out),
+            Err(FromHexError::OddLength)
+        );
+        assert_eq!(
+            decode_to_slice_prefix("0g", &mut out),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 1 })
+        );
+    }
+
     #[test]
     #[cfg(feature = "alloc")]
     fn test_encode() {
         assert_eq!(encode("foobar"), "666f6f626172");
+        assert_eq!(encode_case("foobar", Case::Lower), "666f6f626172");
+        assert_eq!(encode_case("foobar", Case::Upper), "666F6F626172");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_encode_lengths() {
+        // every length goes through the unrolled, vector and scalar paths
+        let data: Vec<u8> = (0..200).map(|i| (i * 37) as u8).collect();
+        for len in 0..data.len() {
+            let expected: String = BytesToHexChars::new(&data[..len], Case::Lower).collect();
+            let hex = encode(&data[..len]);
+            assert_eq!(hex, expected);
+            assert!(hex.capacity() >= 2 * len);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_encode_shared() {
+        assert_eq!(&*encode_arc("foobar"), "666f6f626172");
+        assert_eq!(&*encode_arc(""), "");
+        assert_eq!(&*encode_rc([0xab; 100]), "ab".repeat(100));
+        assert_eq!(&*encode_rc(""), "");
+    }
+
+    #[test]
+    fn test_unchecked() {
+        let mut data = [0; 200];
+        for (i, byte) in data.iter_mut().enumerate() {
+            *byte = (i * 37) as u8;
+        }
+        for len in 0..data.len() {
+            let mut hex = [0; 400];
+            let mut expected = [0; 400];
+            encode_to_slice(&data[..len], &mut expected[..2 * len]).unwrap();
+            // Saftey: the lengths match
+            unsafe { encode_to_slice_unchecked(&data[..len], &mut hex[..2 * len]) };
+            assert_eq!(&hex[..2 * len], &expected[..2 * len]);
+
+            let mut out = [0; 200];
+            // Saftey: the lengths match
+            unsafe { decode_to_slice_unchecked(&hex[..2 * len], &mut out[..len]) };
+            assert_eq!(&out[..len], &data[..len]);
+        }
+    }
+
+    #[test]
+    fn test_encode_to_slice_case() {
+        let mut out = [0; 4];
+        for (case, expected) in [(Case::Lower, "abcd"), (Case::Upper, "ABCD")] {
+            assert_eq!(
+                encode_to_slice_case([0xab, 0xcd], &mut out, case).map(|s| &*s),
+                Ok(expected)
+            );
+        }
+        assert_eq!(
+            encode_to_slice_case([0xab], &mut out, Case::Upper),
+            Err(FromHexError::InvalidStringLength)
+        );
     }
 
     #[test]
@@ -556,6 +1899,30 @@ mod test {
         assert_eq!(Vec::from_hex(b"666F6F626172").unwrap(), b"foobar");
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_decode_lengths() {
+        // every length goes through the unrolled, vector and scalar paths
+        let data: Vec<u8> = (0..200).map(|i| (i * 37) as u8).collect();
+        for len in 0..data.len() {
+            let mut hex = encode(&data[..len]).into_bytes();
+            let out = Vec::from_hex(&hex).unwrap();
+            assert_eq!(out, &data[..len]);
+            assert!(out.capacity() >= len);
+
+            if len > 0 {
+                hex[2 * len - 1] = b'x';
+                assert_eq!(
+                    Vec::from_hex(&hex),
+                    Err(FromHexError::InvalidHexCharacter {
+                        c: 'x',
+                        index: 2 * len - 1
+                    })
+                );
+            }
+        }
+    }
+
     #[test]
     #[cfg(feature = "alloc")]
     pub fn test_invalid_length() {
@@ -603,6 +1970,146 @@ mod test {
         );
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_encode_append() {
+        let mut out = b"0x".to_vec();
+        encode_append([0x01, 0xab], &mut out);
+        encode_upper_append([0xcd], &mut out);
+        encode_append([], &mut out);
+        assert_eq!(out, b"0x01abCD");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_decode_append() {
+        let mut out = vec![0xff];
+        assert_eq!(decode_append("0102", &mut out), Ok(2));
+        assert_eq!(decode_append("", &mut out), Ok(0));
+        assert_eq!(out, [0xff, 0x01, 0x02]);
+
+        assert_eq!(
+            decode_append("03xx", &mut out),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 2 })
+        );
+        assert_eq!(decode_append("030", &mut out), Err(FromHexError::OddLength));
+        assert_eq!(out, [0xff, 0x01, 0x02]);
+    }
+
+    #[test]
+    #[cfg(feature = "allocator-api")]
+    fn test_decode_in() {
+        use alloc::alloc::{AllocError, Allocator, Global, Layout};
+        use core::{cell::Cell, ptr::NonNull};
+
+        // Counts the allocations made through it.
+        struct Counting<'a>(&'a Cell<usize>);
+
+        unsafe impl Allocator for Counting<'_> {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                self.0.set(self.0.get() + 1);
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                // Saftey: The caller upholds the same contract for `Global`
+                unsafe { Global.deallocate(ptr, layout) }
+            }
+        }
+
+        let count = Cell::new(0);
+        assert_eq!(decode_in("6b697769", Counting(&count)).unwrap(), b"kiwi");
+        assert_eq!(count.get(), 1);
+
+        let mut out = Vec::new_in(Counting(&count));
+        assert_eq!(decode_append_in("0102", &mut out), Ok(2));
+        assert_eq!(
+            decode_append_in("03xx", &mut out),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 2 })
+        );
+        assert_eq!(out, [0x01, 0x02]);
+        assert!(decode_in("030", Counting(&count)).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_decode_fragments() {
+        let empty: [&str; 0] = [];
+        assert_eq!(decode_fragments(empty), Ok(vec![]));
+        assert_eq!(decode_fragments(["", "", ""]), Ok(vec![]));
+        assert_eq!(decode_fragments(["0", "", "1"]), Ok(vec![0x01]));
+        assert_eq!(
+            decode_fragments(["a", "bcd", "e", "f01"]),
+            Ok(vec![0xab, 0xcd, 0xef, 0x01])
+        );
+        assert_eq!(
+            decode_fragments([&b"01"[..], b"0", b"2"]),
+            Ok(vec![0x01, 0x02])
+        );
+
+        assert_eq!(decode_fragments(["012"]), Err(FromHexError::OddLength));
+        assert_eq!(
+            decode_fragments(["g", "0"]),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 0 })
+        );
+        assert_eq!(
+            decode_fragments(["0", "g"]),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 1 })
+        );
+        assert_eq!(
+            decode_fragments(["010", "2g"]),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 4 })
+        );
+        assert_eq!(
+            decode_fragments(["0", "", "1", "0z"]),
+            Err(FromHexError::InvalidHexCharacter { c: 'z', index: 3 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_decode_list() {
+        assert_eq!(decode_list("", b','), Ok(vec![]));
+        assert_eq!(decode_list(" \n", b' '), Ok(vec![]));
+        assert_eq!(
+            decode_list("0a,,0b", b','),
+            Ok(vec![vec![0x0a], vec![], vec![0x0b]])
+        );
+        assert_eq!(
+            decode_list(" 0a \t\n 0B\n", b'\n'),
+            Ok(vec![vec![0x0a], vec![0x0b]])
+        );
+        assert_eq!(decode_list("0a;  0b;0", b';'), Err(FromHexError::OddLength));
+        assert_eq!(
+            decode_list("0a;  0b;g0", b';'),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 8 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_to_hex_provided() {
+        struct Prefixed(u8);
+
+        impl ToHex for Prefixed {
+            fn encode_hex<T: iter::FromIterator<char>>(&self) -> T {
+                "0x".chars()
+                    .chain(BytesToHexChars::new(&[self.0], Case::Lower))
+                    .collect()
+            }
+
+            fn encode_hex_upper<T: iter::FromIterator<char>>(&self) -> T {
+                "0x".chars()
+                    .chain(BytesToHexChars::new(&[self.0], Case::Upper))
+                    .collect()
+            }
+        }
+
+        // the provided methods go through the custom implementation
+        assert_eq!(Prefixed(0xab).encode_hex_string(Case::Upper), "0xAB");
+        assert_eq!(Prefixed(0xab).encode_hex_bytes(Case::Lower), b"0xab");
+    }
+
     #[test]
     #[cfg(feature = "alloc")]
     fn test_to_hex() {
@@ -615,5 +2122,79 @@ mod test {
             [0x66, 0x6f, 0x6f, 0x62, 0x61, 0x72].encode_hex_upper::<String>(),
             "666F6F626172".to_string(),
         );
+
+        assert_eq!(b"foobar".encode_hex_string(Case::Lower), "666f6f626172");
+        assert_eq!(b"foobar".encode_hex_string(Case::Upper), "666F6F626172");
+        assert_eq!(b"foobar".encode_hex_bytes(Case::Upper), b"666F6F626172");
+    }
+
+    #[test]
+    fn test_decode_iter() {
+        let mut bytes = decode_iter("0001fEff").unwrap();
+        assert_eq!(bytes.next_back(), Some(0xff));
+        assert_eq!(bytes.next(), Some(0x00));
+        assert!(bytes.eq([0x01, 0xfe]));
+        assert_eq!(decode_iter(b"").unwrap().len(), 0);
+        assert_eq!(
+            decode_iter("0g").unwrap_err(),
+            FromHexError::InvalidHexCharacter { c: 'g', index: 1 }
+        );
+    }
+
+    #[test]
+    fn test_encode_iter_to_slice() {
+        let mut output = [0; 6];
+        assert_eq!(
+            encode_iter_to_slice(b"kiw".iter().copied(), &mut output).map(|s| &*s),
+            Ok("6b6977")
+        );
+        assert_eq!(encode_iter_to_slice(None, &mut output).map(|s| &*s), Ok(""));
+        assert_eq!(
+            encode_iter_to_slice(b"kiwi".iter().copied(), &mut output),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_encode_iter() {
+        assert_eq!(encode_iter(core::iter::empty()), "");
+        assert_eq!(encode_iter(vec![0xff, 0x00]), "ff00");
+        assert_eq!(encode_iter((0..3).map(|_| 0xab)), "ababab");
+    }
+
+    #[test]
+    fn test_bytes_to_hex_chars() {
+        let mut chars = BytesToHexChars::new(&[0x12, 0x34, 0xab], Case::Lower);
+        assert_eq!(chars.len(), 6);
+        assert_eq!(chars.next(), Some('1'));
+        assert_eq!(chars.next_back(), Some('b'));
+        assert_eq!(chars.len(), 4);
+        assert_eq!(chars.next_back(), Some('a'));
+        assert_eq!(chars.next_back(), Some('4'));
+        assert_eq!(chars.next_back(), Some('3'));
+        assert_eq!(chars.next_back(), Some('2'));
+        assert_eq!(chars.len(), 0);
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.next_back(), None);
+
+        let mut chars = BytesToHexChars::new(&[0xcd], Case::Upper);
+        assert_eq!(chars.next_back(), Some('D'));
+        assert_eq!(chars.next(), Some('C'));
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_bytes_to_hex_chars_fold() {
+        let data: Vec<u8> = (0..200).map(|i| (i * 37) as u8).collect();
+        let hex = encode(&data);
+        let mut chars = BytesToHexChars::new(&data, Case::Lower);
+        assert_eq!(chars.clone().collect::<String>(), hex);
+
+        // with a digit left over at either end
+        chars.next();
+        chars.next_back();
+        assert_eq!(chars.collect::<String>(), &hex[1..hex.len() - 1]);
     }
 }
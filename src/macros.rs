@@ -0,0 +1,356 @@
+use core::fmt;
+
+/// Includes a hex text file as a `&'static [u8]`.
+///
+/// The file is located relative to the current file (similarly to how
+/// [`include_str!`] works) and decoded at compile time. Whitespace is
+/// ignored, as is everything from a `#` or `//` to the end of the line, so
+/// the file can be laid out and commented freely. Odd numbers of digits and
+/// invalid characters are reported as compile errors.
+///
+/// # Example
+///
+/// ```
+/// // tests/data/hello.hex:
+/// //
+/// //     # sample fixture
+/// //     48656c6c 6f20776f
+/// //     726C6421 // "Hello world!"
+/// static HELLO: &[u8] = hex::include_hex!("../tests/data/hello.hex");
+///
+/// assert_eq!(HELLO, b"Hello world!");
+/// ```
+#[macro_export]
+macro_rules! include_hex {
+    ($path:expr $(,)?) => {{
+        const SOURCE: &str = ::core::include_str!($path);
+        const LEN: usize = $crate::__private::decoded_len(SOURCE);
+        const BYTES: [u8; LEN] = $crate::__private::decode_source::<LEN>(SOURCE);
+        &BYTES as &'static [u8]
+    }};
+}
+
+/// Asserts that two byte sequences are equal, showing them as hex if not.
+///
+/// Both sides may be anything implementing `AsRef<[u8]>`. On failure, the
+/// panic message contains both sides as aligned hex rows (around the first
+/// difference, for long inputs) with the first differing offset marked,
+/// instead of the hard to read `Debug` output of `Vec<u8>`.
+///
+/// Like [`assert_eq!`], a custom message can be added after the operands.
+///
+/// # Example
+///
+/// ```should_panic
+/// hex::assert_eq_hex!(b"Hello world!", b"Hello World!", "greeting mismatch");
+/// // panics with:
+/// //
+/// // assertion `left == right` failed: greeting mismatch
+/// // first difference at offset 0x6
+/// // 00000000   left: 48 65 6c 6c 6f 20 77 6f 72 6c 64 21
+/// //           right: 48 65 6c 6c 6f 20 57 6f 72 6c 64 21
+/// //                                    ^^
+/// ```
+#[macro_export]
+macro_rules! assert_eq_hex {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let left: &[u8] = ::core::convert::AsRef::as_ref(left);
+                let right: &[u8] = ::core::convert::AsRef::as_ref(right);
+                if left != right {
+                    $crate::__private::assert_failed("==", left, right, ::core::option::Option::None);
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let left: &[u8] = ::core::convert::AsRef::as_ref(left);
+                let right: &[u8] = ::core::convert::AsRef::as_ref(right);
+                if left != right {
+                    $crate::__private::assert_failed(
+                        "==",
+                        left,
+                        right,
+                        ::core::option::Option::Some(::core::format_args!($($arg)+)),
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Asserts that two byte sequences are not equal, showing them as hex if
+/// they are.
+///
+/// This is the counterpart of [`assert_eq_hex!`].
+///
+/// # Example
+///
+/// ```
+/// hex::assert_ne_hex!(b"kiwi", [0x6b, 0x69, 0x77, 0x6a]);
+/// ```
+#[macro_export]
+macro_rules! assert_ne_hex {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let left: &[u8] = ::core::convert::AsRef::as_ref(left);
+                let right: &[u8] = ::core::convert::AsRef::as_ref(right);
+                if left == right {
+                    $crate::__private::assert_failed("!=", left, right, ::core::option::Option::None);
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let left: &[u8] = ::core::convert::AsRef::as_ref(left);
+                let right: &[u8] = ::core::convert::AsRef::as_ref(right);
+                if left == right {
+                    $crate::__private::assert_failed(
+                        "!=",
+                        left,
+                        right,
+                        ::core::option::Option::Some(::core::format_args!($($arg)+)),
+                    );
+                }
+            }
+        }
+    };
+}
+
+// The number of bytes per row and the number of rows shown before and after
+// the first difference in assertion messages.
+const ROW_LEN: usize = 16;
+const CONTEXT_ROWS: usize = 3;
+
+#[doc(hidden)]
+#[cold]
+#[track_caller]
+pub fn assert_failed(op: &str, left: &[u8], right: &[u8], args: Option<fmt::Arguments>) -> ! {
+    let comparison = Comparison { left, right };
+    match args {
+        Some(args) => panic!(
+            "assertion `left {} right` failed: {}\n{}",
+            op, args, comparison
+        ),
+        None => panic!("assertion `left {} right` failed\n{}", op, comparison),
+    }
+}
+
+// Renders two byte sequences as aligned hex rows, marking the first
+// difference.
+struct Comparison<'a> {
+    left: &'a [u8],
+    right: &'a [u8],
+}
+
+impl Comparison<'_> {
+    fn write_row(f: &mut fmt::Formatter, bytes: &[u8], row: usize) -> fmt::Result {
+        let start = (row * ROW_LEN).min(bytes.len());
+        let end = (start + ROW_LEN).min(bytes.len());
+        for (i, byte) in bytes[start..end].iter().enumerate() {
+            if i != 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{:02x}", byte)?;
+        }
+        f.write_str("\n")
+    }
+}
+
+impl fmt::Display for Comparison<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (left, right) = (self.left, self.right);
+        let first = left
+            .iter()
+            .zip(right)
+            .position(|(l, r)| l != r)
+            .unwrap_or_else(|| left.len().min(right.len()));
+
+        if left == right {
+            writeln!(f, "both sides are {} bytes long", left.len())?;
+        } else {
+            writeln!(f, "first difference at offset {:#x}", first)?;
+        }
+
+        let rows = (left.len().max(right.len()) + ROW_LEN - 1) / ROW_LEN;
+        let marked = first / ROW_LEN;
+        let start = marked.saturating_sub(CONTEXT_ROWS);
+        let end = rows.min(marked + CONTEXT_ROWS + 1);
+
+        if start > 0 {
+            writeln!(f, "...")?;
+        }
+        for row in start..end {
+            write!(f, "{:08x}   left: ", row * ROW_LEN)?;
+            Comparison::write_row(f, left, row)?;
+            write!(f, "          right: ")?;
+            Comparison::write_row(f, right, row)?;
+            if row == marked && left != right {
+                let column = 17 + (first % ROW_LEN) * 3;
+                writeln!(f, "{:column$}^^", "", column = column)?;
+            }
+        }
+        if end < rows {
+            writeln!(f, "...")?;
+        }
+        Ok(())
+    }
+}
+
+const fn digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+// Returns the index of the next hex digit at or after `i`, skipping
+// whitespace and comments.
+const fn skip_ignored(src: &[u8], mut i: usize) -> usize {
+    while i < src.len() {
+        let c = src[i];
+        if c == b'#' || (c == b'/' && i + 1 < src.len() && src[i + 1] == b'/') {
+            while i < src.len() && src[i] != b'\n' {
+                i += 1;
+            }
+        } else if c.is_ascii_whitespace() {
+            i += 1;
+        } else {
+            break;
+        }
+    }
+    i
+}
+
+/// Counts the bytes encoded in `src`, panicking (and therefore failing the
+/// build when evaluated in a const context) if the source is not valid.
+pub const fn decoded_len(src: &str) -> usize {
+    let src = src.as_bytes();
+    let mut digits = 0;
+    let mut i = skip_ignored(src, 0);
+    while i < src.len() {
+        if digit(src[i]).is_none() {
+            panic!("include_hex!: invalid hex character");
+        }
+        digits += 1;
+        i = skip_ignored(src, i + 1);
+    }
+    if digits % 2 != 0 {
+        panic!("include_hex!: odd number of hex digits");
+    }
+    digits / 2
+}
+
+/// Decodes `src` into an array, which has to be [`decoded_len`] bytes long.
+pub const fn decode_source<const N: usize>(src: &str) -> [u8; N] {
+    let src = src.as_bytes();
+    let mut out = [0_u8; N];
+    let mut n = 0;
+    let mut i = skip_ignored(src, 0);
+    while i < src.len() {
+        let high = match digit(src[i]) {
+            Some(high) => high,
+            None => panic!("include_hex!: invalid hex character"),
+        };
+        i = skip_ignored(src, i + 1);
+        if i >= src.len() {
+            panic!("include_hex!: odd number of hex digits");
+        }
+        let low = match digit(src[i]) {
+            Some(low) => low,
+            None => panic!("include_hex!: invalid hex character"),
+        };
+        i = skip_ignored(src, i + 1);
+        out[n] = (high << 4) | low;
+        n += 1;
+    }
+    if n != N {
+        panic!("include_hex!: length mismatch");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use alloc::string::ToString;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_comparison() {
+        let left = [0_u8; 20];
+        let mut right = [0_u8; 21];
+        right[17] = 0xff;
+        assert_eq!(
+            Comparison {
+                left: &left,
+                right: &right
+            }
+            .to_string(),
+            "first difference at offset 0x11\n\
+             00000000   left: 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00\n\
+             \x20         right: 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00\n\
+             00000010   left: 00 00 00 00\n\
+             \x20         right: 00 ff 00 00 00\n\
+             \x20                   ^^\n"
+        );
+
+        let long = [0_u8; 16 * 10];
+        let rendered = Comparison {
+            left: &long,
+            right: &long[..16 * 9],
+        }
+        .to_string();
+        assert!(rendered.starts_with("first difference at offset 0x90\n...\n00000060"));
+        assert!(rendered.ends_with("00000090   left: 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00\n          right: \n                 ^^\n"));
+    }
+
+    #[test]
+    fn test_assert_eq_hex() {
+        crate::assert_eq_hex!(b"kiwi", [0x6b, 0x69, 0x77, 0x69]);
+        crate::assert_ne_hex!(b"kiwi", b"kiwis", "{} bytes", 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion `left == right` failed: custom message\n\
+                               first difference at offset 0x3\n")]
+    fn test_assert_eq_hex_failed() {
+        crate::assert_eq_hex!(b"kiwi", b"kiwa", "custom {}", "message");
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion `left != right` failed\nboth sides are 4 bytes long\n")]
+    fn test_assert_ne_hex_failed() {
+        crate::assert_ne_hex!(b"kiwi", b"kiwi");
+    }
+
+    #[test]
+    fn test_decode_source() {
+        const SOURCE: &str = "# header\nDE ad\n// comment\n\tbe\r\nef // trailing\n0 1";
+        assert_eq!(decoded_len(SOURCE), 5);
+        assert_eq!(decode_source::<5>(SOURCE), [0xde, 0xad, 0xbe, 0xef, 0x01]);
+        assert_eq!(decoded_len(""), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "odd number of hex digits")]
+    fn test_decoded_len_odd() {
+        decoded_len("abc");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid hex character")]
+    fn test_decoded_len_invalid() {
+        decoded_len("0x12");
+    }
+}
@@ -0,0 +1,219 @@
+//! Decoding of huge hex files through memory maps.
+//!
+//! Unlike [`crate::fs::decode_file`], these functions don't read the hex text
+//! into memory, but map the file and decode it in place, optionally using
+//! several threads. Trailing ASCII whitespace, such as a final newline, is
+//! ignored; any other non-digit fails with [`io::ErrorKind::InvalidData`],
+//! wrapping a [`FromHexError`] whose index is the position in the file.
+//!
+//! # Safety
+//!
+//! A memory-mapped file must not be modified or truncated while it is
+//! mapped, by this or any other process, as that is undefined behavior. All
+//! functions in this module are `unsafe` for that reason.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::fs::File;
+//!
+//! // Safety: nothing else writes to the dump while it is decoded
+//! let written = unsafe { hex::mmap::decode_file_to("dump.hex", File::create("dump.bin")?)? };
+//! println!("decoded {} bytes", written);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+    thread,
+};
+
+use memmap2::Mmap;
+
+use crate::{decode_to_slice, decoded_len, FromHexError};
+
+// Number of bytes decoded at once by `decode_file_to`.
+const WINDOW_LEN: usize = 1 << 16;
+
+// Wraps a decoding error into an `io::Error`.
+fn invalid_data(err: FromHexError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+// Maps the file at `path`.
+unsafe fn map<P: AsRef<Path>>(path: P) -> io::Result<Mmap> {
+    Mmap::map(&File::open(path)?)
+}
+
+// Strips trailing whitespace and checks the length of the digits.
+fn digits(map: &[u8]) -> io::Result<&[u8]> {
+    let len = map.len()
+        - map
+            .iter()
+            .rev()
+            .take_while(|b| b.is_ascii_whitespace())
+            .count();
+    decoded_len(len).map_err(invalid_data)?;
+    Ok(&map[..len])
+}
+
+// Decodes `hex`, which starts at `offset` in the file.
+fn decode_window(hex: &[u8], out: &mut [u8], offset: usize) -> io::Result<()> {
+    decode_to_slice(hex, out).map_err(|err| invalid_data(err.offset(offset)))
+}
+
+/// Decodes the hex file at `path`.
+///
+/// # Safety
+///
+/// The file must not be modified while it is decoded, see the
+/// [module documentation](self).
+pub unsafe fn decode_file<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
+    decode_file_parallel(path, 1)
+}
+
+/// Decodes the hex file at `path`, splitting the work between `threads`
+/// threads.
+///
+/// If `threads` is 0, the available parallelism of the system is used. If
+/// the file contains several errors, the one closest to the start is
+/// returned.
+///
+/// # Safety
+///
+/// The file must not be modified while it is decoded, see the
+/// [module documentation](self).
+///
+/// # Example
+///
+/// ```no_run
+/// // Safety: nothing else writes to the dump while it is decoded
+/// let bytes = unsafe { hex::mmap::decode_file_parallel("dump.hex", 0)? };
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub unsafe fn decode_file_parallel<P: AsRef<Path>>(path: P, threads: usize) -> io::Result<Vec<u8>> {
+    let map = map(path)?;
+    let hex = digits(&map)?;
+    let mut out = vec![0; hex.len() / 2];
+    let threads = match threads {
+        0 => thread::available_parallelism().map_or(1, |threads| threads.get()),
+        threads => threads,
+    };
+    if threads == 1 || out.len() < 2 * WINDOW_LEN {
+        decode_window(hex, &mut out, 0)?;
+        return Ok(out);
+    }
+
+    let chunk_len = (out.len() + threads - 1) / threads;
+    thread::scope(|scope| {
+        let handles: Vec<_> = out
+            .chunks_mut(chunk_len)
+            .enumerate()
+            .map(|(i, out)| {
+                let offset = 2 * i * chunk_len;
+                let hex = &hex[offset..offset + 2 * out.len()];
+                scope.spawn(move || decode_window(hex, out, offset))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .try_for_each(|handle| handle.join().unwrap())
+    })?;
+    Ok(out)
+}
+
+/// Decodes the hex file at `path` into `out`, returning the number of bytes
+/// written.
+///
+/// The file is decoded in small windows, so neither the hex text nor the
+/// decoded bytes are ever held in memory as a whole. If an error is found,
+/// the bytes decoded before it have already been written.
+///
+/// # Safety
+///
+/// The file must not be modified while it is decoded, see the
+/// [module documentation](self).
+pub unsafe fn decode_file_to<P: AsRef<Path>, W: Write>(path: P, mut out: W) -> io::Result<u64> {
+    let map = map(path)?;
+    let hex = digits(&map)?;
+    let mut buf = vec![0; WINDOW_LEN.min(hex.len() / 2)];
+    for (i, window) in hex.chunks(2 * WINDOW_LEN).enumerate() {
+        let buf = &mut buf[..window.len() / 2];
+        decode_window(window, buf, 2 * i * WINDOW_LEN)?;
+        out.write_all(buf)?;
+    }
+    out.flush()?;
+    Ok(hex.len() as u64 / 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::{env, fs, path::PathBuf};
+
+    fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+        let path = env::temp_dir().join(format!("hex-mmap-{}-{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn error(err: io::Error) -> FromHexError {
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        *err.into_inner().unwrap().downcast().unwrap()
+    }
+
+    #[test]
+    fn test_decode() {
+        let data: Vec<u8> = (0..5 * WINDOW_LEN + 3).map(|i| (i * 7) as u8).collect();
+        let path = temp_file("decode", format!("{}\n", crate::encode(&data)).as_bytes());
+        unsafe {
+            assert_eq!(decode_file(&path).unwrap(), data);
+            assert_eq!(decode_file_parallel(&path, 0).unwrap(), data);
+            assert_eq!(decode_file_parallel(&path, 3).unwrap(), data);
+
+            let mut out = Vec::new();
+            assert_eq!(decode_file_to(&path, &mut out).unwrap(), data.len() as u64);
+            assert_eq!(out, data);
+        }
+        fs::remove_file(&path).unwrap();
+
+        let path = temp_file("empty", b"");
+        unsafe {
+            assert_eq!(decode_file(&path).unwrap(), b"");
+            assert_eq!(decode_file_to(&path, Vec::new()).unwrap(), 0);
+        }
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut hex = crate::encode(vec![0; 3 * WINDOW_LEN]).into_bytes();
+        hex[5 * WINDOW_LEN + 1] = b'x';
+        hex[5 * WINDOW_LEN + 5] = b'y';
+        let path = temp_file("errors", &hex);
+        let expected = FromHexError::InvalidHexCharacter {
+            c: 'x',
+            index: 5 * WINDOW_LEN + 1,
+        };
+        unsafe {
+            assert_eq!(error(decode_file(&path).unwrap_err()), expected);
+            assert_eq!(error(decode_file_parallel(&path, 4).unwrap_err()), expected);
+            assert_eq!(
+                error(decode_file_to(&path, io::sink()).unwrap_err()),
+                expected
+            );
+        }
+        fs::remove_file(&path).unwrap();
+
+        let path = temp_file("odd", b"abc\n");
+        unsafe {
+            assert_eq!(
+                error(decode_file(&path).unwrap_err()),
+                FromHexError::OddLength
+            );
+        }
+        fs::remove_file(&path).unwrap();
+    }
+}
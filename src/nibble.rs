@@ -0,0 +1,417 @@
+//! Nibble-level access to bytes.
+//!
+//! A nibble is half a byte, i.e. a single hex digit. Splitting bytes into
+//! nibbles (and assembling them back) is needed for trie keys such as the
+//! paths of Ethereum's Merkle Patricia Trie, for 4-bit codecs, and is handy
+//! for debugging. Abbreviated hashes with an odd number of digits, like the
+//! short object IDs of git, are matched with [`starts_with_hex_prefix`].
+//!
+//! # Example
+//!
+//! ```
+//! use hex::nibble::nibbles;
+//!
+//! let path: Vec<u8> = nibbles(&[0x12, 0xab]).collect();
+//! assert_eq!(path, [0x1, 0x2, 0xa, 0xb]);
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! use hex::nibble::{NibbleBuilder, Trailing};
+//!
+//! let bytes = path.into_iter().collect::<NibbleBuilder>().finish(Trailing::Reject)?;
+//! assert_eq!(bytes, [0x12, 0xab]);
+//! # }
+//! # Ok::<(), hex::FromHexError>(())
+//! ```
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+#[cfg(feature = "alloc")]
+use crate::FromHexError;
+use crate::DECODE_TABLE;
+
+/// Returns an iterator over the nibbles of `bytes`, most significant nibble
+/// of each byte first.
+///
+/// # Example
+///
+/// ```
+/// let mut nibbles = hex::nibble::nibbles(b"\xf0");
+/// assert_eq!(nibbles.next(), Some(0xf));
+/// assert_eq!(nibbles.next(), Some(0x0));
+/// assert_eq!(nibbles.next(), None);
+/// ```
+pub fn nibbles(bytes: &[u8]) -> Nibbles<'_> {
+    Nibbles {
+        bytes,
+        front: 0,
+        back: bytes.len() * 2,
+    }
+}
+
+/// An iterator over the nibbles of a byte slice.
+///
+/// This struct is created by [`nibbles`].
+#[derive(Debug, Clone)]
+pub struct Nibbles<'a> {
+    bytes: &'a [u8],
+    // The range of nibble indices which haven't been yielded yet.
+    front: usize,
+    back: usize,
+}
+
+impl<'a> Nibbles<'a> {
+    #[inline]
+    fn get(&self, index: usize) -> u8 {
+        let byte = self.bytes[index / 2];
+        if index % 2 == 0 {
+            byte >> 4
+        } else {
+            byte & 0x0f
+        }
+    }
+}
+
+impl<'a> Iterator for Nibbles<'a> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.get(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.front += n.min(self.back - self.front);
+        self.next()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Nibbles<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.get(self.back))
+    }
+}
+
+impl<'a> ExactSizeIterator for Nibbles<'a> {}
+
+impl<'a> FusedIterator for Nibbles<'a> {}
+
+/// Returns `true` if the nibbles of `bytes` start with the hex digits of
+/// `prefix`, which may have an odd number of digits.
+///
+/// This matches abbreviated hashes, like the short object IDs of git,
+/// against raw candidates without encoding them. The case of the digits
+/// doesn't matter, and a prefix containing anything but hex digits never
+/// matches.
+///
+/// # Example
+///
+/// ```
+/// use hex::nibble::starts_with_hex_prefix;
+///
+/// let id = [0xde, 0xad, 0xbe, 0xef];
+/// assert!(starts_with_hex_prefix(&id, "dEaDb"));
+/// assert!(starts_with_hex_prefix(&id, ""));
+/// assert!(!starts_with_hex_prefix(&id, "deab"));
+/// assert!(!starts_with_hex_prefix(&id, "deadbeef0"));
+/// ```
+pub fn starts_with_hex_prefix<T: AsRef<[u8]>>(bytes: &[u8], prefix: T) -> bool {
+    let prefix = prefix.as_ref();
+    prefix.len() <= bytes.len() * 2
+        && prefix
+            .iter()
+            .map(|&c| DECODE_TABLE[c as usize])
+            .eq(nibbles(bytes).take(prefix.len()))
+}
+
+/// Returns `true` if the nibbles of `bytes` start with the first
+/// `nibble_len` nibbles of `prefix`.
+///
+/// This is the counterpart of [`starts_with_hex_prefix`] for prefixes
+/// decoded once with [`decode_prefix`], so many candidates can be matched
+/// without looking at the digits again.
+///
+/// # Panics
+///
+/// Panics if `prefix` is shorter than `nibble_len` nibbles.
+///
+/// # Example
+///
+/// ```
+/// use hex::nibble::starts_with_nibbles;
+///
+/// let id = [0xde, 0xad, 0xbe, 0xef];
+/// assert!(starts_with_nibbles(&id, &[0xde, 0xa0], 3));
+/// assert!(!starts_with_nibbles(&id, &[0xde, 0xa0], 4));
+/// ```
+#[must_use]
+pub fn starts_with_nibbles(bytes: &[u8], prefix: &[u8], nibble_len: usize) -> bool {
+    assert!(
+        nibble_len <= prefix.len() * 2,
+        "prefix of {} bytes is shorter than {} nibbles",
+        prefix.len(),
+        nibble_len
+    );
+    let full = nibble_len / 2;
+    if nibble_len > bytes.len() * 2 || bytes[..full] != prefix[..full] {
+        return false;
+    }
+    nibble_len % 2 == 0 || bytes[full] >> 4 == prefix[full] >> 4
+}
+
+/// Decodes an abbreviated hex prefix, which may have an odd number of
+/// digits, returning the bytes and the number of nibbles.
+///
+/// A trailing half byte is stored in the high nibble of the last byte, with
+/// the low nibble set to zero. Use [`starts_with_nibbles`] to match the
+/// result against raw candidates.
+///
+/// # Example
+///
+/// ```
+/// let (bytes, len) = hex::nibble::decode_prefix("deadb")?;
+/// assert_eq!(bytes, [0xde, 0xad, 0xb0]);
+/// assert_eq!(len, 5);
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_prefix<T: AsRef<[u8]>>(hex: T) -> Result<(Vec<u8>, usize), FromHexError> {
+    let hex = hex.as_ref();
+    let mut bytes = Vec::with_capacity((hex.len() + 1) / 2);
+    let pairs = hex.chunks_exact(2);
+    let remainder = pairs.remainder();
+    for (i, pair) in pairs.enumerate() {
+        bytes.push(crate::val(pair, 2 * i)?);
+    }
+    if let [c] = *remainder {
+        let nibble = DECODE_TABLE[c as usize];
+        if nibble == u8::MAX {
+            return Err(FromHexError::InvalidHexCharacter {
+                c: c as char,
+                index: hex.len() - 1,
+            });
+        }
+        bytes.push(nibble << 4);
+    }
+    Ok((bytes, hex.len()))
+}
+
+/// What to do with a half byte left over at the end of a sequence of nibbles.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Trailing {
+    /// Fail with [`FromHexError::OddLength`].
+    #[default]
+    Reject,
+    /// Fill the low nibble of the last byte with zero, i.e. `[0xa]` becomes
+    /// `[0xa0]`.
+    ZeroFill,
+    /// Drop the half byte.
+    Discard,
+}
+
+/// Assembles bytes from nibbles.
+///
+/// Nibbles are added with [`push`](Self::push), or by using the builder as
+/// an [`Extend`] target or collecting into it. The first nibble of every
+/// pair becomes the most significant half of a byte.
+///
+/// # Example
+///
+/// ```
+/// use hex::nibble::{NibbleBuilder, Trailing};
+///
+/// let mut builder = NibbleBuilder::new();
+/// builder.extend([0x1, 0x2, 0x3]);
+/// assert_eq!(builder.len(), 3);
+/// assert_eq!(builder.finish(Trailing::ZeroFill), Ok(vec![0x12, 0x30]));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NibbleBuilder {
+    bytes: Vec<u8>,
+    half: Option<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl NibbleBuilder {
+    /// Creates an empty builder.
+    #[must_use]
+    pub fn new() -> Self {
+        NibbleBuilder::default()
+    }
+
+    /// Appends a nibble.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nibble` is larger than `0xf`.
+    pub fn push(&mut self, nibble: u8) {
+        assert!(nibble <= 0xf, "nibble out of range: {:#x}", nibble);
+        match self.half.take() {
+            Some(high) => self.bytes.push((high << 4) | nibble),
+            None => self.half = Some(nibble),
+        }
+    }
+
+    /// Returns the number of nibbles added so far.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.bytes.len() * 2 + usize::from(self.half.is_some())
+    }
+
+    /// Returns `true` if no nibbles were added yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the assembled bytes, handling a left over half byte according
+    /// to `trailing`.
+    pub fn finish(mut self, trailing: Trailing) -> Result<Vec<u8>, FromHexError> {
+        match (self.half, trailing) {
+            (None, _) | (Some(_), Trailing::Discard) => {}
+            (Some(high), Trailing::ZeroFill) => self.bytes.push(high << 4),
+            (Some(_), Trailing::Reject) => return Err(FromHexError::OddLength),
+        }
+        Ok(self.bytes)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Extend<u8> for NibbleBuilder {
+    fn extend<T: IntoIterator<Item = u8>>(&mut self, iter: T) {
+        let iter = iter.into_iter();
+        self.bytes.reserve(iter.size_hint().0 / 2);
+        for nibble in iter {
+            self.push(nibble);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::iter::FromIterator<u8> for NibbleBuilder {
+    fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
+        let mut builder = NibbleBuilder::new();
+        builder.extend(iter);
+        builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_nibbles() {
+        let mut iter = nibbles(&[0x12, 0x34, 0x56]);
+        assert_eq!(iter.len(), 6);
+        assert_eq!(iter.next(), Some(0x1));
+        assert_eq!(iter.next_back(), Some(0x6));
+        assert_eq!(iter.next_back(), Some(0x5));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.nth(1), Some(0x3));
+        assert_eq!(iter.next(), Some(0x4));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        assert!(nibbles(&[0xab]).rev().eq([0xb, 0xa]));
+        assert_eq!(nibbles(&[0xab]).nth(5), None);
+        assert_eq!(nibbles(&[]).next(), None);
+    }
+
+    #[test]
+    fn test_starts_with_prefix() {
+        let id = [0x01, 0xab, 0xf0];
+        for (prefix, expected) in [
+            ("", true),
+            ("0", true),
+            ("01", true),
+            ("01A", true),
+            ("01aBf0", true),
+            ("1", false),
+            ("01ac", false),
+            ("01abf00", false),
+            ("0g", false),
+        ] {
+            assert_eq!(starts_with_hex_prefix(&id, prefix), expected, "{}", prefix);
+        }
+
+        assert!(starts_with_nibbles(&id, &[], 0));
+        assert!(starts_with_nibbles(&id, &[0x01, 0xaf], 3));
+        assert!(!starts_with_nibbles(&id, &[0x01, 0xaf], 4));
+        assert!(starts_with_nibbles(&id, &id, 6));
+        assert!(!starts_with_nibbles(&id[..1], &id, 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "prefix of 1 bytes is shorter than 3 nibbles")]
+    fn test_starts_with_nibbles_short_prefix() {
+        let _ = starts_with_nibbles(&[0; 4], &[0], 3);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_decode_prefix() {
+        use alloc::vec;
+
+        assert_eq!(decode_prefix(""), Ok((vec![], 0)));
+        assert_eq!(decode_prefix("a"), Ok((vec![0xa0], 1)));
+        assert_eq!(decode_prefix("01AB"), Ok((vec![0x01, 0xab], 4)));
+        assert_eq!(
+            decode_prefix("01g"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 2 })
+        );
+        assert_eq!(
+            decode_prefix("0g1"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 1 })
+        );
+
+        let (prefix, len) = decode_prefix("deadb").unwrap();
+        assert!(starts_with_nibbles(&[0xde, 0xad, 0xbe, 0xef], &prefix, len));
+        assert!(!starts_with_nibbles(&[0xde, 0xad, 0xce], &prefix, len));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_builder() {
+        use alloc::vec;
+
+        let bytes = [0xde, 0xad, 0xbe, 0xef];
+        let builder: NibbleBuilder = nibbles(&bytes).collect();
+        assert_eq!(builder.len(), 8);
+        assert_eq!(builder.finish(Trailing::Reject), Ok(bytes.to_vec()));
+
+        let odd: NibbleBuilder = [0x1, 0x2, 0x3].iter().copied().collect();
+        assert_eq!(
+            odd.clone().finish(Trailing::Reject),
+            Err(FromHexError::OddLength)
+        );
+        assert_eq!(odd.clone().finish(Trailing::ZeroFill), Ok(vec![0x12, 0x30]));
+        assert_eq!(odd.finish(Trailing::Discard), Ok(vec![0x12]));
+
+        assert!(NibbleBuilder::new().is_empty());
+        assert_eq!(NibbleBuilder::new().finish(Trailing::Reject), Ok(vec![]));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic(expected = "nibble out of range: 0x10")]
+    fn test_builder_out_of_range() {
+        NibbleBuilder::new().push(0x10);
+    }
+}
@@ -0,0 +1,141 @@
+//! Parsers for hex fields in grammars written with the `nom` crate.
+//!
+//! The parsers work on `&str`, `&[u8]` and any other [`Input`] which can be
+//! viewed as bytes. Invalid digits and odd lengths are reported as a
+//! [`FromHexError`] through [`FromExternalError`], at the start of the hex
+//! field, like [`map_res`]. Whether running out of input is an error or
+//! [`Incomplete`] is decided by the caller, with [`Parser::parse_complete`]
+//! or [`Parser::parse`].
+//!
+//! # Example
+//!
+//! ```
+//! use nom::{character::complete::char, Parser};
+//!
+//! // A record like "<16 bit id>:<key>;"
+//! let (rest, (id, _, key, _)) = (
+//!     hex::nom::hex_bytes::<_, nom::error::Error<_>>(2),
+//!     char(':'),
+//!     hex::nom::hex_until(';'),
+//!     char(';'),
+//! )
+//!     .parse_complete("00ff:cafebabe;")?;
+//! assert_eq!(id, [0x00, 0xff]);
+//! assert_eq!(key, [0xca, 0xfe, 0xba, 0xbe]);
+//! assert_eq!(rest, "");
+//! # Ok::<(), nom::Err<nom::error::Error<&str>>>(())
+//! ```
+//!
+//! [`map_res`]: nom::combinator::map_res
+//! [`Incomplete`]: nom::Err::Incomplete
+use alloc::vec::Vec;
+
+use nom::{
+    bytes::{take, take_till},
+    error::{FromExternalError, ParseError},
+    AsBytes, Input, Parser,
+};
+
+use crate::FromHexError;
+
+/// Parses `len` bytes encoded as `2 * len` hex digits.
+///
+/// # Example
+///
+/// ```
+/// use nom::Parser;
+///
+/// let mut parser = hex::nom::hex_bytes::<_, nom::error::Error<_>>(4);
+/// let (rest, kiwi) = parser.parse_complete(&b"6b697769 and more"[..])?;
+/// assert_eq!(kiwi, b"kiwi");
+/// assert_eq!(rest, b" and more");
+/// # Ok::<(), nom::Err<nom::error::Error<&[u8]>>>(())
+/// ```
+pub fn hex_bytes<I, E>(len: usize) -> impl Parser<I, Output = Vec<u8>, Error = E>
+where
+    I: Input + AsBytes,
+    E: ParseError<I> + FromExternalError<I, FromHexError>,
+{
+    take(2 * len).map_res(|digits: I| crate::decode(digits.as_bytes()))
+}
+
+/// Parses hex digits up to `delim` or the end of the input, without
+/// consuming the delimiter.
+///
+/// When parsing streaming input, more input is requested until the
+/// delimiter is found. Any other character before it is an error, so this
+/// can't be used to skip over unrelated data.
+///
+/// # Example
+///
+/// ```
+/// use nom::Parser;
+///
+/// let mut parser = hex::nom::hex_until::<_, _, nom::error::Error<_>>(',');
+/// let (rest, kiwi) = parser.parse_complete("6b697769,")?;
+/// assert_eq!(kiwi, b"kiwi");
+/// assert_eq!(rest, ",");
+/// # Ok::<(), nom::Err<nom::error::Error<&str>>>(())
+/// ```
+pub fn hex_until<I, T, E>(delim: T) -> impl Parser<I, Output = Vec<u8>, Error = E>
+where
+    I: Input + AsBytes,
+    I::Item: PartialEq<T>,
+    T: Copy,
+    E: ParseError<I> + FromExternalError<I, FromHexError>,
+{
+    take_till(move |c: I::Item| c == delim).map_res(|digits: I| crate::decode(digits.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use nom::{
+        error::{Error, ErrorKind},
+        Err, Needed,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_hex_bytes() {
+        let mut parser = hex_bytes::<_, Error<_>>(2);
+        assert_eq!(parser.parse_complete("abcd"), Ok(("", vec![0xab, 0xcd])));
+        assert_eq!(
+            parser.parse_complete("ABCDEF"),
+            Ok(("EF", vec![0xab, 0xcd]))
+        );
+        assert!(parser.parse_complete("abc").is_err());
+        // the error is at the start of the field
+        assert_eq!(
+            parser.parse_complete("abgd"),
+            Err(Err::Error(Error::new("abgd", ErrorKind::MapRes)))
+        );
+        assert!(matches!(parser.parse("abc"), Err(Err::Incomplete(_))));
+
+        assert_eq!(
+            hex_bytes::<_, Error<_>>(0).parse_complete(&b"ab"[..]),
+            Ok((&b"ab"[..], vec![]))
+        );
+    }
+
+    #[test]
+    fn test_hex_until() {
+        let mut parser = hex_until::<_, _, Error<_>>(b';');
+        assert_eq!(
+            parser.parse_complete(&b"ab;cd"[..]),
+            Ok((&b";cd"[..], vec![0xab]))
+        );
+        assert_eq!(
+            parser.parse_complete(&b"abcd"[..]),
+            Ok((&b""[..], vec![0xab, 0xcd]))
+        );
+        assert_eq!(parser.parse_complete(&b";"[..]), Ok((&b";"[..], vec![])));
+        assert!(parser.parse_complete(&b"abc;"[..]).is_err());
+        assert!(parser.parse_complete(&b"ab cd;"[..]).is_err());
+
+        let mut parser = hex_until::<_, _, Error<_>>(';');
+        assert_eq!(parser.parse("abcd"), Err(Err::Incomplete(Needed::new(1))));
+        assert_eq!(parser.parse("abcd;"), Ok((";", vec![0xab, 0xcd])));
+    }
+}
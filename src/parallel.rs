@@ -0,0 +1,114 @@
+use alloc::{string::String, vec, vec::Vec};
+use rayon::prelude::*;
+
+use crate::FromHexError;
+
+// The number of bytes encoded or decoded by a task. Large enough for the
+// scheduling to be negligible, and small enough to spread over all threads
+// for inputs of a few megabytes.
+const CHUNK: usize = 256 * 1024;
+
+/// Encodes `data` as lowercase hex on the rayon thread pool.
+///
+/// The input is split into chunks which are encoded in parallel, directly
+/// into the returned string. This pays off for inputs of many megabytes,
+/// otherwise [`encode`](crate::encode) is as fast.
+///
+/// # Example
+///
+/// ```
+/// let data = vec![0xab; 1 << 20];
+/// assert_eq!(hex::encode_parallel(&data), hex::encode(&data));
+/// ```
+#[must_use]
+pub fn encode_parallel<T: AsRef<[u8]>>(data: T) -> String {
+    let data = data.as_ref();
+    let mut out = vec![0; data.len() * 2];
+    data.par_chunks(CHUNK)
+        .zip(out.par_chunks_mut(2 * CHUNK))
+        .for_each(|(data, out)| {
+            crate::encode_to_slice(data, out).unwrap();
+        });
+    // Saftey: the output only consists of hex digits.
+    unsafe { String::from_utf8_unchecked(out) }
+}
+
+/// Decodes a hex string on the rayon thread pool.
+///
+/// The input is split into chunks which are decoded in parallel, directly
+/// into the returned vector. Errors are the same as for
+/// [`decode`](crate::decode): if there are several invalid characters, the
+/// first one is reported, with its index in the whole input.
+///
+/// # Example
+///
+/// ```
+/// let hex = "ab".repeat(1 << 20);
+/// assert_eq!(hex::decode_parallel(&hex)?, vec![0xab; 1 << 20]);
+///
+/// let mut hex = hex.into_bytes();
+/// hex[900_001] = b'x';
+/// assert_eq!(
+///     hex::decode_parallel(&hex),
+///     Err(hex::FromHexError::InvalidHexCharacter { c: 'x', index: 900_001 })
+/// );
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+pub fn decode_parallel<T: AsRef<[u8]>>(data: T) -> Result<Vec<u8>, FromHexError> {
+    let data = data.as_ref();
+    let mut out = vec![0; crate::decoded_len(data.len())?];
+    let err = data
+        .par_chunks(2 * CHUNK)
+        .zip(out.par_chunks_mut(CHUNK))
+        .enumerate()
+        .find_map_first(|(i, (data, out))| {
+            let err = crate::decode_to_slice(data, out).err()?;
+            Some(err.offset(i * 2 * CHUNK))
+        });
+    match err {
+        Some(err) => Err(err),
+        None => Ok(out),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_roundtrip() {
+        for len in [0, 1, CHUNK - 1, CHUNK, 3 * CHUNK + 5] {
+            let data: Vec<u8> = (0..len).map(|i| (i * 37) as u8).collect();
+            let hex = encode_parallel(&data);
+            assert_eq!(hex, crate::encode(&data));
+            assert_eq!(decode_parallel(&hex), Ok(data));
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(decode_parallel("abc"), Err(FromHexError::OddLength));
+
+        let mut hex = vec![b'0'; 8 * CHUNK];
+        hex[5 * CHUNK + 3] = b'x';
+        hex[7 * CHUNK] = b'y';
+        assert_eq!(
+            decode_parallel(&hex),
+            Err(FromHexError::InvalidHexCharacter {
+                c: 'x',
+                index: 5 * CHUNK + 3
+            })
+        );
+        // the first invalid character is reported, even if it isn't found
+        // first
+        hex[2 * CHUNK - 1] = b'z';
+        assert_eq!(
+            decode_parallel(&hex),
+            Err(FromHexError::InvalidHexCharacter {
+                c: 'z',
+                index: 2 * CHUNK - 1
+            })
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! Hex encoding and decoding of plain-old-data types with `bytemuck`.
+//!
+//! Register maps and fixed wire structs can be decoded straight from hex,
+//! without going through a `[u8; N]` and converting it by hand.
+//!
+//! The bytes are taken as they are laid out in memory. Multi-byte fields are
+//! therefore in the target's native byte order, and a hex dump produced on a
+//! big-endian machine doesn't decode to the same values on a little-endian
+//! one. Use types with an explicit byte order for the fields of wire formats,
+//! e.g. byte arrays or wrappers converting with `from_be_bytes`.
+//!
+//! # Example
+//!
+//! ```
+//! use bytemuck::{Pod, Zeroable};
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+//! #[repr(C)]
+//! struct Header {
+//!     magic: [u8; 4],
+//!     version: u8,
+//!     flags: u8,
+//!     length: [u8; 2],
+//! }
+//!
+//! let header: Header = hex::pod::decode_as("cafebabe0102000a")?;
+//! assert_eq!(header.magic, [0xca, 0xfe, 0xba, 0xbe]);
+//! assert_eq!(u16::from_be_bytes(header.length), 10);
+//! assert_eq!(hex::pod::encode_pod(&header), "cafebabe0102000a");
+//! # Ok::<(), hex::FromHexError>(())
+//! ```
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::mem::{self, MaybeUninit};
+
+use bytemuck::AnyBitPattern;
+#[cfg(feature = "alloc")]
+use bytemuck::NoUninit;
+
+use crate::FromHexError;
+
+/// Decodes a hex string into a value of `T`.
+///
+/// The hex string has to encode exactly `size_of::<T>()` bytes, otherwise
+/// [`FromHexError::InvalidStringLength`] is returned. See the
+/// [module documentation](self) about the byte order.
+///
+/// # Example
+///
+/// ```
+/// let words: [u16; 2] = hex::pod::decode_as("00010002")?;
+/// assert_eq!(words, [u16::from_ne_bytes([0, 1]), u16::from_ne_bytes([0, 2])]);
+///
+/// assert!(hex::pod::decode_as::<u32, _>("0001").is_err());
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+pub fn decode_as<T: AnyBitPattern, U: AsRef<[u8]>>(hex: U) -> Result<T, FromHexError> {
+    let hex = hex.as_ref();
+    if crate::decoded_len(hex.len())? != mem::size_of::<T>() {
+        return Err(FromHexError::InvalidStringLength);
+    }
+    let bytes = crate::decode_iter(hex)?;
+    let mut value = MaybeUninit::<T>::uninit();
+    let ptr = value.as_mut_ptr().cast::<u8>();
+    for (i, byte) in bytes.enumerate() {
+        // Saftey: `i` is less than the size of `T`, and no reference to the
+        // uninitialized memory is created
+        unsafe { ptr.add(i).write(byte) };
+    }
+    // Saftey: All bytes were written, and any bit pattern is valid for `T`
+    Ok(unsafe { value.assume_init() })
+}
+
+/// Encodes the bytes of `value` as lowercase hex.
+///
+/// See the [module documentation](self) about the byte order.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::pod::encode_pod(&[0xab_u8, 0xcd]), "abcd");
+/// assert_eq!(hex::pod::encode_pod(&1_u32.to_be()), "00000001");
+/// ```
+#[must_use]
+#[cfg(feature = "alloc")]
+pub fn encode_pod<T: NoUninit>(value: &T) -> String {
+    crate::encode(bytemuck::bytes_of(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_decode_as() {
+        assert_eq!(
+            decode_as::<u32, _>("01020304"),
+            Ok(u32::from_ne_bytes([1, 2, 3, 4]))
+        );
+        assert_eq!(decode_as::<[u8; 0], _>(""), Ok([]));
+        assert_eq!(decode_as::<u16, _>("01020"), Err(FromHexError::OddLength));
+        assert_eq!(
+            decode_as::<u16, _>("01"),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(decode_as::<u16, _>(b"0102"), Ok(u16::from_ne_bytes([1, 2])));
+        assert_eq!(
+            decode_as::<u16, _>("0g00"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 1 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_encode_pod() {
+        let value = u64::from_ne_bytes([0, 1, 2, 3, 0xfc, 0xfd, 0xfe, 0xff]);
+        assert_eq!(encode_pod(&value), "00010203fcfdfeff");
+        assert_eq!(decode_as::<u64, _>(encode_pod(&value)), Ok(value));
+    }
+}
@@ -0,0 +1,131 @@
+// Vectorized encoding and decoding with `core::simd`, enabled by the
+// `portable-simd` feature, which needs a nightly compiler.
+//
+// The compiler lowers the vectors to whatever the target has, so this works
+// on any architecture, without intrinsics or runtime detection. Like the
+// kernels of the `simd` feature, these only handle whole blocks and return
+// how many bytes they processed, and decoding stops at the first block
+// containing an invalid character, leaving it to the scalar loop to report.
+//
+// The `force-scalar` feature compiles this out.
+use core::mem::MaybeUninit;
+use core::simd::{cmp::SimdPartialOrd, u8x16, Select};
+
+// Encodes blocks of 16 bytes, returning the number of bytes encoded.
+#[inline]
+pub(crate) fn encode(input: &[u8], output: &mut [MaybeUninit<u8>], table: &[u8; 16]) -> usize {
+    debug_assert_eq!(2 * input.len(), output.len());
+    let table = u8x16::from_array(*table);
+    let mut done = 0;
+    for (chunk, out) in input.chunks_exact(16).zip(output.chunks_exact_mut(32)) {
+        let bytes = u8x16::from_slice(chunk);
+        let high = table.swizzle_dyn(bytes >> 4);
+        let low = table.swizzle_dyn(bytes & u8x16::splat(0x0f));
+        let (first, second) = high.interleave(low);
+        crate::write_slice(&mut out[..16], first.as_array());
+        crate::write_slice(&mut out[16..], second.as_array());
+        done += 16;
+    }
+    done
+}
+
+// Converts 16 hex digits to their values, or returns `None` if any of them
+// is invalid.
+#[inline]
+fn digits(hex: u8x16) -> Option<u8x16> {
+    // Subtracting wraps around, so everything below the first digit ends up
+    // above the last one, and an unsigned comparison checks the range.
+    let decimal = hex - u8x16::splat(b'0');
+    let is_decimal = decimal.simd_le(u8x16::splat(9));
+    let letter = (hex | u8x16::splat(0x20)) - u8x16::splat(b'a');
+    let is_letter = letter.simd_le(u8x16::splat(5));
+    if !(is_decimal | is_letter).all() {
+        return None;
+    }
+    Some(is_decimal.select(decimal, letter + u8x16::splat(10)))
+}
+
+// Decodes blocks of 32 digits, returning the number of bytes decoded.
+#[inline]
+pub(crate) fn decode(hex: &[u8], out: &mut [MaybeUninit<u8>]) -> usize {
+    debug_assert_eq!(hex.len(), 2 * out.len());
+    let mut done = 0;
+    for (chunk, out) in hex.chunks_exact(32).zip(out.chunks_exact_mut(16)) {
+        let (high, low) =
+            u8x16::from_slice(&chunk[..16]).deinterleave(u8x16::from_slice(&chunk[16..]));
+        let (high, low) = match (digits(high), digits(low)) {
+            (Some(high), Some(low)) => (high, low),
+            _ => break,
+        };
+        crate::write_slice(out, ((high << 4) | low).as_array());
+        done += 16;
+    }
+    done
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromHexError, HEX_CHARS_LOWER, HEX_CHARS_UPPER};
+    use pretty_assertions::assert_eq;
+
+    fn encode_init(input: &[u8], output: &mut [u8], table: &[u8; 16]) -> usize {
+        // Saftey: encoding only writes initialized bytes.
+        encode(input, unsafe { crate::as_uninit(output) }, table)
+    }
+
+    fn decode_init(hex: &[u8], out: &mut [u8]) -> usize {
+        // Saftey: decoding only writes initialized bytes.
+        decode(hex, unsafe { crate::as_uninit(out) })
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let mut data = [0; 100];
+        for (i, byte) in data.iter_mut().enumerate() {
+            *byte = (i * 37) as u8;
+        }
+        for len in [0, 15, 16, 17, 32, 100] {
+            for table in [HEX_CHARS_LOWER, HEX_CHARS_UPPER] {
+                let mut hex = [0; 200];
+                let done = encode_init(&data[..len], &mut hex[..2 * len], table);
+                assert_eq!(done, len - len % 16);
+                let mut expected = [0; 200];
+                crate::encode_to_slice_inner(&data[..done], &mut expected[..2 * done], table)
+                    .unwrap();
+                assert_eq!(&hex[..2 * done], &expected[..2 * done]);
+
+                let mut out = [0; 100];
+                assert_eq!(decode_init(&expected[..2 * done], &mut out[..done]), done);
+                assert_eq!(&out[..done], &data[..done]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        // every byte value, in each position of a block
+        for c in 0..=255_u8 {
+            let valid = crate::DECODE_TABLE[c as usize] != crate::tables::INVALID;
+            for i in [0, 1, 15, 16, 31, 32, 63] {
+                let mut hex = [b'a'; 64];
+                hex[i] = c;
+                let mut out = [0; 32];
+                let done = decode_init(&hex, &mut out);
+                if valid {
+                    assert_eq!(done, 32);
+                    assert_eq!(crate::decode_to_slice(hex, &mut out), Ok(()));
+                } else {
+                    assert_eq!(done, i / 32 * 16, "{} {}", c, i);
+                    assert_eq!(
+                        crate::decode_to_slice(hex, &mut out),
+                        Err(FromHexError::InvalidHexCharacter {
+                            c: c as char,
+                            index: i
+                        })
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,26 @@
+//! Re-exports of the commonly used items.
+//!
+//! This brings the traits into scope, which makes their methods available,
+//! along with the main types and free functions.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! use hex::prelude::*;
+//!
+//! let bytes = Vec::from_hex("6b697769")?;
+//! assert_eq!(bytes.encode_hex::<String>(), "6b697769");
+//! assert_eq!(Hex(&bytes).to_string(), encode(&bytes));
+//! assert_eq!(bytes.iter().copied().encode_hex().collect::<String>(), "6b697769");
+//! # }
+//! # Ok::<(), hex::FromHexError>(())
+//! ```
+pub use crate::{
+    decode_to_slice, display_iter, encode_to_slice, encode_to_slice_upper, Case, FixedHex, FromHex,
+    FromHexError, Hex, HexBuffer, HexStr, IterHexExt, ToHex,
+};
+
+#[cfg(feature = "alloc")]
+pub use crate::{decode, encode, encode_upper, HexString};
@@ -0,0 +1,144 @@
+//! [`proptest`](::proptest) strategies for hex strings and the wrapper types.
+//!
+//! Besides strategies producing valid values, [`near_valid_hex`] produces
+//! strings which are *almost* valid hex, which is where parsers tend to hide
+//! their bugs.
+//!
+//! # Example
+//!
+//! ```
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn roundtrip(hex in hex::proptest::hex_string(0..64)) {
+//!         prop_assert_eq!(hex::encode(hex.decode()), hex.as_str());
+//!     }
+//!
+//!     fn never_panics(s in hex::proptest::near_valid_hex(0..64)) {
+//!         let _ = hex::decode(&s);
+//!     }
+//! }
+//! # roundtrip();
+//! # never_panics();
+//! ```
+use ::proptest::arbitrary::{any, Arbitrary};
+use ::proptest::collection::{vec, SizeRange};
+use ::proptest::prelude::*;
+use ::proptest::strategy::BoxedStrategy;
+use std::string::String;
+
+use crate::{FixedHex, HexString};
+
+/// Generates valid [`HexString`]s encoding a number of bytes in `size`.
+pub fn hex_string(size: impl Into<SizeRange>) -> impl Strategy<Value = HexString> {
+    vec(any::<u8>(), size).prop_map(HexString::encode)
+}
+
+/// Generates valid hex strings encoding a number of bytes in `size`, using a
+/// random mix of upper and lower case digits.
+pub fn mixed_case_hex(size: impl Into<SizeRange>) -> impl Strategy<Value = String> {
+    vec((any::<u8>(), any::<bool>(), any::<bool>()), size).prop_map(|bytes| {
+        let mut s = String::with_capacity(bytes.len() * 2);
+        for (byte, high_upper, low_upper) in bytes {
+            let (high, low) = crate::byte2hex(byte, crate::HEX_CHARS_LOWER);
+            s.push(case(high, high_upper));
+            s.push(case(low, low_upper));
+        }
+        s
+    })
+}
+
+fn case(c: u8, upper: bool) -> char {
+    if upper {
+        c.to_ascii_uppercase() as char
+    } else {
+        c as char
+    }
+}
+
+/// Generates [`FixedHex`] values.
+pub fn fixed_hex<const N: usize>() -> impl Strategy<Value = FixedHex<N>> {
+    vec(any::<u8>(), N).prop_map(|bytes| {
+        let mut out = [0; N];
+        out.copy_from_slice(&bytes);
+        FixedHex(out)
+    })
+}
+
+/// Generates strings which are valid hex encoding a number of bytes in
+/// `size`, except for a single defect.
+///
+/// Defects include a missing or extra digit, an invalid or non-ascii
+/// character, embedded whitespace and a `0x` prefix.
+pub fn near_valid_hex(size: impl Into<SizeRange>) -> impl Strategy<Value = String> {
+    const DEFECTS: &[&str] = &[
+        "g", "G", "x", " ", "\t", "\n", ":", "-", "\0", "é", "０", "\u{ff}",
+    ];
+
+    let valid = mixed_case_hex(size);
+    (
+        valid,
+        any::<prop::sample::Index>(),
+        0..4_u8,
+        0..DEFECTS.len(),
+    )
+        .prop_map(|(mut s, index, kind, defect)| {
+            let at = index.index(s.len() + 1);
+            match kind {
+                // odd length: drop or add a digit
+                0 if !s.is_empty() => {
+                    s.remove(at.min(s.len() - 1));
+                }
+                0 => s.push('0'),
+                1 => s.insert(at, 'a'),
+                2 => s.insert_str(0, "0x"),
+                _ => s.insert_str(at, DEFECTS[defect]),
+            }
+            s
+        })
+}
+
+impl Arbitrary for HexString {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<HexString>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        hex_string(0..256).boxed()
+    }
+}
+
+impl<const N: usize> Arbitrary for FixedHex<N> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<FixedHex<N>>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        fixed_hex().boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_hex_string(hex in hex_string(0..32)) {
+            prop_assert!(HexString::new(hex.as_str()).is_ok());
+        }
+
+        #[test]
+        fn test_mixed_case_hex(s in mixed_case_hex(0..32)) {
+            prop_assert!(crate::decode(&s).is_ok());
+        }
+
+        #[test]
+        fn test_near_valid_hex(s in near_valid_hex(0..32)) {
+            prop_assert!(crate::decode(&s).is_err());
+        }
+
+        #[test]
+        fn test_fixed_hex(hex in any::<FixedHex<20>>()) {
+            prop_assert_eq!(hex.to_string().parse::<FixedHex<20>>(), Ok(hex));
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use alloc::{string::String, vec, vec::Vec};
+use rand_core::RngCore;
+
+use crate::Case;
+
+/// Generates a random string of `len` hex digits.
+///
+/// Every digit is chosen uniformly and independently, so `len` may be odd.
+/// Use [`random_bytes_hex`] if the decoded bytes are needed as well.
+///
+/// # Example
+///
+/// ```
+/// # use rand_core::{impls, RngCore};
+/// # struct Counter(u64);
+/// # impl RngCore for Counter {
+/// #     fn next_u32(&mut self) -> u32 { self.next_u64() as u32 }
+/// #     fn next_u64(&mut self) -> u64 { self.0 += 0x9e37_79b9_7f4a_7c15; self.0 }
+/// #     fn fill_bytes(&mut self, dest: &mut [u8]) { impls::fill_bytes_via_next(self, dest) }
+/// #     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+/// #         self.fill_bytes(dest);
+/// #         Ok(())
+/// #     }
+/// # }
+/// # let mut rng = Counter(0);
+/// use hex::Case;
+///
+/// let nonce = hex::random_hex(15, Case::Upper, &mut rng);
+/// assert_eq!(nonce.len(), 15);
+/// assert!(nonce.chars().all(|c| matches!(c, '0'..='9' | 'A'..='F')));
+/// ```
+pub fn random_hex<R: RngCore + ?Sized>(len: usize, case: Case, rng: &mut R) -> String {
+    let mut bytes = vec![0; len / 2 + len % 2];
+    rng.fill_bytes(&mut bytes);
+    let mut hex: String = crate::encode_to_iter(case.table(), &bytes);
+    hex.truncate(len);
+    hex
+}
+
+/// Generates `len` random bytes, returning them along with their hex
+/// encoding.
+pub fn random_bytes_hex<R: RngCore + ?Sized>(
+    len: usize,
+    case: Case,
+    rng: &mut R,
+) -> (Vec<u8>, String) {
+    let mut bytes = vec![0; len];
+    rng.fill_bytes(&mut bytes);
+    let hex = crate::encode_to_iter(case.table(), &bytes);
+    (bytes, hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rand_core::impls;
+
+    struct Counter(u8);
+
+    impl RngCore for Counter {
+        fn next_u32(&mut self) -> u32 {
+            impls::next_u32_via_fill(self)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            impls::next_u64_via_fill(self)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for byte in dest {
+                *byte = self.0;
+                self.0 = self.0.wrapping_add(0x11);
+            }
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_random_hex() {
+        assert_eq!(random_hex(5, Case::Lower, &mut Counter(0xab)), "abbcc");
+        assert_eq!(random_hex(4, Case::Upper, &mut Counter(0xab)), "ABBC");
+        assert_eq!(random_hex(0, Case::Lower, &mut Counter(0)), "");
+    }
+
+    #[test]
+    fn test_random_bytes_hex() {
+        let (bytes, hex) = random_bytes_hex(3, Case::Lower, &mut Counter(0xee));
+        assert_eq!(bytes, [0xee, 0xff, 0x10]);
+        assert_eq!(hex, "eeff10");
+    }
+}
@@ -16,20 +16,19 @@ struct Foo {
 "##
 )]
 use serde::de::{Error, Visitor};
-use serde::Deserializer;
-#[cfg(feature = "alloc")]
-use serde::Serializer;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 #[cfg(feature = "alloc")]
 use alloc::string::String;
 
 use core::fmt;
 use core::marker::PhantomData;
+use core::str::FromStr;
 
-use crate::FromHex;
+use crate::{FixedHex, FromHex, Hex, HexStr};
 
 #[cfg(feature = "alloc")]
-use crate::ToHex;
+use crate::{Case, HexString, ToHex};
 
 /// Serializes `data` as hex string using uppercase characters.
 ///
@@ -40,8 +39,7 @@ where
     S: Serializer,
     T: ToHex,
 {
-    let s = data.encode_hex_upper::<String>();
-    serializer.serialize_str(&s)
+    serialize_case(data, Case::Upper, serializer)
 }
 
 /// Serializes `data` as hex string using lowercase characters.
@@ -56,7 +54,45 @@ where
     S: Serializer,
     T: ToHex,
 {
-    let s = data.encode_hex::<String>();
+    serialize_case(data, Case::Lower, serializer)
+}
+
+/// Serializes `data` as hex string using the given case.
+///
+/// Apart from taking the case at runtime, this works exactly like
+/// `serialize()` and `serialize_upper()`. It can't be used with
+/// `#[serde(with = "...")]` directly, but from a `Serialize` implementation
+/// or a `serialize_with` function.
+///
+/// # Example
+///
+/// ```
+/// use serde::{Serialize, Serializer};
+///
+/// struct Key {
+///     bytes: Vec<u8>,
+///     case: hex::Case,
+/// }
+///
+/// impl Serialize for Key {
+///     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+///         hex::serde::serialize_case(&self.bytes, self.case, serializer)
+///     }
+/// }
+///
+/// let key = Key { bytes: vec![0xab], case: hex::Case::Upper };
+/// assert_eq!(serde_json::to_string(&key).unwrap(), r#""AB""#);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn serialize_case<S, T>(data: T, case: Case, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: ToHex,
+{
+    let s: String = match case {
+        Case::Lower => data.encode_hex(),
+        Case::Upper => data.encode_hex_upper(),
+    };
     serializer.serialize_str(&s)
 }
 
@@ -100,3 +136,81 @@ where
 
     deserializer.deserialize_str(HexStrVisitor(PhantomData))
 }
+
+impl Serialize for HexStr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for &'a HexStr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <&str>::deserialize(deserializer)?;
+        HexStr::new(s).map_err(Error::custom)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for HexString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for HexString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        HexString::new(s).map_err(Error::custom)
+    }
+}
+
+impl<const N: usize> Serialize for FixedHex<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for FixedHex<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(FromStrVisitor(PhantomData))
+    }
+}
+
+// Deserializes any type which can be parsed from a string.
+struct FromStrVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for FromStrVisitor<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: fmt::Display,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a hex encoded string")
+    }
+
+    fn visit_str<E>(self, data: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        data.parse().map_err(Error::custom)
+    }
+}
+
+impl<T: AsRef<[u8]>> Serialize for Hex<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Hex<T>
+where
+    T: FromHex,
+    <T as FromHex>::Error: fmt::Display,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer).map(Hex)
+    }
+}
@@ -0,0 +1,647 @@
+// Vectorized encoding and decoding of long inputs, enabled by the `simd`
+// feature.
+//
+// On x86 and x86_64, the instruction set is detected at runtime by
+// `backend`, so a binary built for the baseline still uses AVX2 where it is
+// available, and SSSE3 on older CPUs. Decoding uses AVX-512 VBMI on CPUs
+// which have it.
+//
+// On wasm32 and RISC-V, SIMD128 and the vector extension are used if their
+// target features are enabled at compile time, as these targets have no
+// runtime detection in `std`. The RISC-V kernels are written in assembly,
+// which is only tested under QEMU, so they also need the `rvv` feature.
+//
+// The kernels return how many bytes they processed, leaving the rest of the
+// input to the scalar loops. Most only handle whole blocks, and decoding
+// stops at the first block containing an invalid character, so the scalar
+// loop finds and reports it. The vector extension of RISC-V handles inputs
+// of any length, so its kernels process everything up to the first invalid
+// character.
+//
+// The `force-scalar` feature compiles this out.
+use core::mem::MaybeUninit;
+
+use crate::backend::Backend;
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod avx2 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+    use core::mem::MaybeUninit;
+
+    // Encodes blocks of 32 bytes, returning the number of bytes encoded.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn encode(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        table: &[u8; 16],
+    ) -> usize {
+        let table = _mm256_broadcastsi128_si256(_mm_loadu_si128(table.as_ptr().cast()));
+        let mask = _mm256_set1_epi8(0x0f);
+        let mut done = 0;
+        for (chunk, out) in input.chunks_exact(32).zip(output.chunks_exact_mut(64)) {
+            let bytes = _mm256_loadu_si256(chunk.as_ptr().cast());
+            let high = _mm256_and_si256(_mm256_srli_epi16(bytes, 4), mask);
+            let low = _mm256_and_si256(bytes, mask);
+            let high = _mm256_shuffle_epi8(table, high);
+            let low = _mm256_shuffle_epi8(table, low);
+            // Interleaving works within each 128 bit lane, so the halves are
+            // put back in order when storing.
+            let first = _mm256_unpacklo_epi8(high, low);
+            let second = _mm256_unpackhi_epi8(high, low);
+            let out = out.as_mut_ptr();
+            _mm256_storeu_si256(out.cast(), _mm256_permute2x128_si256(first, second, 0x20));
+            _mm256_storeu_si256(
+                out.add(32).cast(),
+                _mm256_permute2x128_si256(first, second, 0x31),
+            );
+            done += 32;
+        }
+        done
+    }
+
+    // Converts 32 hex digits to their values, or returns `None` if any of
+    // them is invalid.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn digits(hex: __m256i) -> Option<__m256i> {
+        // Subtracting wraps around, so everything below the first digit ends
+        // up above the last one, and an unsigned minimum checks the range.
+        let decimal = _mm256_sub_epi8(hex, _mm256_set1_epi8(b'0' as i8));
+        let is_decimal = _mm256_cmpeq_epi8(_mm256_min_epu8(decimal, _mm256_set1_epi8(9)), decimal);
+        let letter = _mm256_sub_epi8(
+            _mm256_or_si256(hex, _mm256_set1_epi8(0x20)),
+            _mm256_set1_epi8(b'a' as i8),
+        );
+        let is_letter = _mm256_cmpeq_epi8(_mm256_min_epu8(letter, _mm256_set1_epi8(5)), letter);
+        if _mm256_movemask_epi8(_mm256_or_si256(is_decimal, is_letter)) != -1 {
+            return None;
+        }
+        let letter = _mm256_add_epi8(letter, _mm256_set1_epi8(10));
+        Some(_mm256_blendv_epi8(letter, decimal, is_decimal))
+    }
+
+    // Decodes blocks of 64 digits, returning the number of bytes decoded.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn decode(hex: &[u8], out: &mut [MaybeUninit<u8>]) -> usize {
+        // Multiplies the high digit of each pair by 16 and adds the low one.
+        let weights = _mm256_set1_epi16(0x0110);
+        let mut done = 0;
+        for (chunk, out) in hex.chunks_exact(64).zip(out.chunks_exact_mut(32)) {
+            let first = digits(_mm256_loadu_si256(chunk.as_ptr().cast()));
+            let second = digits(_mm256_loadu_si256(chunk.as_ptr().add(32).cast()));
+            let (first, second) = match (first, second) {
+                (Some(first), Some(second)) => (first, second),
+                _ => break,
+            };
+            let first = _mm256_maddubs_epi16(first, weights);
+            let second = _mm256_maddubs_epi16(second, weights);
+            // Packing works within each 128 bit lane, so the quarters are put
+            // back in order.
+            let bytes = _mm256_permute4x64_epi64(_mm256_packus_epi16(first, second), 0xd8);
+            _mm256_storeu_si256(out.as_mut_ptr().cast(), bytes);
+            done += 32;
+        }
+        done
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod avx512 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+    use core::mem::MaybeUninit;
+
+    use crate::DECODE_TABLE;
+
+    // Decodes blocks of 64 digits, returning the number of bytes decoded.
+    #[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+    pub(super) unsafe fn decode(hex: &[u8], out: &mut [MaybeUninit<u8>]) -> usize {
+        // The ASCII half of the decode table fits into two registers, so
+        // every digit is looked up and validated with a single `vpermi2b`.
+        let table_low = _mm512_loadu_si512(DECODE_TABLE.as_ptr().cast());
+        let table_high = _mm512_loadu_si512(DECODE_TABLE.as_ptr().add(64).cast());
+        // Multiplies the high digit of each pair by 16 and adds the low one.
+        let weights = _mm512_set1_epi16(0x0110);
+        let mut done = 0;
+        for (chunk, out) in hex.chunks_exact(64).zip(out.chunks_exact_mut(32)) {
+            let hex = _mm512_loadu_si512(chunk.as_ptr().cast());
+            let digits = _mm512_permutex2var_epi8(table_low, hex, table_high);
+            // Invalid digits have the high bit set, as do non-ASCII bytes,
+            // which were looked up by their lower 7 bits.
+            if _mm512_movepi8_mask(_mm512_or_si512(digits, hex)) != 0 {
+                break;
+            }
+            let bytes = _mm512_cvtepi16_epi8(_mm512_maddubs_epi16(digits, weights));
+            _mm256_storeu_si256(out.as_mut_ptr().cast(), bytes);
+            done += 32;
+        }
+        done
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod ssse3 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+    use core::mem::MaybeUninit;
+
+    // Encodes blocks of 16 bytes, returning the number of bytes encoded.
+    #[target_feature(enable = "ssse3")]
+    pub(super) unsafe fn encode(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        table: &[u8; 16],
+    ) -> usize {
+        let table = _mm_loadu_si128(table.as_ptr().cast());
+        let mask = _mm_set1_epi8(0x0f);
+        let mut done = 0;
+        for (chunk, out) in input.chunks_exact(16).zip(output.chunks_exact_mut(32)) {
+            let bytes = _mm_loadu_si128(chunk.as_ptr().cast());
+            let high = _mm_shuffle_epi8(table, _mm_and_si128(_mm_srli_epi16(bytes, 4), mask));
+            let low = _mm_shuffle_epi8(table, _mm_and_si128(bytes, mask));
+            let out = out.as_mut_ptr();
+            _mm_storeu_si128(out.cast(), _mm_unpacklo_epi8(high, low));
+            _mm_storeu_si128(out.add(16).cast(), _mm_unpackhi_epi8(high, low));
+            done += 16;
+        }
+        done
+    }
+
+    // Converts 16 hex digits to their values, or returns `None` if any of
+    // them is invalid.
+    #[inline]
+    #[target_feature(enable = "ssse3")]
+    unsafe fn digits(hex: __m128i) -> Option<__m128i> {
+        // The same range checks as for AVX2.
+        let decimal = _mm_sub_epi8(hex, _mm_set1_epi8(b'0' as i8));
+        let is_decimal = _mm_cmpeq_epi8(_mm_min_epu8(decimal, _mm_set1_epi8(9)), decimal);
+        let letter = _mm_sub_epi8(
+            _mm_or_si128(hex, _mm_set1_epi8(0x20)),
+            _mm_set1_epi8(b'a' as i8),
+        );
+        let is_letter = _mm_cmpeq_epi8(_mm_min_epu8(letter, _mm_set1_epi8(5)), letter);
+        if _mm_movemask_epi8(_mm_or_si128(is_decimal, is_letter)) != 0xffff {
+            return None;
+        }
+        // The value of a valid digit is its low nibble, plus 9 for letters,
+        // whose high nibble is 4 or 6. Looking the offset up by the high
+        // nibble avoids a blend, which would need SSE4.1.
+        let offsets = _mm_setr_epi8(0, 0, 0, 0, 9, 0, 9, 0, 0, 0, 0, 0, 0, 0, 0, 0);
+        let mask = _mm_set1_epi8(0x0f);
+        let offset = _mm_shuffle_epi8(offsets, _mm_and_si128(_mm_srli_epi16(hex, 4), mask));
+        Some(_mm_add_epi8(_mm_and_si128(hex, mask), offset))
+    }
+
+    // Decodes blocks of 32 digits, returning the number of bytes decoded.
+    #[target_feature(enable = "ssse3")]
+    pub(super) unsafe fn decode(hex: &[u8], out: &mut [MaybeUninit<u8>]) -> usize {
+        // Multiplies the high digit of each pair by 16 and adds the low one.
+        let weights = _mm_set1_epi16(0x0110);
+        let mut done = 0;
+        for (chunk, out) in hex.chunks_exact(32).zip(out.chunks_exact_mut(16)) {
+            let first = digits(_mm_loadu_si128(chunk.as_ptr().cast()));
+            let second = digits(_mm_loadu_si128(chunk.as_ptr().add(16).cast()));
+            let (first, second) = match (first, second) {
+                (Some(first), Some(second)) => (first, second),
+                _ => break,
+            };
+            let first = _mm_maddubs_epi16(first, weights);
+            let second = _mm_maddubs_epi16(second, weights);
+            _mm_storeu_si128(out.as_mut_ptr().cast(), _mm_packus_epi16(first, second));
+            done += 16;
+        }
+        done
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+mod simd128 {
+    use core::arch::wasm32::*;
+    use core::mem::MaybeUninit;
+
+    // Encodes blocks of 16 bytes, returning the number of bytes encoded.
+    pub(super) fn encode(input: &[u8], output: &mut [MaybeUninit<u8>], table: &[u8; 16]) -> usize {
+        // Saftey: the table is 16 bytes long.
+        let table = unsafe { v128_load(table.as_ptr().cast()) };
+        let mut done = 0;
+        for (chunk, out) in input.chunks_exact(16).zip(output.chunks_exact_mut(32)) {
+            // Saftey: the chunk is 16 bytes long.
+            let bytes = unsafe { v128_load(chunk.as_ptr().cast()) };
+            let high = u8x16_swizzle(table, u8x16_shr(bytes, 4));
+            let low = u8x16_swizzle(table, v128_and(bytes, u8x16_splat(0x0f)));
+            let first =
+                u8x16_shuffle::<0, 16, 1, 17, 2, 18, 3, 19, 4, 20, 5, 21, 6, 22, 7, 23>(high, low);
+            let second =
+                u8x16_shuffle::<8, 24, 9, 25, 10, 26, 11, 27, 12, 28, 13, 29, 14, 30, 15, 31>(
+                    high, low,
+                );
+            // Saftey: the output chunk is 32 bytes long.
+            unsafe {
+                v128_store(out.as_mut_ptr().cast(), first);
+                v128_store(out.as_mut_ptr().add(16).cast(), second);
+            }
+            done += 16;
+        }
+        done
+    }
+
+    // Converts 16 hex digits to their values, or returns `None` if any of
+    // them is invalid.
+    #[inline]
+    fn digits(hex: v128) -> Option<v128> {
+        // Subtracting wraps around, so everything below the first digit ends
+        // up above the last one, and an unsigned comparison checks the range.
+        let decimal = u8x16_sub(hex, u8x16_splat(b'0'));
+        let is_decimal = u8x16_le(decimal, u8x16_splat(9));
+        let letter = u8x16_sub(v128_or(hex, u8x16_splat(0x20)), u8x16_splat(b'a'));
+        let is_letter = u8x16_le(letter, u8x16_splat(5));
+        if !u8x16_all_true(v128_or(is_decimal, is_letter)) {
+            return None;
+        }
+        let letter = u8x16_add(letter, u8x16_splat(10));
+        Some(v128_bitselect(decimal, letter, is_decimal))
+    }
+
+    // Combines each pair of digit values into a byte, in a 16 bit lane.
+    #[inline]
+    fn pairs(digits: v128) -> v128 {
+        // The first digit of a pair is in the low half of the lane.
+        let high = u16x8_shl(v128_and(digits, u16x8_splat(0x0f)), 4);
+        v128_or(high, u16x8_shr(digits, 8))
+    }
+
+    // Decodes blocks of 32 digits, returning the number of bytes decoded.
+    pub(super) fn decode(hex: &[u8], out: &mut [MaybeUninit<u8>]) -> usize {
+        let mut done = 0;
+        for (chunk, out) in hex.chunks_exact(32).zip(out.chunks_exact_mut(16)) {
+            // Saftey: the chunk is 32 bytes long.
+            let (first, second) = unsafe {
+                (
+                    v128_load(chunk.as_ptr().cast()),
+                    v128_load(chunk.as_ptr().add(16).cast()),
+                )
+            };
+            let (first, second) = match (digits(first), digits(second)) {
+                (Some(first), Some(second)) => (first, second),
+                _ => break,
+            };
+            let bytes = u8x16_narrow_i16x8(pairs(first), pairs(second));
+            // Saftey: the output chunk is 16 bytes long.
+            unsafe { v128_store(out.as_mut_ptr().cast(), bytes) };
+            done += 16;
+        }
+        done
+    }
+}
+
+#[cfg(all(
+    feature = "rvv",
+    any(target_arch = "riscv32", target_arch = "riscv64"),
+    target_feature = "v"
+))]
+mod rvv {
+    use core::arch::asm;
+    use core::mem::MaybeUninit;
+
+    // Encodes all of `input`, with as many bytes per iteration as the vector
+    // registers hold.
+    pub(super) fn encode(input: &[u8], output: &mut [MaybeUninit<u8>], table: &[u8; 16]) -> usize {
+        // Saftey: the loop reads `input.len()` bytes from `input` and writes
+        // twice as many to `output`, which is twice as long.
+        unsafe {
+            asm!(
+                "vsetivli zero, 16, e8, m1, ta, ma",
+                "vle8.v v8, ({table})",
+                "2:",
+                "vsetvli {vl}, {len}, e8, m1, ta, ma",
+                "vle8.v v0, ({input})",
+                "vsrl.vi v1, v0, 4",
+                "vand.vi v2, v0, 15",
+                "vrgather.vv v16, v8, v1",
+                "vrgather.vv v17, v8, v2",
+                // Stores the high and low digits interleaved.
+                "vsseg2e8.v v16, ({output})",
+                "add {input}, {input}, {vl}",
+                "sub {len}, {len}, {vl}",
+                "slli {vl}, {vl}, 1",
+                "add {output}, {output}, {vl}",
+                "bnez {len}, 2b",
+                table = in(reg) table.as_ptr(),
+                input = inout(reg) input.as_ptr() => _,
+                output = inout(reg) output.as_mut_ptr() => _,
+                len = inout(reg) input.len() => _,
+                vl = out(reg) _,
+                out("v0") _,
+                out("v1") _,
+                out("v2") _,
+                out("v8") _,
+                out("v16") _,
+                out("v17") _,
+                options(nostack),
+            );
+        }
+        input.len()
+    }
+
+    // Decodes `hex` with as many bytes per iteration as the vector registers
+    // hold, stopping at the first invalid digit. Returns the number of bytes
+    // decoded.
+    pub(super) fn decode(hex: &[u8], out: &mut [MaybeUninit<u8>]) -> usize {
+        let done: usize;
+        // Saftey: the loop reads `2 * out.len()` bytes from `hex`, which is
+        // that long, and writes at most `out.len()` bytes to `out`.
+        unsafe {
+            asm!(
+                "2:",
+                "vsetvli {vl}, {len}, e8, m1, ta, ma",
+                // Loads the high digits into v2 and the low digits into v3.
+                "vlseg2e8.v v2, ({hex})",
+                // Subtracting wraps around, so everything below the first
+                // digit ends up above the last one, and an unsigned
+                // comparison checks the range.
+                "vsub.vx v5, v2, {zero}",
+                "vor.vx v6, v2, {lower}",
+                "vsub.vx v6, v6, {a}",
+                "vadd.vi v7, v6, 10",
+                "vmsleu.vi v0, v5, 9",
+                "vmsleu.vi v1, v6, 5",
+                "vmor.mm v1, v1, v0",
+                "vmerge.vvm v4, v7, v5, v0",
+                // The same for the low digits.
+                "vsub.vx v5, v3, {zero}",
+                "vor.vx v6, v3, {lower}",
+                "vsub.vx v6, v6, {a}",
+                "vadd.vi v7, v6, 10",
+                "vmsleu.vi v0, v5, 9",
+                "vmsleu.vi v9, v6, 5",
+                "vmor.mm v9, v9, v0",
+                "vmerge.vvm v8, v7, v5, v0",
+                // If a pair is invalid, only the pairs before it are stored,
+                // and the loop ends.
+                "vmand.mm v1, v1, v9",
+                "vmnot.m v1, v1",
+                "vfirst.m {first}, v1",
+                "bltz {first}, 3f",
+                "vsetvli {vl}, {first}, e8, m1, ta, ma",
+                "li {len}, 0",
+                "3:",
+                "vsll.vi v4, v4, 4",
+                "vor.vv v4, v4, v8",
+                "vse8.v v4, ({out})",
+                "add {out}, {out}, {vl}",
+                "add {done}, {done}, {vl}",
+                "sub {len}, {len}, {vl}",
+                "slli {vl}, {vl}, 1",
+                "add {hex}, {hex}, {vl}",
+                "bgtz {len}, 2b",
+                zero = in(reg) usize::from(b'0'),
+                lower = in(reg) 0x20_usize,
+                a = in(reg) usize::from(b'a'),
+                hex = inout(reg) hex.as_ptr() => _,
+                out = inout(reg) out.as_mut_ptr() => _,
+                len = inout(reg) out.len() => _,
+                done = inout(reg) 0_usize => done,
+                vl = out(reg) _,
+                first = out(reg) _,
+                out("v0") _,
+                out("v1") _,
+                out("v2") _,
+                out("v3") _,
+                out("v4") _,
+                out("v5") _,
+                out("v6") _,
+                out("v7") _,
+                out("v8") _,
+                out("v9") _,
+                options(nostack),
+            );
+        }
+        done
+    }
+}
+
+// Encodes a prefix of `input` into `output`, which is twice as long, with
+// the kernels of `backend`. Returns the number of bytes encoded.
+//
+// The CPU must support `backend`.
+#[inline]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub(crate) unsafe fn encode(
+    backend: Backend,
+    input: &[u8],
+    output: &mut [MaybeUninit<u8>],
+    table: &[u8; 16],
+) -> usize {
+    debug_assert_eq!(2 * input.len(), output.len());
+    match backend {
+        Backend::Avx512Vbmi | Backend::Avx2 if input.len() >= 32 => {
+            avx2::encode(input, output, table)
+        }
+        Backend::Avx512Vbmi | Backend::Avx2 | Backend::Ssse3 if input.len() >= 16 => {
+            ssse3::encode(input, output, table)
+        }
+        _ => 0,
+    }
+}
+
+// Decodes a prefix of `hex` into `out`, which is half as long, with the
+// kernels of `backend`. Returns the number of bytes decoded, stopping early
+// if `hex` contains invalid characters.
+//
+// The CPU must support `backend`.
+#[inline]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub(crate) unsafe fn decode(backend: Backend, hex: &[u8], out: &mut [MaybeUninit<u8>]) -> usize {
+    debug_assert_eq!(hex.len(), 2 * out.len());
+    match backend {
+        Backend::Avx512Vbmi if out.len() >= 32 => avx512::decode(hex, out),
+        Backend::Avx2 if out.len() >= 32 => avx2::decode(hex, out),
+        Backend::Avx512Vbmi | Backend::Avx2 | Backend::Ssse3 if out.len() >= 16 => {
+            ssse3::decode(hex, out)
+        }
+        _ => 0,
+    }
+}
+
+#[inline]
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+// The arguments are unused on targets without kernels.
+#[allow(unused_variables)]
+pub(crate) unsafe fn encode(
+    backend: Backend,
+    input: &[u8],
+    output: &mut [MaybeUninit<u8>],
+    table: &[u8; 16],
+) -> usize {
+    debug_assert_eq!(2 * input.len(), output.len());
+    match backend {
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        Backend::Simd128 => simd128::encode(input, output, table),
+        #[cfg(all(
+            feature = "rvv",
+            any(target_arch = "riscv32", target_arch = "riscv64"),
+            target_feature = "v"
+        ))]
+        Backend::RiscvVector => rvv::encode(input, output, table),
+        _ => 0,
+    }
+}
+
+#[inline]
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+pub(crate) unsafe fn decode(backend: Backend, hex: &[u8], out: &mut [MaybeUninit<u8>]) -> usize {
+    debug_assert_eq!(hex.len(), 2 * out.len());
+    match backend {
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        Backend::Simd128 => simd128::decode(hex, out),
+        #[cfg(all(
+            feature = "rvv",
+            any(target_arch = "riscv32", target_arch = "riscv64"),
+            target_feature = "v"
+        ))]
+        Backend::RiscvVector => rvv::decode(hex, out),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromHexError, HEX_CHARS_LOWER, HEX_CHARS_UPPER};
+    use pretty_assertions::assert_eq;
+
+    type Encode = unsafe fn(&[u8], &mut [MaybeUninit<u8>], &[u8; 16]) -> usize;
+    type Decode = unsafe fn(&[u8], &mut [MaybeUninit<u8>]) -> usize;
+
+    // The kernels the CPU supports, with the number of bytes in a block.
+    fn kernels() -> Vec<(usize, Option<Encode>, Decode)> {
+        #[allow(unused_mut)]
+        let mut kernels = Vec::new();
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx512bw") && is_x86_feature_detected!("avx512vbmi") {
+                kernels.push((32, None, avx512::decode as Decode));
+            }
+            if is_x86_feature_detected!("avx2") {
+                kernels.push((32, Some(avx2::encode as Encode), avx2::decode as Decode));
+            }
+            if is_x86_feature_detected!("ssse3") {
+                kernels.push((16, Some(ssse3::encode as Encode), ssse3::decode as Decode));
+            }
+        }
+        #[cfg(all(
+            feature = "rvv",
+            any(target_arch = "riscv32", target_arch = "riscv64"),
+            target_feature = "v"
+        ))]
+        kernels.push((1, Some(rvv::encode as Encode), rvv::decode as Decode));
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        kernels.push((
+            16,
+            Some(simd128::encode as Encode),
+            simd128::decode as Decode,
+        ));
+        kernels
+    }
+
+    fn data() -> Vec<u8> {
+        (0..1000).map(|i| (i * 37 % 256) as u8).collect()
+    }
+
+    const LENS: [usize; 10] = [0, 1, 15, 16, 17, 31, 32, 33, 95, 1000];
+
+    #[test]
+    fn test_encode() {
+        let data = data();
+        for (block, encode) in kernels().into_iter().filter_map(|(b, e, _)| Some((b, e?))) {
+            for len in LENS {
+                for table in [HEX_CHARS_LOWER, HEX_CHARS_UPPER] {
+                    let mut hex = vec![0; 2 * len];
+                    // Saftey: the CPU supports the kernel, which only writes
+                    // initialized bytes.
+                    let done = unsafe { encode(&data[..len], crate::as_uninit(&mut hex), table) };
+                    assert_eq!(done, len - len % block);
+                    let expected: Vec<u8> = data[..done]
+                        .iter()
+                        .flat_map(|&b| [table[(b >> 4) as usize], table[(b & 0xf) as usize]])
+                        .collect();
+                    assert_eq!(&hex[..2 * done], &expected[..]);
+                }
+            }
+        }
+        // and through the public functions
+        for len in LENS {
+            assert_eq!(
+                crate::encode_upper(&data[..len]),
+                crate::encode(&data[..len]).to_uppercase()
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode() {
+        let data = data();
+        for (block, _, decode) in kernels() {
+            for len in LENS {
+                for hex in [
+                    crate::encode(&data[..len]),
+                    crate::encode_upper(&data[..len]),
+                ] {
+                    let mut out = vec![0; len];
+                    // Saftey: the CPU supports the kernel, which only writes
+                    // initialized bytes.
+                    let done = unsafe { decode(hex.as_bytes(), crate::as_uninit(&mut out)) };
+                    assert_eq!(done, len - len % block);
+                    assert_eq!(&out[..done], &data[..done]);
+                }
+            }
+        }
+        // and through the public functions
+        for len in LENS {
+            let hex = crate::encode(&data[..len]);
+            assert_eq!(crate::decode(&hex).unwrap(), &data[..len]);
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        // every byte value, in each position of a block
+        for c in 0..=255_u8 {
+            let valid = crate::DECODE_TABLE[c as usize] != crate::tables::INVALID;
+            for i in [0, 1, 15, 16, 31, 32, 63, 64, 127] {
+                let mut hex = [b'a'; 128];
+                hex[i] = c;
+                let mut out = [0; 64];
+                for (block, _, decode) in kernels() {
+                    // Saftey: the CPU supports the kernel, which only writes
+                    // initialized bytes.
+                    let done = unsafe { decode(&hex, crate::as_uninit(&mut out)) };
+                    if valid {
+                        assert_eq!(done, 64);
+                        assert_eq!(out[i / 2], crate::decode(&hex[i & !1..][..2]).unwrap()[0]);
+                    } else {
+                        assert_eq!(done, i / (2 * block) * block, "{} {}", c, i);
+                    }
+                }
+                if valid {
+                    assert_eq!(crate::decode_to_slice(hex, &mut out), Ok(()));
+                } else {
+                    assert_eq!(
+                        crate::decode_to_slice(hex, &mut out),
+                        Err(FromHexError::InvalidHexCharacter {
+                            c: c as char,
+                            index: i
+                        })
+                    );
+                }
+            }
+        }
+    }
+}
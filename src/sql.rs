@@ -0,0 +1,204 @@
+//! SQL hex literals.
+//!
+//! SQL writes binary strings as `X'DEADBEEF'` (standard SQL, supported by
+//! most databases) or `0xDEADBEEF` (MySQL, MariaDB, SQLite and SQL Server).
+//! [`Literal`] emits either form and [`decode`] parses both.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! use hex::sql::{Literal, Style};
+//!
+//! let key = [0xde, 0xad, 0xbe, 0xef];
+//! let query = format!("SELECT * FROM t WHERE key = {}", Literal::new(key, Style::Quoted));
+//! assert_eq!(query, "SELECT * FROM t WHERE key = X'DEADBEEF'");
+//! assert_eq!(hex::sql::decode("X'DEADBEEF'"), Ok(key.to_vec()));
+//! # }
+//! ```
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+use crate::Case;
+#[cfg(feature = "alloc")]
+use crate::{FromHexError, DECODE_TABLE};
+
+/// The syntax of a SQL hex literal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Style {
+    /// The standard `X'DEADBEEF'` syntax.
+    #[default]
+    Quoted,
+    /// The `0xDEADBEEF` syntax used by MySQL and others.
+    ///
+    /// As `0x` on its own isn't a valid literal, empty data is written as
+    /// `X''` instead.
+    Prefixed,
+}
+
+/// Displays bytes as a SQL hex literal, using uppercase digits.
+///
+/// The output only contains ASCII letters, digits and quotes, so it can be
+/// embedded into a statement as is.
+///
+/// # Example
+///
+/// ```
+/// use hex::sql::{Literal, Style};
+///
+/// assert_eq!(Literal::new(b"kiwi", Style::Quoted).to_string(), "X'6B697769'");
+/// assert_eq!(Literal::new(b"kiwi", Style::Prefixed).to_string(), "0x6B697769");
+/// assert_eq!(Literal::new(b"", Style::Prefixed).to_string(), "X''");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Literal<T> {
+    data: T,
+    style: Style,
+}
+
+impl<T: AsRef<[u8]>> Literal<T> {
+    /// Creates a literal of `data` using the given style.
+    pub fn new(data: T, style: Style) -> Self {
+        Literal { data, style }
+    }
+}
+
+impl<T: AsRef<[u8]>> fmt::Display for Literal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let data = self.data.as_ref();
+        if self.style == Style::Prefixed && !data.is_empty() {
+            f.write_str("0x")?;
+            crate::fmt_hex(data, Case::Upper, f)
+        } else {
+            f.write_str("X'")?;
+            crate::fmt_hex(data, Case::Upper, f)?;
+            f.write_str("'")
+        }
+    }
+}
+
+/// Decodes a SQL hex literal in either style.
+///
+/// The `X` of a quoted literal may be lowercase, the `x` of a prefixed one
+/// may not. Following MySQL, a quoted literal must have an even number of
+/// digits, while a prefixed one with an odd number of digits is padded with a
+/// leading zero.
+///
+/// An unterminated quoted literal, as well as `0x` without any digits, is
+/// reported as [`FromHexError::InvalidStringLength`]. Other malformed input
+/// is reported as an invalid character, including quotes inside a quoted
+/// literal.
+///
+/// # Example
+///
+/// ```
+/// use hex::FromHexError;
+///
+/// assert_eq!(hex::sql::decode("x'cafe'"), Ok(vec![0xca, 0xfe]));
+/// assert_eq!(hex::sql::decode("0xABC"), Ok(vec![0x0a, 0xbc]));
+/// assert_eq!(hex::sql::decode("X'ABC'"), Err(FromHexError::OddLength));
+/// assert_eq!(hex::sql::decode("X'AB"), Err(FromHexError::InvalidStringLength));
+/// assert_eq!(
+///     hex::sql::decode("X'AB'C'"),
+///     Err(FromHexError::InvalidHexCharacter { c: '\'', index: 4 })
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode(literal: &str) -> Result<Vec<u8>, FromHexError> {
+    match literal.as_bytes() {
+        [b'X' | b'x', b'\'', rest @ ..] => match rest.split_last() {
+            Some((b'\'', digits)) => crate::decode(digits).map_err(|err| err.offset(2)),
+            _ => Err(FromHexError::InvalidStringLength),
+        },
+        [b'0', b'x', digits @ ..] if !digits.is_empty() => {
+            let odd = digits.len() % 2;
+            let mut out = vec![0; digits.len() / 2 + odd];
+            if odd == 1 {
+                out[0] = DECODE_TABLE[digits[0] as usize];
+                if out[0] == u8::MAX {
+                    return Err(FromHexError::InvalidHexCharacter {
+                        c: literal[2..].chars().next().unwrap(),
+                        index: 2,
+                    });
+                }
+            }
+            crate::decode_to_slice(&digits[odd..], &mut out[odd..])
+                .map_err(|err| err.offset(2 + odd))?;
+            Ok(out)
+        }
+        [] | [b'0'] | [b'0', b'x'] => Err(FromHexError::InvalidStringLength),
+        [b'0', ..] => Err(FromHexError::InvalidHexCharacter {
+            c: literal[1..].chars().next().unwrap(),
+            index: 1,
+        }),
+        _ => Err(FromHexError::InvalidHexCharacter {
+            c: literal.chars().next().unwrap(),
+            index: 0,
+        }),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "alloc")]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_literal() {
+        assert_eq!(Literal::new([0xab], Style::Quoted).to_string(), "X'AB'");
+        assert_eq!(Literal::new([0xab], Style::Prefixed).to_string(), "0xAB");
+        assert_eq!(Literal::new([], Style::Quoted).to_string(), "X''");
+        assert_eq!(Literal::new([], Style::Prefixed).to_string(), "X''");
+    }
+
+    #[test]
+    fn test_decode() {
+        for style in [Style::Quoted, Style::Prefixed] {
+            for data in [&[][..], &[0], &[0xde, 0xad, 0xbe, 0xef]] {
+                let literal = Literal::new(data, style).to_string();
+                assert_eq!(decode(&literal), Ok(data.to_vec()), "{}", literal);
+            }
+        }
+        assert_eq!(decode("0x1"), Ok(vec![1]));
+        assert_eq!(
+            decode("0xg"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 2 })
+        );
+        assert_eq!(
+            decode("0x1g1"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+        );
+        assert_eq!(
+            decode("X'1g'"),
+            Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+        );
+    }
+
+    #[test]
+    fn test_decode_malformed() {
+        for literal in ["", "0", "0x", "X'", "x'00", "X'00' "] {
+            assert_eq!(
+                decode(literal),
+                Err(FromHexError::InvalidStringLength),
+                "{}",
+                literal
+            );
+        }
+        assert_eq!(
+            decode("0X00"),
+            Err(FromHexError::InvalidHexCharacter { c: 'X', index: 1 })
+        );
+        assert_eq!(
+            decode("X00"),
+            Err(FromHexError::InvalidHexCharacter { c: 'X', index: 0 })
+        );
+        assert_eq!(
+            decode("'00'"),
+            Err(FromHexError::InvalidHexCharacter { c: '\'', index: 0 })
+        );
+    }
+}
@@ -0,0 +1,91 @@
+//! Implementations of the [`sqlx`] encoding traits for the wrapper types.
+//!
+//! [`HexString`] maps to `TEXT` columns, [`FixedHex`] to `BLOB`/`BYTEA`
+//! columns holding the raw bytes.
+use sqlx::{encode::IsNull, error::BoxDynError, Database, Decode, Encode, Type};
+use std::convert::TryFrom;
+
+use crate::{FixedHex, HexString};
+
+impl<DB> Type<DB> for HexString
+where
+    DB: Database,
+    String: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB> Encode<'q, DB> for HexString
+where
+    DB: Database,
+    String: Encode<'q, DB>,
+{
+    fn encode(self, buf: &mut <DB as Database>::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+        self.into_string().encode(buf)
+    }
+
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        String::from(self.as_str()).encode(buf)
+    }
+}
+
+impl<'r, DB> Decode<'r, DB> for HexString
+where
+    DB: Database,
+    String: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(HexString::new(String::decode(value)?)?)
+    }
+}
+
+impl<DB, const N: usize> Type<DB> for FixedHex<N>
+where
+    DB: Database,
+    Vec<u8>: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <Vec<u8> as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <Vec<u8> as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB, const N: usize> Encode<'q, DB> for FixedHex<N>
+where
+    DB: Database,
+    Vec<u8>: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        self.0.to_vec().encode(buf)
+    }
+}
+
+impl<'r, DB, const N: usize> Decode<'r, DB> for FixedHex<N>
+where
+    DB: Database,
+    Vec<u8>: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let bytes = Vec::decode(value)?;
+        let len = bytes.len();
+        match <[u8; N]>::try_from(bytes) {
+            Ok(bytes) => Ok(FixedHex(bytes)),
+            Err(_) => Err(format!("expected {} bytes, got {}", N, len).into()),
+        }
+    }
+}
@@ -0,0 +1,1087 @@
+//! Validated hex strings.
+//!
+//! [`HexStr`] and [`HexString`] relate to each other like `str` and `String`:
+//! the former is a borrowed view which can be checked once and then passed
+//! around without having to validate it again, the latter owns its contents.
+#[cfg(feature = "alloc")]
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::borrow::Borrow;
+use core::convert::TryFrom;
+use core::ops::{
+    Bound, Deref, Index, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo,
+    RangeToInclusive,
+};
+#[cfg(feature = "alloc")]
+use core::str::FromStr;
+use core::{cmp::Ordering, fmt, hash, str};
+
+#[cfg(feature = "alloc")]
+use crate::FromHex;
+use crate::{FromHexError, DECODE_TABLE};
+
+// Decodes a pair of digits which are known to be valid.
+#[inline]
+fn decode_pair(pair: &[u8]) -> u8 {
+    (DECODE_TABLE[pair[0] as usize] << 4) | DECODE_TABLE[pair[1] as usize]
+}
+
+/// A borrowed string slice that is guaranteed to contain valid hex.
+///
+/// The contents always have an even length and only consist of hex digits,
+/// which may be of either case. Functions taking a `&HexStr` can skip
+/// validating their input, as this has already been done upstream.
+///
+/// Comparisons work as if both sides were decoded: with other hex strings
+/// (including plain `str`s) they are case insensitive, and comparisons with
+/// `[u8]` compare against the decoded bytes.
+///
+/// # Example
+///
+/// ```
+/// use hex::HexStr;
+///
+/// let hex = HexStr::new("6b697769")?;
+/// let mut bytes = [0_u8; 4];
+/// hex.decode_to_slice(&mut bytes)?;
+/// assert_eq!(&bytes, b"kiwi");
+///
+/// assert!(HexStr::new("kiwi").is_err());
+///
+/// assert_eq!(hex, "6B697769");
+/// assert_eq!(*hex, b"kiwi"[..]);
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+#[repr(transparent)]
+pub struct HexStr(str);
+
+impl HexStr {
+    /// Validates `s` as hex.
+    pub fn new(s: &str) -> Result<&HexStr, FromHexError> {
+        crate::validate(s.as_bytes())?;
+        Ok(HexStr::new_unchecked(s))
+    }
+
+    /// Validates `s` as hex, returning it as a `&HexStr`.
+    pub fn from_bytes(s: &[u8]) -> Result<&HexStr, FromHexError> {
+        crate::validate(s)?;
+        // Valid hex is always ascii, which makes it valid utf8 as well.
+        Ok(HexStr::new_unchecked(str::from_utf8(s).unwrap()))
+    }
+
+    pub(crate) fn new_unchecked(s: &str) -> &HexStr {
+        debug_assert!(crate::validate(s.as_bytes()).is_ok());
+        // Safety: `HexStr` is a `repr(transparent)` wrapper around `str`.
+        unsafe { &*(s as *const str as *const HexStr) }
+    }
+
+    /// Returns the hex string as a `&str`.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the number of bytes encoded by this string, which is half of
+    /// its length.
+    #[must_use]
+    pub fn as_bytes_len(&self) -> usize {
+        self.0.len() / 2
+    }
+
+    /// Decodes the string into raw bytes.
+    ///
+    /// This cannot fail, as the contents have already been validated.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn decode(&self) -> Vec<u8> {
+        Vec::from_hex(&self.0).expect("HexStr contains valid hex")
+    }
+
+    /// Decodes the string into a mutable bytes slice.
+    ///
+    /// The only possible error is [`FromHexError::InvalidStringLength`], if
+    /// `out` isn't exactly [`as_bytes_len`](Self::as_bytes_len) bytes long.
+    pub fn decode_to_slice(&self, out: &mut [u8]) -> Result<(), FromHexError> {
+        crate::decode_to_slice(&self.0, out)
+    }
+
+    /// Decodes the byte at index `i`, or returns `None` if it is out of
+    /// bounds.
+    ///
+    /// Only the two digits encoding the byte are looked at, so this is cheap
+    /// even for huge strings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let hex = hex::HexStr::new("6b697769")?;
+    /// assert_eq!(hex.get_byte(1), Some(b'i'));
+    /// assert_eq!(hex.get_byte(4), None);
+    /// # Ok::<(), hex::FromHexError>(())
+    /// ```
+    #[must_use]
+    pub fn get_byte(&self, i: usize) -> Option<u8> {
+        let start = i.checked_mul(2)?;
+        let end = start.checked_add(2)?;
+        self.0.as_bytes().get(start..end).map(decode_pair)
+    }
+
+    /// Returns an iterator which decodes the bytes of the string on demand.
+    ///
+    /// The iterator can be consumed from either end, e.g. to process the
+    /// least significant bytes of a big-endian number first. Skipping ahead
+    /// (e.g. with [`Iterator::nth`] or [`Iterator::skip`]) does not decode
+    /// the skipped bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let hex = hex::HexStr::new("6b697769")?;
+    /// assert!(hex.bytes().eq(b"kiwi".iter().copied()));
+    /// assert!(hex.bytes().rev().eq(b"iwik".iter().copied()));
+    /// assert_eq!(hex.bytes().nth(2), Some(b'w'));
+    /// # Ok::<(), hex::FromHexError>(())
+    /// ```
+    pub fn bytes(&self) -> Bytes<'_> {
+        Bytes {
+            inner: self.0.as_bytes().chunks_exact(2),
+        }
+    }
+
+    /// Returns an iterator over the two-digit pairs of the string, each of
+    /// which encodes a single byte.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let hex = hex::HexStr::new("0aFf")?;
+    /// let pairs: Vec<&str> = hex.pairs().map(|pair| pair.as_str()).collect();
+    /// assert_eq!(pairs, ["0a", "Ff"]);
+    /// # Ok::<(), hex::FromHexError>(())
+    /// ```
+    pub fn pairs(&self) -> Pairs<'_> {
+        Pairs {
+            inner: self.0.as_bytes().chunks_exact(2),
+        }
+    }
+
+    /// Divides the string into two at the byte index `n`, i.e. after the
+    /// first `2 * n` digits.
+    ///
+    /// Both halves are valid hex again, so e.g. the header and the body of a
+    /// hex-encoded record can be passed on without validating them again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is larger than [`as_bytes_len`](Self::as_bytes_len).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let record = hex::HexStr::new("0102cafebabe")?;
+    /// let (header, body) = record.split_at_byte(2);
+    /// assert_eq!(header, "0102");
+    /// assert_eq!(body.as_bytes_len(), 4);
+    /// # Ok::<(), hex::FromHexError>(())
+    /// ```
+    #[must_use]
+    pub fn split_at_byte(&self, n: usize) -> (&HexStr, &HexStr) {
+        assert!(
+            n <= self.as_bytes_len(),
+            "byte index {} is out of bounds of a hex string of {} bytes",
+            n,
+            self.as_bytes_len()
+        );
+        let (head, tail) = self.0.split_at(2 * n);
+        (HexStr::new_unchecked(head), HexStr::new_unchecked(tail))
+    }
+
+    /// Returns `true` if the decoded bytes start with `needle`.
+    ///
+    /// Only the digits which are compared are decoded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let hex = hex::HexStr::new("CAFEbabe")?;
+    /// assert!(hex.starts_with_bytes(&[0xca, 0xfe]));
+    /// assert!(!hex.starts_with_bytes(&[0xba, 0xbe]));
+    /// # Ok::<(), hex::FromHexError>(())
+    /// ```
+    #[must_use]
+    pub fn starts_with_bytes(&self, needle: &[u8]) -> bool {
+        needle.len() <= self.as_bytes_len() && self.split_at_byte(needle.len()).0.eq_bytes(needle)
+    }
+
+    /// Returns `true` if the decoded bytes end with `needle`.
+    ///
+    /// Only the digits which are compared are decoded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let hex = hex::HexStr::new("CAFEbabe")?;
+    /// assert!(hex.ends_with_bytes(&[0xba, 0xbe]));
+    /// assert!(!hex.ends_with_bytes(&[0xca, 0xfe]));
+    /// # Ok::<(), hex::FromHexError>(())
+    /// ```
+    #[must_use]
+    pub fn ends_with_bytes(&self, needle: &[u8]) -> bool {
+        let len = self.as_bytes_len();
+        needle.len() <= len && self.split_at_byte(len - needle.len()).1.eq_bytes(needle)
+    }
+
+    /// Returns `true` if the decoded bytes contain `needle`.
+    ///
+    /// The string is searched without decoding it as a whole, see
+    /// [`find_bytes`](crate::find_bytes).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let hex = hex::HexStr::new("CAFEbabe")?;
+    /// assert!(hex.contains_bytes(&[0xfe, 0xba]));
+    /// // "eb" isn't byte-aligned
+    /// assert!(!hex.contains_bytes(&[0xeb]));
+    /// # Ok::<(), hex::FromHexError>(())
+    /// ```
+    #[must_use]
+    pub fn contains_bytes(&self, needle: &[u8]) -> bool {
+        crate::find_bytes(&self.0, needle).is_some()
+    }
+
+    /// Returns a subslice of the string, or `None` if `range` is out of
+    /// bounds or doesn't start and end at a byte boundary.
+    ///
+    /// Like for `str`, the range is given in characters, so both of its ends
+    /// have to be even for the subslice to be valid hex. Indexing with a range
+    /// (e.g. `&hex[2..6]`) does the same, but panics instead of returning
+    /// `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let hex = hex::HexStr::new("0102cafebabe")?;
+    /// assert_eq!(hex.get(4..8).unwrap(), "cafe");
+    /// assert_eq!(&hex[8..], "babe");
+    /// assert!(hex.get(3..8).is_none());
+    /// assert!(hex.get(..14).is_none());
+    /// # Ok::<(), hex::FromHexError>(())
+    /// ```
+    #[must_use]
+    pub fn get<R: RangeBounds<usize>>(&self, range: R) -> Option<&HexStr> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1)?,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.0.len(),
+        };
+        if start % 2 != 0 || end % 2 != 0 {
+            return None;
+        }
+        self.0.get(start..end).map(HexStr::new_unchecked)
+    }
+}
+
+// Implements indexing `HexStr` with `$ty`, keeping the validated type.
+macro_rules! impl_index {
+    ($($ty:ty),*) => {
+        $(
+            impl Index<$ty> for HexStr {
+                type Output = HexStr;
+
+                fn index(&self, range: $ty) -> &HexStr {
+                    match self.get(range.clone()) {
+                        Some(hex) => hex,
+                        None => panic!(
+                            "range {:?} is out of bounds or not on a byte boundary of a hex string of length {}",
+                            range,
+                            self.0.len()
+                        ),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_index!(
+    Range<usize>,
+    RangeFrom<usize>,
+    RangeTo<usize>,
+    RangeFull,
+    RangeInclusive<usize>,
+    RangeToInclusive<usize>
+);
+
+impl Deref for HexStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for HexStr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for HexStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for HexStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+// Writes the digits of `hex` in the given case.
+fn fmt_digits(hex: &str, case: crate::Case, f: &mut fmt::Formatter) -> fmt::Result {
+    let mut buf = [0_u8; 128];
+    for chunk in hex.as_bytes().chunks(buf.len()) {
+        let buf = &mut buf[..chunk.len()];
+        buf.copy_from_slice(chunk);
+        match case {
+            crate::Case::Lower => buf.make_ascii_lowercase(),
+            crate::Case::Upper => buf.make_ascii_uppercase(),
+        }
+        // Saftey: Changing the case of hex digits keeps them valid utf8
+        f.write_str(unsafe { str::from_utf8_unchecked(buf) })?;
+    }
+    Ok(())
+}
+
+/// Formats the decoded bytes as hex, like a big-endian integer.
+///
+/// Unlike integers, leading zeros are kept. The `#` flag adds a `0x` prefix.
+///
+/// # Example
+///
+/// ```
+/// let hex = hex::HexStr::new("00Ab")?;
+/// assert_eq!(format!("{:x}", hex), "00ab");
+/// assert_eq!(format!("{:#X}", hex), "0x00AB");
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+impl fmt::LowerHex for HexStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        crate::pad_hex(f, self.0.len(), |f| {
+            fmt_digits(&self.0, crate::Case::Lower, f)
+        })
+    }
+}
+
+/// Like the `LowerHex` implementation, but using uppercase letters.
+impl fmt::UpperHex for HexStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        crate::pad_hex(f, self.0.len(), |f| {
+            fmt_digits(&self.0, crate::Case::Upper, f)
+        })
+    }
+}
+
+// Comparisons and hashing are defined in terms of the decoded bytes, which
+// makes them case insensitive.
+impl PartialEq for HexStr {
+    fn eq(&self, other: &HexStr) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Eq for HexStr {}
+
+impl HexStr {
+    // Compares with a string, as if both were decoded.
+    fn eq_str(&self, other: &str) -> bool {
+        // Changing the case can't turn a non-digit into a digit.
+        self.0.eq_ignore_ascii_case(other)
+    }
+
+    // Compares with raw bytes, decoding `self` on the fly.
+    fn eq_bytes(&self, other: &[u8]) -> bool {
+        self.as_bytes_len() == other.len() && self.bytes().eq(other.iter().copied())
+    }
+}
+
+// Implements comparisons of `$ty` with strings, as if they were decoded, and
+// with raw bytes, in both directions.
+macro_rules! impl_partial_eq {
+    ($ty:ty) => {
+        impl PartialEq<str> for $ty {
+            fn eq(&self, other: &str) -> bool {
+                AsRef::<HexStr>::as_ref(self).eq_str(other)
+            }
+        }
+
+        impl PartialEq<&str> for $ty {
+            fn eq(&self, other: &&str) -> bool {
+                AsRef::<HexStr>::as_ref(self).eq_str(other)
+            }
+        }
+
+        impl PartialEq<[u8]> for $ty {
+            fn eq(&self, other: &[u8]) -> bool {
+                AsRef::<HexStr>::as_ref(self).eq_bytes(other)
+            }
+        }
+
+        impl PartialEq<$ty> for str {
+            fn eq(&self, other: &$ty) -> bool {
+                other == self
+            }
+        }
+
+        impl PartialEq<$ty> for &str {
+            fn eq(&self, other: &$ty) -> bool {
+                other == self
+            }
+        }
+
+        impl PartialEq<$ty> for [u8] {
+            fn eq(&self, other: &$ty) -> bool {
+                other == self
+            }
+        }
+    };
+}
+
+impl_partial_eq!(HexStr);
+#[cfg(feature = "alloc")]
+impl_partial_eq!(HexString);
+
+impl PartialOrd for HexStr {
+    fn partial_cmp(&self, other: &HexStr) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HexStr {
+    fn cmp(&self, other: &HexStr) -> Ordering {
+        // Lowercase digits sort in the same order as the nibbles they encode.
+        let lhs = self.0.bytes().map(|c| c.to_ascii_lowercase());
+        let rhs = other.0.bytes().map(|c| c.to_ascii_lowercase());
+        lhs.cmp(rhs)
+    }
+}
+
+impl hash::Hash for HexStr {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        state.write_usize(self.as_bytes_len());
+        for byte in self.bytes() {
+            state.write_u8(byte);
+        }
+    }
+}
+
+/// An iterator over the two-digit pairs of a [`HexStr`].
+///
+/// This struct is created by [`HexStr::pairs`].
+#[derive(Debug, Clone)]
+pub struct Pairs<'a> {
+    inner: core::slice::ChunksExact<'a, u8>,
+}
+
+impl<'a> Iterator for Pairs<'a> {
+    type Item = &'a HexStr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|pair| HexStr::new_unchecked(str::from_utf8(pair).unwrap()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Pairs<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|pair| HexStr::new_unchecked(str::from_utf8(pair).unwrap()))
+    }
+}
+
+impl<'a> ExactSizeIterator for Pairs<'a> {}
+
+impl AsRef<HexStr> for HexStr {
+    fn as_ref(&self) -> &HexStr {
+        self
+    }
+}
+
+impl<'a> TryFrom<&'a str> for &'a HexStr {
+    type Error = FromHexError;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        HexStr::new(s)
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for &'a HexStr {
+    type Error = FromHexError;
+
+    fn try_from(s: &'a [u8]) -> Result<Self, Self::Error> {
+        HexStr::from_bytes(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ToOwned for HexStr {
+    type Owned = HexString;
+
+    fn to_owned(&self) -> HexString {
+        HexString::from(self)
+    }
+}
+
+/// An iterator which lazily decodes the bytes of a [`HexStr`].
+///
+/// This struct is created by [`HexStr::bytes`].
+#[derive(Debug, Clone)]
+pub struct Bytes<'a> {
+    inner: core::slice::ChunksExact<'a, u8>,
+}
+
+impl<'a> Iterator for Bytes<'a> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(decode_pair)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth(n).map(decode_pair)
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.inner.last().map(decode_pair)
+    }
+}
+
+impl<'a> DoubleEndedIterator for Bytes<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(decode_pair)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth_back(n).map(decode_pair)
+    }
+}
+
+impl<'a> ExactSizeIterator for Bytes<'a> {}
+
+impl<'a> core::iter::FusedIterator for Bytes<'a> {}
+
+/// An owned string that is guaranteed to contain valid hex.
+///
+/// The contents always have an even length and only consist of lowercase
+/// hex digits (`0-9` and `a-f`). Uppercase input is normalized on
+/// construction, so two `HexString`s encoding the same bytes are always
+/// equal.
+///
+/// # Example
+///
+/// ```
+/// use hex::HexString;
+///
+/// let hex: HexString = "48656C6C6F".parse()?;
+/// assert_eq!(hex.as_str(), "48656c6c6f");
+/// assert_eq!(hex.as_bytes_len(), 5);
+/// assert_eq!(hex.decode(), b"Hello");
+///
+/// assert!("48656c6c6".parse::<HexString>().is_err());
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Text)
+)]
+pub struct HexString(String);
+
+#[cfg(feature = "alloc")]
+impl HexString {
+    /// Validates `s` and wraps it, converting uppercase digits to lowercase.
+    pub fn new<T: Into<String>>(s: T) -> Result<Self, FromHexError> {
+        let mut s = s.into();
+        crate::validate(s.as_bytes())?;
+        s.make_ascii_lowercase();
+        Ok(HexString(s))
+    }
+
+    /// Encodes `data` into a new `HexString`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// assert_eq!(hex::HexString::encode("kiwi").as_str(), "6b697769");
+    /// ```
+    #[must_use]
+    pub fn encode<T: AsRef<[u8]>>(data: T) -> Self {
+        HexString(crate::encode(data))
+    }
+
+    /// Returns the hex string as a `&str`.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the hex string as a `&HexStr`.
+    #[must_use]
+    pub fn as_hex_str(&self) -> &HexStr {
+        HexStr::new_unchecked(&self.0)
+    }
+
+    /// Unwraps the underlying `String`.
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    /// Returns the number of bytes encoded by this string, which is half of
+    /// its length.
+    #[must_use]
+    pub fn as_bytes_len(&self) -> usize {
+        self.0.len() / 2
+    }
+
+    /// Decodes the string into raw bytes.
+    ///
+    /// This cannot fail, as the contents have already been validated.
+    #[must_use]
+    pub fn decode(&self) -> Vec<u8> {
+        Vec::from_hex(&self.0).expect("HexString contains valid hex")
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Deref for HexString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsRef<str> for HexString {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsRef<HexStr> for HexString {
+    fn as_ref(&self) -> &HexStr {
+        self.as_hex_str()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Borrow<HexStr> for HexString {
+    fn borrow(&self) -> &HexStr {
+        self.as_hex_str()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for HexString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Formats the decoded bytes as hex, see the implementation for [`HexStr`].
+#[cfg(feature = "alloc")]
+impl fmt::LowerHex for HexString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(self.as_hex_str(), f)
+    }
+}
+
+/// Formats the decoded bytes as uppercase hex, see the implementation for
+/// [`HexStr`].
+#[cfg(feature = "alloc")]
+impl fmt::UpperHex for HexString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::UpperHex::fmt(self.as_hex_str(), f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromStr for HexString {
+    type Err = FromHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        HexString::new(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl hash::Hash for HexString {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_hex_str().hash(state)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&str> for HexString {
+    type Error = FromHexError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        HexString::new(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<String> for HexString {
+    type Error = FromHexError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        HexString::new(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<&HexStr> for HexString {
+    fn from(hex: &HexStr) -> Self {
+        HexString(hex.as_str().to_ascii_lowercase())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<HexString> for String {
+    fn from(hex: HexString) -> Self {
+        hex.0
+    }
+}
+
+/// A builder assembling a [`HexString`] piece by piece.
+///
+/// Every piece is either raw bytes, which are encoded, or already validated
+/// hex, so the result is valid by construction and [`finish`](Self::finish)
+/// doesn't need to check it again.
+///
+/// # Example
+///
+/// ```
+/// use hex::{HexStr, HexStringBuilder};
+///
+/// let checksum = HexStr::new("CAFE")?;
+/// let mut builder = HexStringBuilder::new();
+/// builder.push_byte(0x01).push_bytes(b"kiwi").push_hex(checksum);
+/// assert_eq!(builder.finish().as_str(), "016b697769cafe");
+/// # Ok::<(), hex::FromHexError>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default)]
+pub struct HexStringBuilder(String);
+
+#[cfg(feature = "alloc")]
+impl HexStringBuilder {
+    /// Creates an empty builder.
+    #[must_use]
+    pub fn new() -> Self {
+        HexStringBuilder(String::new())
+    }
+
+    /// Creates an empty builder with room for `bytes` bytes.
+    #[must_use]
+    pub fn with_capacity(bytes: usize) -> Self {
+        HexStringBuilder(String::with_capacity(bytes.saturating_mul(2)))
+    }
+
+    /// Appends a single byte.
+    pub fn push_byte(&mut self, byte: u8) -> &mut Self {
+        self.push_bytes([byte])
+    }
+
+    /// Appends raw bytes, encoding them as hex.
+    pub fn push_bytes<T: AsRef<[u8]>>(&mut self, data: T) -> &mut Self {
+        self.0.extend(crate::BytesToHexChars::new(
+            data.as_ref(),
+            crate::Case::Lower,
+        ));
+        self
+    }
+
+    /// Appends already validated hex, converting it to lowercase.
+    pub fn push_hex(&mut self, hex: &HexStr) -> &mut Self {
+        let start = self.0.len();
+        self.0.push_str(hex.as_str());
+        self.0[start..].make_ascii_lowercase();
+        self
+    }
+
+    /// Returns the number of bytes appended so far.
+    #[must_use]
+    pub fn as_bytes_len(&self) -> usize {
+        self.0.len() / 2
+    }
+
+    /// Returns the assembled hex string.
+    #[must_use]
+    pub fn finish(self) -> HexString {
+        HexString(self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use alloc::string::ToString;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_hex_str() {
+        let hex = HexStr::new("666F6f626172").unwrap();
+        assert_eq!(hex.as_str(), "666F6f626172");
+        assert_eq!(hex.as_bytes_len(), 6);
+
+        let mut out = [0; 6];
+        hex.decode_to_slice(&mut out).unwrap();
+        assert_eq!(&out, b"foobar");
+        assert_eq!(
+            hex.decode_to_slice(&mut [0; 5]),
+            Err(FromHexError::InvalidStringLength)
+        );
+
+        assert_eq!(hex.get_byte(0), Some(b'f'));
+        assert_eq!(hex.get_byte(5), Some(b'r'));
+        assert_eq!(hex.get_byte(6), None);
+        assert_eq!(hex.get_byte(usize::MAX), None);
+        assert_eq!(hex.get_byte(usize::MAX / 2), None);
+
+        let mut bytes = hex.bytes();
+        assert_eq!(bytes.len(), 6);
+        assert_eq!(bytes.nth(1), Some(b'o'));
+        assert_eq!(bytes.len(), 4);
+        assert_eq!(bytes.next_back(), Some(b'r'));
+        assert_eq!(bytes.nth_back(1), Some(b'b'));
+        assert_eq!(bytes.len(), 1);
+        assert!(bytes.eq(b"o".iter().copied()));
+
+        let mut pairs = hex.pairs();
+        assert_eq!(pairs.len(), 6);
+        assert_eq!(pairs.next().map(HexStr::as_str), Some("66"));
+        assert_eq!(pairs.next_back().map(HexStr::as_str), Some("72"));
+
+        assert_eq!(hex, HexStr::new("666f6f626172").unwrap());
+        assert_ne!(hex, HexStr::new("666f6f62617a").unwrap());
+        assert!(HexStr::new("0A").unwrap() < HexStr::new("0b").unwrap());
+        assert!(HexStr::new("FF").unwrap() < HexStr::new("ff00").unwrap());
+
+        assert_eq!(
+            HexStr::from_bytes(b"abc").unwrap_err(),
+            FromHexError::OddLength
+        );
+        assert_eq!(
+            HexStr::new("zz").unwrap_err(),
+            FromHexError::InvalidHexCharacter { c: 'z', index: 0 }
+        );
+    }
+
+    #[test]
+    fn test_split_at_byte() {
+        let hex = HexStr::new("00aaBBcc").unwrap();
+        let (head, tail) = hex.split_at_byte(1);
+        assert_eq!(head.as_str(), "00");
+        assert_eq!(tail.as_str(), "aaBBcc");
+
+        let (head, tail) = hex.split_at_byte(0);
+        assert_eq!((head.as_str(), tail), ("", hex));
+        let (head, tail) = hex.split_at_byte(4);
+        assert_eq!((head, tail.as_str()), (hex, ""));
+    }
+
+    #[test]
+    fn test_search_bytes() {
+        let hex = HexStr::new("00aaBBcc").unwrap();
+        for needle in [&b""[..], b"\x00", b"\x00\xaa", b"\x00\xaa\xbb\xcc"] {
+            assert!(hex.starts_with_bytes(needle), "{:?}", needle);
+        }
+        for needle in [&b"\xaa"[..], b"\x00\xab", b"\x00\xaa\xbb\xcc\x00"] {
+            assert!(!hex.starts_with_bytes(needle), "{:?}", needle);
+        }
+
+        for needle in [&b""[..], b"\xcc", b"\xbb\xcc", b"\x00\xaa\xbb\xcc"] {
+            assert!(hex.ends_with_bytes(needle), "{:?}", needle);
+        }
+        for needle in [&b"\xbb"[..], b"\xab\xcc", b"\xff\x00\xaa\xbb\xcc"] {
+            assert!(!hex.ends_with_bytes(needle), "{:?}", needle);
+        }
+
+        assert!(hex.contains_bytes(b""));
+        assert!(hex.contains_bytes(b"\xaa\xbb"));
+        assert!(hex.contains_bytes(b"\xcc"));
+        assert!(!hex.contains_bytes(b"\x0a"));
+        assert!(!hex.contains_bytes(b"\xcc\x00"));
+        assert!(!HexStr::new("").unwrap().contains_bytes(b"\x00"));
+    }
+
+    #[test]
+    fn test_get_range() {
+        let hex = HexStr::new("00aaBBcc").unwrap();
+        assert_eq!(hex.get(..).map(HexStr::as_str), Some("00aaBBcc"));
+        assert_eq!(hex.get(2..6).map(HexStr::as_str), Some("aaBB"));
+        assert_eq!(hex.get(2..=5).map(HexStr::as_str), Some("aaBB"));
+        assert_eq!(hex.get(6..).map(HexStr::as_str), Some("cc"));
+        assert_eq!(hex.get(..=1).map(HexStr::as_str), Some("00"));
+        assert_eq!(hex.get(8..8).map(HexStr::as_str), Some(""));
+        assert_eq!(hex.get(1..4), None);
+        assert_eq!(hex.get(2..5), None);
+        let (start, end) = (4, 2);
+        assert_eq!(hex.get(start..end), None);
+        assert_eq!(hex.get(6..10), None);
+        assert_eq!(hex.get(..=usize::MAX), None);
+
+        assert_eq!(hex[2..4].as_str(), "aa");
+        assert_eq!(hex[..4].as_str(), "00aa");
+        assert_eq!(Index::index(hex, ..), hex);
+    }
+
+    #[test]
+    #[should_panic(expected = "range 1..4 is out of bounds or not on a byte boundary")]
+    fn test_index_odd() {
+        let hex = HexStr::new("00aaBBcc").unwrap();
+        let _ = &hex[1..4];
+    }
+
+    #[test]
+    #[should_panic(expected = "byte index 3 is out of bounds of a hex string of 2 bytes")]
+    fn test_split_at_byte_out_of_bounds() {
+        let _ = HexStr::new("0000").unwrap().split_at_byte(3);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_new() {
+        let hex = HexString::new("666F6f626172").unwrap();
+        assert_eq!(hex.as_str(), "666f6f626172");
+        assert_eq!(hex.as_bytes_len(), 6);
+        assert_eq!(hex.decode(), b"foobar");
+        assert_eq!(hex.to_string(), "666f6f626172");
+        assert_eq!(hex.len(), 12);
+        assert_eq!(hex.as_hex_str(), HexStr::new("666F6F626172").unwrap());
+
+        assert_eq!(HexString::new("").unwrap().as_bytes_len(), 0);
+        assert_eq!(HexString::new("123"), Err(FromHexError::OddLength));
+        assert_eq!(
+            HexString::new("12 3"),
+            Err(FromHexError::InvalidHexCharacter { c: ' ', index: 2 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_borrow() {
+        use alloc::{borrow::Cow, collections::BTreeMap};
+
+        let borrowed = HexStr::new("0A0b").unwrap();
+        let owned = borrowed.to_owned();
+        assert_eq!(owned.as_str(), "0a0b");
+        assert_eq!(Borrow::<HexStr>::borrow(&owned), borrowed);
+
+        let mut map = BTreeMap::new();
+        map.insert(owned, 1);
+        assert_eq!(map.get(borrowed), Some(&1));
+
+        #[cfg(feature = "std")]
+        {
+            let mut map = std::collections::HashMap::new();
+            map.insert(HexString::new("0a0b").unwrap(), 1);
+            assert_eq!(map.get(borrowed), Some(&1));
+        }
+
+        let cow: Cow<HexStr> = Cow::Borrowed(borrowed);
+        assert_eq!(cow.into_owned(), HexString::try_from("0a0b").unwrap());
+
+        assert_eq!(<&HexStr>::try_from("0a0b"), Ok(borrowed));
+        assert_eq!(<&HexStr>::try_from(&b"0a0b"[..]), Ok(borrowed));
+        assert_eq!(
+            HexString::try_from(String::from("0a0")),
+            Err(FromHexError::OddLength)
+        );
+    }
+
+    #[test]
+    fn test_partial_eq() {
+        let hex = HexStr::new("0A0b").unwrap();
+        assert!(hex == "0a0B" && "0a0b" == hex && *"0A0B" == *hex);
+        assert!(hex != "0a0c" && hex != "0a0" && hex != "0x0b" && hex != "0a0b00");
+        let bytes: &[u8] = &[0x0a, 0x0b];
+        assert!(*hex == *bytes);
+        assert!(*bytes == *hex);
+        assert!(*hex != [0x0a][..] && *hex != [0x0a, 0x0b, 0x0c][..]);
+
+        #[cfg(feature = "alloc")]
+        {
+            let owned = hex.to_owned();
+            assert!(owned == "0A0B" && "0a0b" == owned);
+            assert!(owned == *bytes);
+            assert!(*bytes == owned);
+            assert!(owned != "zz");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_fmt_hex() {
+        use alloc::format;
+
+        let borrowed = HexStr::new("0A0b").unwrap();
+        assert_eq!(format!("{:x}|{:X}", borrowed, borrowed), "0a0b|0A0B");
+        assert_eq!(format!("{:#08x}", borrowed), "0x000a0b");
+        assert_eq!(format!("{:>7X}", borrowed), "   0A0B");
+
+        let owned = borrowed.to_owned();
+        assert_eq!(format!("{:#x}|{:#X}", owned, owned), "0x0a0b|0x0A0B");
+
+        let large = HexString::encode([0xab; 100]);
+        assert_eq!(format!("{:X}", large), "AB".repeat(100));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_builder() {
+        assert_eq!(HexStringBuilder::new().finish().as_str(), "");
+
+        let mut builder = HexStringBuilder::with_capacity(6);
+        builder.push_bytes([]).push_byte(0xAB);
+        builder.push_hex(HexStr::new("CdEf").unwrap());
+        builder.push_bytes(b"\x00\xff");
+        assert_eq!(builder.as_bytes_len(), 5);
+        let hex = builder.finish();
+        assert_eq!(hex.as_str(), "abcdef00ff");
+        assert_eq!(hex, HexString::new("ABCDEF00FF").unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_encode() {
+        let hex = HexString::encode(b"foobar");
+        assert_eq!(hex, "666F6F626172".parse::<HexString>().unwrap());
+        assert_eq!(String::from(hex), "666f6f626172");
+    }
+}
@@ -0,0 +1,117 @@
+// Decoding eight digits at a time in a `u64`, for targets without vector
+// units.
+//
+// All eight bytes are range checked and converted with a few word-wide
+// operations, instead of a table lookup per byte. Like the vector paths,
+// this returns how many bytes it decoded, stopping at the first word
+// containing an invalid character and leaving it to the scalar loop to
+// report.
+//
+// The `force-scalar` feature compiles this out.
+use core::convert::TryInto;
+use core::mem::MaybeUninit;
+
+const ONES: u64 = 0x0101_0101_0101_0101;
+const HIGH_BITS: u64 = 0x8080_8080_8080_8080;
+
+// Returns the high bit of every byte in `word` which is in `low..=high`,
+// assuming all bytes are ASCII, so no carry crosses into the next byte.
+#[inline(always)]
+fn in_range(word: u64, low: u8, high: u8) -> u64 {
+    let at_least_low = word + (0x80 - u64::from(low)) * ONES;
+    let above_high = word + (0x7f - u64::from(high)) * ONES;
+    at_least_low & !above_high & HIGH_BITS
+}
+
+// Decodes eight digits, or returns `None` if any of them is invalid.
+#[inline(always)]
+fn decode_word(word: u64) -> Option<u32> {
+    if word & HIGH_BITS != 0 {
+        return None;
+    }
+    let decimal = in_range(word, b'0', b'9');
+    let letter = in_range(word | (0x20 * ONES), b'a', b'f');
+    if decimal | letter != HIGH_BITS {
+        return None;
+    }
+    // The value of a digit is its low nibble, plus 9 for letters.
+    let digits = (word & (0x0f * ONES)) + (letter >> 7) * 9;
+    // The first digit of each pair is in the low byte of a 16 bit lane.
+    let pairs = ((digits << 4) | (digits >> 8)) & 0x00ff_00ff_00ff_00ff;
+    let pairs = (pairs | (pairs >> 8)) & 0x0000_ffff_0000_ffff;
+    Some((pairs | (pairs >> 16)) as u32)
+}
+
+// Decodes blocks of 8 digits, returning the number of bytes decoded.
+#[inline]
+pub(crate) fn decode(hex: &[u8], out: &mut [MaybeUninit<u8>]) -> usize {
+    debug_assert_eq!(hex.len(), 2 * out.len());
+    let mut done = 0;
+    for (chunk, out) in hex.chunks_exact(8).zip(out.chunks_exact_mut(4)) {
+        let word = u64::from_le_bytes(chunk.try_into().unwrap());
+        match decode_word(word) {
+            Some(bytes) => crate::write_slice(out, &bytes.to_le_bytes()),
+            None => break,
+        }
+        done += 4;
+    }
+    done
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FromHexError;
+    use pretty_assertions::assert_eq;
+
+    fn decode_init(hex: &[u8], out: &mut [u8]) -> usize {
+        // Saftey: decoding only writes initialized bytes.
+        decode(hex, unsafe { crate::as_uninit(out) })
+    }
+
+    #[test]
+    fn test_decode() {
+        let mut data = [0; 64];
+        for (i, byte) in data.iter_mut().enumerate() {
+            *byte = (i * 37) as u8;
+        }
+        for len in [0, 3, 4, 5, 8, 64] {
+            let mut hex = [0; 128];
+            crate::encode_to_slice(&data[..len], &mut hex[..2 * len]).unwrap();
+            let mut out = [0; 64];
+            assert_eq!(decode_init(&hex[..2 * len], &mut out[..len]), len - len % 4);
+            assert_eq!(&out[..len - len % 4], &data[..len - len % 4]);
+
+            crate::encode_to_slice_upper(&data[..len], &mut hex[..2 * len]).unwrap();
+            assert_eq!(decode_init(&hex[..2 * len], &mut out[..len]), len - len % 4);
+            assert_eq!(&out[..len - len % 4], &data[..len - len % 4]);
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        // every byte value, in each position of a word
+        for c in 0..=255_u8 {
+            let valid = crate::DECODE_TABLE[c as usize] != crate::tables::INVALID;
+            for i in 0..16 {
+                let mut hex = *b"0123456789abcdef";
+                hex[i] = c;
+                let mut out = [0; 8];
+                let done = decode_init(&hex, &mut out);
+                if valid {
+                    assert_eq!(done, 8);
+                    assert_eq!(out[i / 2], crate::val(&hex[i & !1..][..2], 0).unwrap());
+                } else {
+                    assert_eq!(done, i / 8 * 4, "{} {}", c, i);
+                    assert_eq!(
+                        crate::decode_to_slice(hex, &mut out),
+                        Err(FromHexError::InvalidHexCharacter {
+                            c: c as char,
+                            index: i
+                        })
+                    );
+                }
+            }
+        }
+    }
+}
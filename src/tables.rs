@@ -0,0 +1,175 @@
+//! The lookup tables used for encoding and decoding.
+//!
+//! These are exposed so crates writing fused parsers or their own SIMD
+//! kernels can reuse the tables instead of duplicating them.
+
+/// The hex digits in lowercase, indexed by their value.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::tables::HEX_CHARS_LOWER[0xb], b'b');
+/// ```
+pub const HEX_CHARS_LOWER: &[u8; 16] = b"0123456789abcdef";
+
+/// The hex digits in uppercase, indexed by their value.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(hex::tables::HEX_CHARS_UPPER[0xb], b'B');
+/// ```
+pub const HEX_CHARS_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+// Builds a table of the two digits encoding each byte, in memory order.
+const fn pairs(digits: &[u8; 16]) -> [u16; 256] {
+    let mut pairs = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        pairs[i] = u16::from_ne_bytes([digits[i >> 4], digits[i & 0xf]]);
+        i += 1;
+    }
+    pairs
+}
+
+/// The two lowercase digits encoding each byte, as a `u16` which has them in
+/// order in memory.
+///
+/// This lets encoders write a byte with a single lookup and store.
+///
+/// # Example
+///
+/// ```
+/// use hex::tables::ENCODE_PAIRS_LOWER;
+///
+/// assert_eq!(ENCODE_PAIRS_LOWER[0xab].to_ne_bytes(), *b"ab");
+/// ```
+pub static ENCODE_PAIRS_LOWER: [u16; 256] = pairs(HEX_CHARS_LOWER);
+
+/// The two uppercase digits encoding each byte, as a `u16` which has them in
+/// order in memory.
+///
+/// # Example
+///
+/// ```
+/// use hex::tables::ENCODE_PAIRS_UPPER;
+///
+/// assert_eq!(ENCODE_PAIRS_UPPER[0xab].to_ne_bytes(), *b"AB");
+/// ```
+pub static ENCODE_PAIRS_UPPER: [u16; 256] = pairs(HEX_CHARS_UPPER);
+
+/// The value [`DECODE_TABLE`] holds for characters which aren't hex digits.
+pub const INVALID: u8 = u8::MAX;
+
+const __: u8 = INVALID;
+
+/// Maps ASCII characters to the value of the hex digit they represent, or
+/// [`INVALID`] if they aren't a hex digit.
+///
+/// Both uppercase and lowercase letters are mapped. Bytes above `0x7f` are
+/// covered as well, so any `u8` can be used as index without a bounds check.
+///
+/// # Example
+///
+/// ```
+/// use hex::tables::{DECODE_TABLE, INVALID};
+///
+/// assert_eq!(DECODE_TABLE[usize::from(b'B')], 11);
+/// assert_eq!(DECODE_TABLE[usize::from(b'g')], INVALID);
+/// ```
+#[rustfmt::skip]
+pub static DECODE_TABLE: [u8; 256] = [
+    //   1   2   3   4   5   6   7   8   9   a   b   c   d   e   f
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 0
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 1
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 2
+     0,  1,  2,  3,  4,  5,  6,  7,  8,  9, __, __, __, __, __, __, // 3
+    __, 10, 11, 12, 13, 14, 15, __, __, __, __, __, __, __, __, __, // 4
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 5
+    __, 10, 11, 12, 13, 14, 15, __, __, __, __, __, __, __, __, __, // 6
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 7
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 8
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 9
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // a
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // b
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // c
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // d
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // e
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // f
+];
+
+// Builds a table decoding each pair of characters, in memory order.
+#[cfg(feature = "pair-table")]
+const fn decode_pairs() -> [u16; 65536] {
+    let mut pairs = [u16::MAX; 65536];
+    let mut i = 0;
+    while i < 65536 {
+        let [high, low] = (i as u16).to_ne_bytes();
+        let high = DECODE_TABLE[high as usize];
+        let low = DECODE_TABLE[low as usize];
+        if high != INVALID && low != INVALID {
+            pairs[i] = ((high << 4) | low) as u16;
+        }
+        i += 1;
+    }
+    pairs
+}
+
+/// Maps two ASCII characters, read as a `u16` in memory order, to the byte
+/// they encode, or `u16::MAX` if either isn't a hex digit.
+///
+/// This decodes a byte with a single lookup, instead of one for each digit,
+/// at the cost of 128 KiB. It is only compiled in with the `pair-table`
+/// feature.
+///
+/// # Example
+///
+/// ```
+/// use hex::tables::DECODE_PAIRS;
+///
+/// assert_eq!(DECODE_PAIRS[usize::from(u16::from_ne_bytes(*b"aB"))], 0xab);
+/// assert_eq!(DECODE_PAIRS[usize::from(u16::from_ne_bytes(*b"ag"))], u16::MAX);
+/// ```
+#[cfg(feature = "pair-table")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pair-table")))]
+pub static DECODE_PAIRS: [u16; 65536] = decode_pairs();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_tables() {
+        for (i, &digit) in DECODE_TABLE.iter().enumerate() {
+            let expected = (i as u8 as char).to_digit(16).map_or(INVALID, |d| d as u8);
+            assert_eq!(digit, expected, "{:#x}", i);
+            if digit != INVALID {
+                assert!(
+                    HEX_CHARS_LOWER[digit as usize] == i as u8
+                        || HEX_CHARS_UPPER[digit as usize] == i as u8
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pairs() {
+        for byte in 0..=255_u8 {
+            let (high, low) = crate::byte2hex(byte, HEX_CHARS_LOWER);
+            assert_eq!(ENCODE_PAIRS_LOWER[byte as usize].to_ne_bytes(), [high, low]);
+            let (high, low) = crate::byte2hex(byte, HEX_CHARS_UPPER);
+            assert_eq!(ENCODE_PAIRS_UPPER[byte as usize].to_ne_bytes(), [high, low]);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "pair-table")]
+    fn test_decode_pairs() {
+        for (i, &decoded) in DECODE_PAIRS.iter().enumerate() {
+            let pair = (i as u16).to_ne_bytes();
+            let expected = crate::val(&pair, 0).map_or(u16::MAX, u16::from);
+            assert_eq!(decoded, expected, "{:?}", pair);
+        }
+    }
+}
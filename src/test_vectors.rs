@@ -0,0 +1,254 @@
+//! A parser for `key = value` style test-vector files.
+//!
+//! This covers the text formats used by NIST CAVP (`.rsp`), and the many
+//! suites modelled after them:
+//!
+//! - lines starting with `#` are comments,
+//! - `[name]` and `[name = value]` lines set parameters, which apply to all
+//!   following records until the next group of parameters,
+//! - `key = value` lines add fields to the current record,
+//! - records are separated by blank lines.
+//!
+//! Values are decoded as hex up front, where possible.
+//!
+//! # Example
+//!
+//! ```
+//! let input = "\
+//! ## SHA-1 ShortMsg
+//! [L = 20]
+//!
+//! Len = 0
+//! Msg = 00
+//! MD = da39a3ee5e6b4b0d3255bfef95601890afd80709
+//!
+//! Len = 8
+//! Msg = 36
+//! MD = c1dfd96eea8cc2b62785275bca38ac261256e278
+//! ";
+//!
+//! let records = hex::test_vectors::parse(input).collect::<Result<Vec<_>, _>>()?;
+//! assert_eq!(records.len(), 2);
+//! assert_eq!(records[1].param("L"), Some("20"));
+//! assert_eq!(records[1].value("Len"), Some("8"));
+//! assert_eq!(records[1].bytes("Msg"), Some(&[0x36][..]));
+//! # Ok::<(), hex::test_vectors::ParseError>(())
+//! ```
+use alloc::vec::Vec;
+use core::{fmt, str::Lines};
+
+use crate::FromHex;
+
+/// A single `key = value` entry of a test-vector file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field<'a> {
+    /// The key, with surrounding whitespace removed.
+    pub key: &'a str,
+    /// The raw value, with surrounding whitespace removed.
+    pub value: &'a str,
+    /// The value decoded as hex, or `None` if it isn't valid hex.
+    pub bytes: Option<Vec<u8>>,
+}
+
+impl<'a> Field<'a> {
+    fn new(key: &'a str, value: &'a str) -> Self {
+        let (key, value) = (key.trim(), value.trim());
+        Field {
+            key,
+            value,
+            bytes: Vec::from_hex(value).ok(),
+        }
+    }
+}
+
+/// A record of a test-vector file, along with the parameters in effect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record<'a> {
+    /// The 1-based line number the record starts at.
+    pub line: usize,
+    /// The parameters set by the preceding `[...]` lines.
+    pub params: Vec<Field<'a>>,
+    /// The fields of this record, in order.
+    pub fields: Vec<Field<'a>>,
+}
+
+impl<'a> Record<'a> {
+    /// Returns the first field called `key`.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Field<'a>> {
+        self.fields.iter().find(|field| field.key == key)
+    }
+
+    /// Returns the raw value of the first field called `key`.
+    #[must_use]
+    pub fn value(&self, key: &str) -> Option<&'a str> {
+        self.get(key).map(|field| field.value)
+    }
+
+    /// Returns the decoded value of the first field called `key`, if it is
+    /// valid hex.
+    #[must_use]
+    pub fn bytes(&self, key: &str) -> Option<&[u8]> {
+        self.get(key).and_then(|field| field.bytes.as_deref())
+    }
+
+    /// Returns the raw value of the parameter called `key`.
+    ///
+    /// Parameters without a value (e.g. `[ENCRYPT]`) have an empty value.
+    #[must_use]
+    pub fn param(&self, key: &str) -> Option<&'a str> {
+        self.params
+            .iter()
+            .find(|field| field.key == key)
+            .map(|field| field.value)
+    }
+}
+
+/// The error returned when a line cannot be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError {
+    /// The 1-based number of the offending line.
+    pub line: usize,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid test vector syntax on line {}", self.line)
+    }
+}
+
+/// Parses the records of a test-vector file.
+pub fn parse(input: &str) -> Records<'_> {
+    Records {
+        lines: input.lines(),
+        line: 0,
+        params: Vec::new(),
+        params_done: true,
+    }
+}
+
+/// An iterator over the records of a test-vector file.
+///
+/// This struct is created by [`parse`].
+#[derive(Debug, Clone)]
+pub struct Records<'a> {
+    lines: Lines<'a>,
+    line: usize,
+    params: Vec<Field<'a>>,
+    // Whether a record was read since the last `[...]` line, in which case the
+    // next one starts a new group of parameters.
+    params_done: bool,
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = Result<Record<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record: Option<Record<'a>> = None;
+        for line in &mut self.lines {
+            self.line += 1;
+            let line = line.trim();
+
+            if line.is_empty() {
+                if record.is_some() {
+                    break;
+                }
+            } else if line.starts_with('#') {
+                continue;
+            } else if let Some(param) = line.strip_prefix('[') {
+                let param = match param.strip_suffix(']') {
+                    Some(param) => param,
+                    None => return Some(Err(ParseError { line: self.line })),
+                };
+                if record.is_some() {
+                    return Some(Err(ParseError { line: self.line }));
+                }
+                if self.params_done {
+                    self.params.clear();
+                    self.params_done = false;
+                }
+                let (key, value) = param.split_once('=').unwrap_or((param, ""));
+                self.params.push(Field::new(key, value));
+            } else if let Some((key, value)) = line.split_once('=') {
+                self.params_done = true;
+                let line = self.line;
+                let params = &self.params;
+                record
+                    .get_or_insert_with(|| Record {
+                        line,
+                        params: params.clone(),
+                        fields: Vec::new(),
+                    })
+                    .fields
+                    .push(Field::new(key, value));
+            } else {
+                return Some(Err(ParseError { line: self.line }));
+            }
+        }
+        record.map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use pretty_assertions::assert_eq;
+
+    const INPUT: &str = "\
+# CAVS 11.1
+# Config info for aes_values
+
+[ENCRYPT]
+[Keylen = 128]
+
+COUNT = 0
+KEY = 00000000000000000000000000000000
+PLAINTEXT = f34481ec3cc627bacd5dc3fb08f273e6
+
+COUNT = 1
+KEY=10a58869d74be5a374cf867cfb473859
+PLAINTEXT =
+
+[DECRYPT]
+
+COUNT = 0
+CIPHERTEXT = 0336763e966d92595a567cc9ce537f5e
+";
+
+    #[test]
+    fn test_parse() {
+        let records = parse(INPUT).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records.len(), 3);
+
+        assert_eq!(records[0].line, 7);
+        assert_eq!(records[0].param("ENCRYPT"), Some(""));
+        assert_eq!(records[0].param("Keylen"), Some("128"));
+        assert_eq!(records[0].value("COUNT"), Some("0"));
+        assert_eq!(records[0].bytes("COUNT"), None);
+        assert_eq!(records[0].bytes("KEY"), Some(&[0; 16][..]));
+
+        assert_eq!(
+            records[1].value("KEY"),
+            Some("10a58869d74be5a374cf867cfb473859")
+        );
+        assert_eq!(records[1].bytes("PLAINTEXT"), Some(&[][..]));
+        assert_eq!(records[1].params, records[0].params);
+
+        assert_eq!(records[2].params, vec![Field::new("DECRYPT", "")]);
+        assert_eq!(records[2].param("Keylen"), None);
+        assert_eq!(records[2].fields.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let mut records = parse("COUNT = 0\nthis is not a field\n");
+        assert_eq!(records.next(), Some(Err(ParseError { line: 2 })));
+
+        let mut records = parse("[L = 20\n");
+        assert_eq!(records.next(), Some(Err(ParseError { line: 1 })));
+    }
+}
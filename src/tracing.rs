@@ -0,0 +1,36 @@
+//! Recording bytes as hex in [`tracing`](::tracing) spans and events.
+//!
+//! # Example
+//!
+//! ```
+//! let payload = [0xde, 0xad, 0xbe, 0xef];
+//!
+//! tracing::debug!(payload = hex::tracing::field(&payload), "received packet");
+//! ```
+use ::tracing::field::{display, DisplayValue};
+
+use crate::Hex;
+
+/// Wraps `data` so it is recorded as lowercase hex.
+///
+/// Nothing is encoded up front: the hex is only written when a subscriber
+/// actually records the field, so this is free when the span or event is
+/// disabled. This is in contrast to `field = hex::encode(data)`, which always
+/// allocates and encodes.
+pub fn field<T: AsRef<[u8]>>(data: T) -> DisplayValue<Hex<T>> {
+    display(Hex(data))
+}
+
+#[cfg(test)]
+#[cfg(feature = "alloc")]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_field() {
+        assert_eq!(format!("{:?}", field(b"kiwi")), "6b697769");
+        assert_eq!(format!("{}", field([0_u8; 0])), "");
+    }
+}
@@ -0,0 +1,120 @@
+// Fully unrolled encoding and decoding for the sizes of common digests and
+// identifiers: 16 (MD5, UUID), 20 (SHA-1, addresses), 32 (SHA-256, keys) and
+// 64 (SHA-512, signatures) bytes.
+//
+// The length is a const generic, so every loop has a constant trip count and
+// is unrolled by the compiler. Decoding validates without branching on every
+// pair, and leaves reporting the exact error to the generic path.
+//
+// The `force-scalar` feature compiles this out.
+use core::convert::TryInto;
+use core::mem::MaybeUninit;
+
+use crate::DECODE_TABLE;
+
+#[inline(always)]
+fn encode_n<const N: usize>(input: &[u8], output: &mut [MaybeUninit<u8>], table: &[u8; 16]) {
+    let input: &[u8; N] = input.try_into().unwrap();
+    let output = &mut output[..2 * N];
+    for i in 0..N {
+        output[2 * i].write(table[(input[i] >> 4) as usize]);
+        output[2 * i + 1].write(table[(input[i] & 0x0f) as usize]);
+    }
+}
+
+#[inline(always)]
+fn decode_n<const N: usize>(hex: &[u8], out: &mut [MaybeUninit<u8>]) -> bool {
+    let hex = &hex[..2 * N];
+    let out: &mut [MaybeUninit<u8>; N] = out.try_into().unwrap();
+    // Valid digits decode to at most 0xf, invalid ones to 0xff.
+    let mut digits = 0;
+    for i in 0..N {
+        let high = DECODE_TABLE[hex[2 * i] as usize];
+        let low = DECODE_TABLE[hex[2 * i + 1] as usize];
+        digits |= high | low;
+        out[i].write((high << 4) | low);
+    }
+    digits <= 0x0f
+}
+
+// Encodes `input` into `output`, which is twice as long, if there is an
+// unrolled implementation for its length. Returns `false` otherwise.
+#[inline]
+pub(crate) fn encode(input: &[u8], output: &mut [MaybeUninit<u8>], table: &[u8; 16]) -> bool {
+    debug_assert_eq!(2 * input.len(), output.len());
+    match input.len() {
+        16 => encode_n::<16>(input, output, table),
+        20 => encode_n::<20>(input, output, table),
+        32 => encode_n::<32>(input, output, table),
+        64 => encode_n::<64>(input, output, table),
+        _ => return false,
+    }
+    true
+}
+
+// Decodes `hex` into `out`, which is half as long, if there is an unrolled
+// implementation for its length. Returns `false` otherwise, and if `hex`
+// contains invalid characters, in which case `out` may have been written to.
+#[inline]
+pub(crate) fn decode(hex: &[u8], out: &mut [MaybeUninit<u8>]) -> bool {
+    debug_assert_eq!(hex.len(), 2 * out.len());
+    match out.len() {
+        16 => decode_n::<16>(hex, out),
+        20 => decode_n::<20>(hex, out),
+        32 => decode_n::<32>(hex, out),
+        64 => decode_n::<64>(hex, out),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HEX_CHARS_UPPER;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_roundtrip() {
+        let mut data = [0; 64];
+        for (i, byte) in data.iter_mut().enumerate() {
+            *byte = (i * 37) as u8;
+        }
+        for len in [16, 20, 32, 64] {
+            let mut hex = [0; 128];
+            // Saftey: encoding only writes initialized bytes.
+            let output = unsafe { crate::as_uninit(&mut hex[..2 * len]) };
+            assert!(encode(&data[..len], output, HEX_CHARS_UPPER));
+            let mut expected = [0; 128];
+            crate::encode_to_slice_upper(&data[..len], &mut expected[..2 * len]).unwrap();
+            assert_eq!(&hex[..2 * len], &expected[..2 * len]);
+
+            let mut out = [0; 64];
+            // Saftey: decoding only writes initialized bytes.
+            assert!(decode(&hex[..2 * len], unsafe {
+                crate::as_uninit(&mut out[..len])
+            }));
+            assert_eq!(&out[..len], &data[..len]);
+        }
+
+        let mut out = [MaybeUninit::uninit(); 8];
+        assert!(!encode(&data[..4], &mut out, HEX_CHARS_UPPER));
+        assert!(!decode(&[b'0'; 8], &mut out[..4]));
+    }
+
+    #[test]
+    fn test_invalid() {
+        for i in [0, 1, 17, 31] {
+            let mut hex = [b'a'; 32];
+            hex[i] = b'g';
+            assert!(!decode(&hex, &mut [MaybeUninit::uninit(); 16]), "{}", i);
+            // and the generic path reports it
+            assert_eq!(
+                crate::decode_to_slice(hex, &mut [0; 16]),
+                Err(crate::FromHexError::InvalidHexCharacter { c: 'g', index: i })
+            );
+        }
+        let mut hex = [b'0'; 40];
+        hex[39] = 0xff;
+        assert!(!decode(&hex, &mut [MaybeUninit::uninit(); 20]));
+    }
+}
@@ -0,0 +1,220 @@
+//! Decoding of hex text stored as UTF-16.
+//!
+//! Windows registry exports, event logs and memory dumps often store hex
+//! digits as wide characters. The functions in this module decode such text
+//! directly, either given as code units or as raw bytes in either byte order.
+//!
+//! Indices in [`FromHexError::InvalidHexCharacter`] count code units, not
+//! including a byte order mark. Unpaired surrogates are reported as
+//! [`char::REPLACEMENT_CHARACTER`].
+//!
+//! # Example
+//!
+//! ```
+//! use hex::utf16::{self, ByteOrder};
+//!
+//! let units: Vec<u16> = "6b697769".encode_utf16().collect();
+//! let mut bytes = [0; 4];
+//! utf16::decode_to_slice(&units, &mut bytes)?;
+//! assert_eq!(&bytes, b"kiwi");
+//!
+//! // "ff" in UTF-16LE with a byte order mark
+//! let raw = [0xff, 0xfe, b'f', 0, b'f', 0];
+//! let mut byte = [0; 1];
+//! utf16::decode_bytes_to_slice(&raw, ByteOrder::BigEndian, &mut byte)?;
+//! assert_eq!(byte, [0xff]);
+//! # Ok::<(), hex::FromHexError>(())
+//! ```
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+use crate::{FromHexError, DECODE_TABLE};
+
+/// The byte order of UTF-16 text given as raw bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteOrder {
+    /// UTF-16LE, the native encoding of wide strings on Windows.
+    LittleEndian,
+    /// UTF-16BE.
+    BigEndian,
+}
+
+// Returns the value of the digit `unit`, at `index` in the input.
+fn digit(unit: u16, index: usize) -> Result<u8, FromHexError> {
+    let digit = DECODE_TABLE.get(unit as usize).copied().unwrap_or(u8::MAX);
+    if digit == u8::MAX {
+        let c = char::from_u32(unit.into()).unwrap_or(char::REPLACEMENT_CHARACTER);
+        return Err(FromHexError::InvalidHexCharacter { c, index });
+    }
+    Ok(digit)
+}
+
+// Decodes the code units yielded by `units` into `out`, which holds `len`
+// code units.
+fn decode_units<I: Iterator<Item = u16>>(
+    mut units: I,
+    len: usize,
+    out: &mut [u8],
+) -> Result<(), FromHexError> {
+    if crate::decoded_len(len)? != out.len() {
+        return Err(FromHexError::InvalidStringLength);
+    }
+    for (i, byte) in out.iter_mut().enumerate() {
+        let (high, low) = (units.next().unwrap(), units.next().unwrap());
+        *byte = (digit(high, 2 * i)? << 4) | digit(low, 2 * i + 1)?;
+    }
+    Ok(())
+}
+
+/// Decodes hex given as UTF-16 code units into a mutable bytes slice.
+///
+/// `out` has to be exactly half as long as `units`, otherwise
+/// [`FromHexError::InvalidStringLength`] is returned.
+pub fn decode_to_slice(units: &[u16], out: &mut [u8]) -> Result<(), FromHexError> {
+    decode_units(units.iter().copied(), units.len(), out)
+}
+
+/// Decodes hex given as UTF-16 code units.
+///
+/// # Example
+///
+/// ```
+/// let units: Vec<u16> = "C0FFEE".encode_utf16().collect();
+/// assert_eq!(hex::utf16::decode(&units), Ok(vec![0xc0, 0xff, 0xee]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode(units: &[u16]) -> Result<Vec<u8>, FromHexError> {
+    let mut out = vec![0; crate::decoded_len(units.len())?];
+    decode_to_slice(units, &mut out)?;
+    Ok(out)
+}
+
+// Strips a byte order mark from `bytes`, returning the remaining bytes and
+// their byte order.
+fn strip_bom(bytes: &[u8], order: ByteOrder) -> (&[u8], ByteOrder) {
+    match bytes {
+        [0xff, 0xfe, rest @ ..] => (rest, ByteOrder::LittleEndian),
+        [0xfe, 0xff, rest @ ..] => (rest, ByteOrder::BigEndian),
+        _ => (bytes, order),
+    }
+}
+
+/// Decodes hex given as raw UTF-16 bytes into a mutable bytes slice.
+///
+/// A leading byte order mark is skipped and takes precedence over `order`.
+/// An odd number of bytes is rejected with
+/// [`FromHexError::InvalidStringLength`], as is an `out` which isn't exactly
+/// a quarter as long as the text without byte order mark.
+pub fn decode_bytes_to_slice(
+    bytes: &[u8],
+    order: ByteOrder,
+    out: &mut [u8],
+) -> Result<(), FromHexError> {
+    let (bytes, order) = strip_bom(bytes, order);
+    if bytes.len() % 2 != 0 {
+        return Err(FromHexError::InvalidStringLength);
+    }
+    let units = bytes.chunks_exact(2).map(|unit| match order {
+        ByteOrder::LittleEndian => u16::from_le_bytes([unit[0], unit[1]]),
+        ByteOrder::BigEndian => u16::from_be_bytes([unit[0], unit[1]]),
+    });
+    decode_units(units, bytes.len() / 2, out)
+}
+
+/// Decodes hex given as raw UTF-16 bytes.
+///
+/// This works like [`decode_bytes_to_slice`], but allocates the output.
+///
+/// # Example
+///
+/// ```
+/// use hex::utf16::ByteOrder;
+///
+/// let raw = [0, b'0', 0, b'A'];
+/// assert_eq!(hex::utf16::decode_bytes(&raw, ByteOrder::BigEndian), Ok(vec![0x0a]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_bytes(bytes: &[u8], order: ByteOrder) -> Result<Vec<u8>, FromHexError> {
+    let len = strip_bom(bytes, order).0.len();
+    let mut out = vec![0; len / 4];
+    decode_bytes_to_slice(bytes, order, &mut out)?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_decode_units() {
+        let mut out = [0; 2];
+        let units = [0x0030, 0x0031, 0x0061, 0x0046];
+        assert_eq!(decode_to_slice(&units, &mut out), Ok(()));
+        assert_eq!(out, [0x01, 0xaf]);
+
+        assert_eq!(
+            decode_to_slice(&units[..3], &mut out),
+            Err(FromHexError::OddLength)
+        );
+        assert_eq!(
+            decode_to_slice(&units[..2], &mut out),
+            Err(FromHexError::InvalidStringLength)
+        );
+        // fullwidth digit one
+        assert_eq!(
+            decode_to_slice(&[0x0030, 0xff11], &mut out[..1]),
+            Err(FromHexError::InvalidHexCharacter { c: '１', index: 1 })
+        );
+        assert_eq!(
+            decode_to_slice(&[0xd800, 0x0030], &mut out[..1]),
+            Err(FromHexError::InvalidHexCharacter {
+                c: char::REPLACEMENT_CHARACTER,
+                index: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_bytes() {
+        let mut out = [0; 1];
+        for (bytes, order) in [
+            (&b"a\0b\0"[..], ByteOrder::LittleEndian),
+            (&b"\0a\0b"[..], ByteOrder::BigEndian),
+            (&b"\xff\xfea\0b\0"[..], ByteOrder::BigEndian),
+            (&b"\xfe\xff\0a\0b"[..], ByteOrder::LittleEndian),
+        ] {
+            assert_eq!(decode_bytes_to_slice(bytes, order, &mut out), Ok(()));
+            assert_eq!(out, [0xab]);
+        }
+
+        assert_eq!(
+            decode_bytes_to_slice(b"a\0b", ByteOrder::LittleEndian, &mut out),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            decode_bytes_to_slice(b"a\0b\0", ByteOrder::BigEndian, &mut out),
+            Err(FromHexError::InvalidHexCharacter {
+                c: '\u{6100}',
+                index: 0
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_alloc() {
+        use alloc::vec;
+
+        assert_eq!(decode(&[0x0066, 0x0066]), Ok(vec![0xff]));
+        assert_eq!(decode(&[0x0066]), Err(FromHexError::OddLength));
+        assert_eq!(
+            decode_bytes(b"\xff\xfef\0f\0", ByteOrder::BigEndian),
+            Ok(vec![0xff])
+        );
+        assert_eq!(
+            decode_bytes(b"f\0f", ByteOrder::LittleEndian),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Parsers for hex fields in grammars written with the `winnow` crate.
+//!
+//! The parsers work on any [`Stream`] whose slices are bytes or strings, such
+//! as `&str`, `&[u8]` and their [`Partial`] variants. Invalid digits and odd
+//! lengths are reported as a [`FromHexError`] through
+//! [`FromExternalError`], at the start of the hex field, like
+//! [`Parser::try_map`].
+//!
+//! # Example
+//!
+//! ```
+//! use winnow::{error::ContextError, Parser};
+//!
+//! // A record like "<16 bit id>:<key>;"
+//! let mut input = "00ff:cafebabe;";
+//! let (id, _, key, _) = (
+//!     hex::winnow::hex_bytes::<_, ContextError>(2),
+//!     ':',
+//!     hex::winnow::hex_until(';'),
+//!     ';',
+//! )
+//!     .parse_next(&mut input)?;
+//! assert_eq!(id, [0x00, 0xff]);
+//! assert_eq!(key, [0xca, 0xfe, 0xba, 0xbe]);
+//! assert_eq!(input, "");
+//! # Ok::<(), ContextError>(())
+//! ```
+//!
+//! [`Partial`]: winnow::Partial
+use alloc::vec::Vec;
+
+use winnow::{
+    error::{FromExternalError, ParserError},
+    stream::{ContainsToken, Stream, StreamIsPartial},
+    token::{take, take_till},
+    Parser,
+};
+
+use crate::FromHexError;
+
+/// Parses `len` bytes encoded as `2 * len` hex digits.
+///
+/// # Example
+///
+/// ```
+/// use winnow::{error::ContextError, Parser};
+///
+/// let mut input = &b"6b697769 and more"[..];
+/// let kiwi = hex::winnow::hex_bytes::<_, ContextError>(4).parse_next(&mut input)?;
+/// assert_eq!(kiwi, b"kiwi");
+/// assert_eq!(input, b" and more");
+/// # Ok::<(), ContextError>(())
+/// ```
+pub fn hex_bytes<I, E>(len: usize) -> impl Parser<I, Vec<u8>, E>
+where
+    I: Stream + StreamIsPartial,
+    I::Slice: AsRef<[u8]>,
+    E: ParserError<I> + FromExternalError<I, FromHexError>,
+{
+    take(2 * len).try_map(crate::decode)
+}
+
+/// Parses hex digits up to `delim` or the end of the input, without
+/// consuming the delimiter.
+///
+/// On a [`Partial`] stream, more input is requested until the delimiter is
+/// found. Any other character before it is an error, so this can't be used
+/// to skip over unrelated data.
+///
+/// # Example
+///
+/// ```
+/// use winnow::{error::ContextError, Parser};
+///
+/// let mut input = "6b697769,";
+/// let kiwi = hex::winnow::hex_until::<_, _, ContextError>(',').parse_next(&mut input)?;
+/// assert_eq!(kiwi, b"kiwi");
+/// assert_eq!(input, ",");
+/// # Ok::<(), ContextError>(())
+/// ```
+///
+/// [`Partial`]: winnow::Partial
+pub fn hex_until<I, T, E>(delim: T) -> impl Parser<I, Vec<u8>, E>
+where
+    I: Stream + StreamIsPartial,
+    I::Slice: AsRef<[u8]>,
+    T: ContainsToken<I::Token>,
+    E: ParserError<I> + FromExternalError<I, FromHexError>,
+{
+    take_till(0.., delim).try_map(crate::decode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use pretty_assertions::assert_eq;
+    use winnow::{
+        error::{ContextError, ErrMode, Needed},
+        Partial,
+    };
+
+    #[test]
+    fn test_hex_bytes() {
+        let mut parser = hex_bytes::<_, ContextError>(2);
+        assert_eq!(parser.parse_peek("abcd"), Ok(("", vec![0xab, 0xcd])));
+        assert_eq!(parser.parse_peek("ABCDEF"), Ok(("EF", vec![0xab, 0xcd])));
+        assert!(parser.parse_peek("abc").is_err());
+        // the error is at the start of the field
+        let mut input = "abgd";
+        assert!(parser.parse_next(&mut input).is_err());
+        assert_eq!(input, "abgd");
+
+        assert_eq!(
+            hex_bytes::<_, ContextError>(0).parse_peek(&b"ab"[..]),
+            Ok((&b"ab"[..], vec![]))
+        );
+    }
+
+    #[test]
+    fn test_hex_until() {
+        let mut parser = hex_until::<_, _, ContextError>(b';');
+        assert_eq!(
+            parser.parse_peek(&b"ab;cd"[..]),
+            Ok((&b";cd"[..], vec![0xab]))
+        );
+        assert_eq!(
+            parser.parse_peek(&b"abcd"[..]),
+            Ok((&b""[..], vec![0xab, 0xcd]))
+        );
+        assert_eq!(parser.parse_peek(&b";"[..]), Ok((&b";"[..], vec![])));
+        assert!(parser.parse_peek(&b"abc;"[..]).is_err());
+        assert!(parser.parse_peek(&b"ab cd;"[..]).is_err());
+    }
+
+    #[test]
+    fn test_partial() {
+        let mut parser = hex_until::<_, _, ErrMode<ContextError>>(';');
+        assert_eq!(
+            parser.parse_peek(Partial::new("abcd")),
+            Err(ErrMode::Incomplete(Needed::new(1)))
+        );
+        assert_eq!(
+            parser.parse_peek(Partial::new("abcd;")),
+            Ok((Partial::new(";"), vec![0xab, 0xcd]))
+        );
+
+        let mut parser = hex_bytes::<_, ErrMode<ContextError>>(2);
+        assert!(matches!(
+            parser.parse_peek(Partial::new("ab")),
+            Err(ErrMode::Incomplete(_))
+        ));
+    }
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "diesel")]
+
+use diesel::{prelude::*, sql_query, sqlite::SqliteConnection};
+use hex::{FixedHex, HexString};
+
+diesel::table! {
+    keys (id) {
+        id -> Integer,
+        name -> Text,
+        digest -> Binary,
+    }
+}
+
+#[derive(Debug, PartialEq, Queryable, Insertable)]
+#[diesel(table_name = keys)]
+struct Key {
+    id: i32,
+    name: HexString,
+    digest: FixedHex<4>,
+}
+
+fn connection() -> SqliteConnection {
+    let mut conn = SqliteConnection::establish(":memory:").unwrap();
+    sql_query(
+        "CREATE TABLE keys (id INTEGER PRIMARY KEY, name TEXT NOT NULL, digest BLOB NOT NULL)",
+    )
+    .execute(&mut conn)
+    .unwrap();
+    conn
+}
+
+#[test]
+fn roundtrip() {
+    let mut conn = connection();
+    let key = Key {
+        id: 1,
+        name: HexString::new("C0FFEE").unwrap(),
+        digest: FixedHex(*b"kiwi"),
+    };
+    diesel::insert_into(keys::table)
+        .values(&key)
+        .execute(&mut conn)
+        .unwrap();
+
+    assert_eq!(keys::table.first::<Key>(&mut conn).unwrap(), key);
+    assert_eq!(
+        keys::table.select(keys::name).first::<String>(&mut conn),
+        Ok("c0ffee".to_string())
+    );
+}
+
+#[test]
+fn invalid_values() {
+    let mut conn = connection();
+    sql_query("INSERT INTO keys VALUES (1, 'kiwi', x'00')")
+        .execute(&mut conn)
+        .unwrap();
+
+    assert!(keys::table
+        .select(keys::name)
+        .first::<HexString>(&mut conn)
+        .is_err());
+    assert!(keys::table
+        .select(keys::digest)
+        .first::<FixedHex<4>>(&mut conn)
+        .is_err());
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "async-graphql")]
+
+use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+use hex::{FixedHex, HexString};
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn echo(&self, hex: HexString) -> HexString {
+        hex
+    }
+
+    async fn digest(&self, key: FixedHex<4>) -> FixedHex<4> {
+        key
+    }
+}
+
+async fn execute(query: &str) -> async_graphql::Response {
+    Schema::new(Query, EmptyMutation, EmptySubscription)
+        .execute(query)
+        .await
+}
+
+#[tokio::test]
+async fn roundtrip() {
+    let response = execute(r#"{ echo(hex: "C0FFEE") digest(key: "6b697769") }"#).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    assert_eq!(
+        response.data.into_json().unwrap(),
+        serde_json::json!({ "echo": "c0ffee", "digest": "6b697769" })
+    );
+}
+
+#[tokio::test]
+async fn invalid_input() {
+    for query in [
+        r#"{ echo(hex: "kiwi") }"#,
+        r#"{ echo(hex: 42) }"#,
+        r#"{ digest(key: "6b6977") }"#,
+    ] {
+        assert_eq!(execute(query).await.errors.len(), 1, "{}", query);
+    }
+}
+
+#[test]
+fn schema() {
+    let sdl = Schema::new(Query, EmptyMutation, EmptySubscription).sdl();
+    assert!(sdl.contains("scalar HexString"));
+    assert!(sdl.contains("scalar FixedHex4"));
+}
@@ -0,0 +1,12 @@
+static HELLO: &[u8] = hex::include_hex!("data/hello.hex");
+
+#[test]
+fn include_hex() {
+    assert_eq!(HELLO, b"Hello world!");
+}
+
+#[test]
+fn include_hex_expr() {
+    let bytes = hex::include_hex!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/hello.hex"));
+    assert_eq!(bytes, b"Hello world!");
+}
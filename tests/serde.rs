@@ -1,5 +1,5 @@
 #![cfg(all(feature = "serde", feature = "alloc"))]
-#![allow(clippy::blacklisted_name)]
+#![allow(clippy::disallowed_names)]
 
 use serde::{Deserialize, Serialize};
 
@@ -57,3 +57,57 @@ fn deserialize_upper() {
     let de: Bar = serde_json::from_str(r#"{"foo":"010A64"}"#).expect("deserialization failed");
     assert_eq!(de, bar);
 }
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Baz<'a> {
+    owned: hex::HexString,
+    #[serde(borrow)]
+    borrowed: &'a hex::HexStr,
+}
+
+#[test]
+fn hex_string_roundtrip() {
+    let json = r#"{"owned":"010A64","borrowed":"010A64"}"#;
+    let baz: Baz = serde_json::from_str(json).expect("deserialization failed");
+    assert_eq!(baz.owned.as_str(), "010a64");
+    assert_eq!(baz.borrowed.as_str(), "010A64");
+
+    let ser = serde_json::to_string(&baz).expect("serialization failed");
+    assert_eq!(ser, r#"{"owned":"010a64","borrowed":"010A64"}"#);
+
+    assert!(serde_json::from_str::<hex::HexString>(r#""010""#).is_err());
+    assert!(serde_json::from_str::<&hex::HexStr>(r#""0g""#).is_err());
+}
+
+#[test]
+fn fixed_hex_roundtrip() {
+    let hash: hex::FixedHex<3> =
+        serde_json::from_str(r#""0x010A64""#).expect("deserialization failed");
+    assert_eq!(hash, hex::FixedHex([1, 10, 100]));
+
+    let ser = serde_json::to_string(&hash).expect("serialization failed");
+    assert_eq!(ser, r#""010a64""#);
+
+    assert!(serde_json::from_str::<hex::FixedHex<4>>(r#""010a64""#).is_err());
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Qux {
+    bytes: hex::Hex<Vec<u8>>,
+    array: hex::Hex<[u8; 2]>,
+}
+
+#[test]
+fn hex_wrapper_roundtrip() {
+    let qux = Qux {
+        bytes: hex::Hex(vec![1, 10, 100]),
+        array: hex::Hex([0xab, 0xcd]),
+    };
+
+    let ser = serde_json::to_string(&qux).expect("serialization failed");
+    assert_eq!(ser, r#"{"bytes":"010a64","array":"abcd"}"#);
+
+    let de: Qux = serde_json::from_str(r#"{"bytes":"010A64","array":"ABCD"}"#)
+        .expect("deserialization failed");
+    assert_eq!(de, qux);
+}
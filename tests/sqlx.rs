@@ -0,0 +1,56 @@
+#![cfg(feature = "sqlx")]
+
+use hex::{FixedHex, HexString};
+use sqlx::{Connection, SqliteConnection};
+
+async fn connection() -> SqliteConnection {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    sqlx::query("CREATE TABLE keys (name TEXT NOT NULL, digest BLOB NOT NULL)")
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    conn
+}
+
+#[tokio::test]
+async fn roundtrip() {
+    let mut conn = connection().await;
+    let name = HexString::new("C0FFEE").unwrap();
+    let digest = FixedHex(*b"kiwi");
+    sqlx::query("INSERT INTO keys VALUES (?, ?)")
+        .bind(&name)
+        .bind(digest)
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+    let row: (HexString, FixedHex<4>) = sqlx::query_as("SELECT name, digest FROM keys")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(row, (name, digest));
+
+    let raw: (String, Vec<u8>) = sqlx::query_as("SELECT name, digest FROM keys")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(raw, ("c0ffee".to_string(), b"kiwi".to_vec()));
+}
+
+#[tokio::test]
+async fn invalid_values() {
+    let mut conn = connection().await;
+    sqlx::query("INSERT INTO keys VALUES ('kiwi', x'00')")
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+    let name = sqlx::query_scalar::<_, HexString>("SELECT name FROM keys")
+        .fetch_one(&mut conn)
+        .await;
+    assert!(name.is_err());
+    let digest = sqlx::query_scalar::<_, FixedHex<4>>("SELECT digest FROM keys")
+        .fetch_one(&mut conn)
+        .await;
+    assert!(digest.is_err());
+}